@@ -0,0 +1,18 @@
+-- Migration 008: Per-language BM25 indexing
+
+-- Language the document was analyzed with (ISO 639-1; NULL = indexed before 008, treated as English)
+ALTER TABLE documents ADD COLUMN language TEXT;
+
+-- Pre-stemmed text for non-English documents. Stemming and stop-word removal
+-- happen in Rust (Snowball), so the FTS tokenizer only splits and folds case.
+CREATE VIRTUAL TABLE IF NOT EXISTS chunks_fts_stemmed USING fts5(
+    chunk_id UNINDEXED,
+    language UNINDEXED,
+    chunk_text,
+    section_header,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS chunks_fts_stemmed_delete AFTER DELETE ON chunks BEGIN
+    DELETE FROM chunks_fts_stemmed WHERE chunk_id = old.chunk_id;
+END;
@@ -0,0 +1,67 @@
+-- Migration 009: Field-weighted BM25
+-- Rebuilds the FTS tables with separate title, path and breadcrumb columns so
+-- bm25() column weights can favour title/path matches over body mentions.
+
+-- Display title (first heading or file name), set on ingest
+ALTER TABLE documents ADD COLUMN title TEXT;
+
+DROP TRIGGER IF EXISTS chunks_fts_insert;
+DROP TRIGGER IF EXISTS chunks_fts_delete;
+DROP TRIGGER IF EXISTS chunks_fts_update;
+DROP TABLE IF EXISTS chunks_fts;
+
+-- Column order matters: bm25() weights are passed positionally
+CREATE VIRTUAL TABLE chunks_fts USING fts5(
+    chunk_id UNINDEXED,
+    chunk_text,
+    breadcrumb,
+    title,
+    path,
+    tokenize = 'porter unicode61'
+);
+
+INSERT INTO chunks_fts(chunk_id, chunk_text, breadcrumb, title, path)
+SELECT c.chunk_id, c.chunk_text, c.section_header, d.title, d.doc_path
+FROM chunks c
+JOIN documents d ON c.doc_id = d.doc_id;
+
+CREATE TRIGGER chunks_fts_insert AFTER INSERT ON chunks BEGIN
+    INSERT INTO chunks_fts(chunk_id, chunk_text, breadcrumb, title, path)
+    SELECT new.chunk_id, new.chunk_text, new.section_header, d.title, d.doc_path
+    FROM documents d
+    WHERE d.doc_id = new.doc_id;
+END;
+
+CREATE TRIGGER chunks_fts_delete AFTER DELETE ON chunks BEGIN
+    DELETE FROM chunks_fts WHERE chunk_id = old.chunk_id;
+END;
+
+CREATE TRIGGER chunks_fts_update AFTER UPDATE ON chunks BEGIN
+    UPDATE chunks_fts
+    SET chunk_text = new.chunk_text,
+        breadcrumb = new.section_header
+    WHERE chunk_id = new.chunk_id;
+END;
+
+CREATE TRIGGER chunks_fts_document_update AFTER UPDATE OF title, doc_path ON documents BEGIN
+    UPDATE chunks_fts
+    SET title = new.title,
+        path = new.doc_path
+    WHERE chunk_id IN (SELECT chunk_id FROM chunks WHERE doc_id = new.doc_id);
+END;
+
+-- Stemmed rows are built in Rust; recreate with the same columns and clear the
+-- file hash of non-English documents so the next incremental ingest rebuilds them
+DROP TABLE IF EXISTS chunks_fts_stemmed;
+
+CREATE VIRTUAL TABLE chunks_fts_stemmed USING fts5(
+    chunk_id UNINDEXED,
+    language UNINDEXED,
+    chunk_text,
+    breadcrumb,
+    title,
+    path,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+UPDATE documents SET file_hash = '' WHERE language IS NOT NULL AND language != 'en';
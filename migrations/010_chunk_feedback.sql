@@ -0,0 +1,15 @@
+-- Migration 010: Per-chunk relevance feedback
+-- query_logs.user_feedback keeps the aggregate (-1 | 0 | 1); individual judgements
+-- live here with the query embedding used to match similar future queries.
+
+CREATE TABLE IF NOT EXISTS chunk_feedback (
+    feedback_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    query_id TEXT NOT NULL,
+    chunk_id TEXT NOT NULL,                 -- not a FK: chunk ids survive re-ingestion
+    feedback INT NOT NULL,                  -- -1 | 1
+    query_embedding BLOB,                   -- LE f32; NULL if embedding failed
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY(query_id) REFERENCES query_logs(query_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_chunk_feedback_chunk ON chunk_feedback(chunk_id);
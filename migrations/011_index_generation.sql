@@ -0,0 +1,39 @@
+-- Migration 011: Index generation counter
+-- Bumped by triggers on every write that can change search results, so the
+-- in-memory result cache can detect changes made by any process (server, ingest, watch).
+
+CREATE TABLE IF NOT EXISTS index_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    generation INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO index_state (id, generation) VALUES (1, 0);
+
+CREATE TRIGGER IF NOT EXISTS index_generation_chunk_insert AFTER INSERT ON chunks BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS index_generation_chunk_update AFTER UPDATE ON chunks BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS index_generation_chunk_delete AFTER DELETE ON chunks BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS index_generation_document_insert AFTER INSERT ON documents BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS index_generation_document_update AFTER UPDATE ON documents BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+CREATE TRIGGER IF NOT EXISTS index_generation_document_delete AFTER DELETE ON documents BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
+
+-- Feedback changes the learned boost, so cached rankings become stale too
+CREATE TRIGGER IF NOT EXISTS index_generation_feedback_insert AFTER INSERT ON chunk_feedback BEGIN
+    UPDATE index_state SET generation = generation + 1 WHERE id = 1;
+END;
@@ -0,0 +1,27 @@
+-- Migration 012: Contextual chunk headers
+-- chunks.context_header (title/path, breadcrumb, optional summary) is prepended to the
+-- chunk text for embedding and BM25; chunk_text itself stays the original text.
+
+ALTER TABLE chunks ADD COLUMN context_header TEXT;
+ALTER TABLE documents ADD COLUMN summary TEXT;
+
+DROP TRIGGER IF EXISTS chunks_fts_insert;
+DROP TRIGGER IF EXISTS chunks_fts_update;
+
+CREATE TRIGGER chunks_fts_insert AFTER INSERT ON chunks BEGIN
+    INSERT INTO chunks_fts(chunk_id, chunk_text, breadcrumb, title, path)
+    SELECT new.chunk_id,
+        CASE WHEN new.context_header IS NULL THEN new.chunk_text
+            ELSE new.context_header || char(10) || new.chunk_text END,
+        new.section_header, d.title, d.doc_path
+    FROM documents d
+    WHERE d.doc_id = new.doc_id;
+END;
+
+CREATE TRIGGER chunks_fts_update AFTER UPDATE OF chunk_text, section_header, context_header ON chunks BEGIN
+    UPDATE chunks_fts
+    SET chunk_text = CASE WHEN new.context_header IS NULL THEN new.chunk_text
+            ELSE new.context_header || char(10) || new.chunk_text END,
+        breadcrumb = new.section_header
+    WHERE chunk_id = new.chunk_id;
+END;
@@ -0,0 +1,6 @@
+-- Migration 013: Document-level embeddings
+-- Embedding of title + summary (or title + opening text) used by the
+-- `granularity: "document"` search mode with `aggregation: "embedding"`.
+-- Cleared on re-ingest and rebuilt by `embed` / the watcher.
+
+ALTER TABLE documents ADD COLUMN embedding BLOB;
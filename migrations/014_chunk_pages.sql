@@ -0,0 +1,5 @@
+-- Migration 014: Chunk page numbers
+-- 1-based page of each chunk for paginated formats (PDF), returned with
+-- structured search results for citations. NULL for other formats.
+
+ALTER TABLE chunks ADD COLUMN page INTEGER;
@@ -0,0 +1,5 @@
+-- Migration 015: Per-document embedding switch
+-- Set from `[[ingest.rules]] embed = false` at ingest. `embed` and the watcher
+-- skip chunks and documents with embed = 0.
+
+ALTER TABLE documents ADD COLUMN embed INTEGER NOT NULL DEFAULT 1;
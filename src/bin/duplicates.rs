@@ -0,0 +1,63 @@
+//! Near-duplicate report: chunks and documents sharing copy-pasted or paraphrased content.
+
+use clap::Parser;
+use ragmcp::{db::Db, search::duplicates::{find_duplicates, format_report}, Config};
+
+/// Find near-duplicate chunks and documents using shingling/MinHash and stored embeddings.
+#[derive(Parser, Debug)]
+#[command(name = "duplicates")]
+struct Args {
+    /// Only scan this namespace
+    #[arg(long)]
+    namespace: Option<String>,
+
+    /// Only report content shared with this document
+    #[arg(long)]
+    doc: Option<String>,
+
+    /// Minimum word-shingle Jaccard similarity (default: [duplicates] text_threshold)
+    #[arg(long)]
+    text_threshold: Option<f32>,
+
+    /// Minimum embedding cosine similarity (default: [duplicates] embedding_threshold)
+    #[arg(long)]
+    embedding_threshold: Option<f32>,
+
+    /// Chunk pairs to list (default: [duplicates] max_pairs)
+    #[arg(long)]
+    max_pairs: Option<usize>,
+
+    /// Diff lines shown per pair (0 disables diffs)
+    #[arg(long, default_value = "20")]
+    diff_lines: usize,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+
+    let args = Args::parse();
+    let config = Config::load()?;
+    let db = Db::new(config.db_path());
+
+    let mut dup_config = config.duplicates.clone();
+    if let Some(threshold) = args.text_threshold {
+        dup_config.text_threshold = threshold;
+    }
+    if let Some(threshold) = args.embedding_threshold {
+        dup_config.embedding_threshold = threshold;
+    }
+
+    let start = std::time::Instant::now();
+    let mut report = find_duplicates(&db, &dup_config, args.namespace.as_deref()).await?;
+    if let Some(doc) = &args.doc {
+        report = report.involving(doc);
+    }
+
+    println!(
+        "{}",
+        format_report(&report, args.max_pairs.unwrap_or(dup_config.max_pairs), args.diff_lines)
+    );
+    println!("Scan time: {:?}", start.elapsed());
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use clap::Parser;
 use ragmcp::Config;
 use ragmcp::db::{Db, migrate};
-use ragmcp::embeddings::{OpenAIEmbedder, store_embedding};
+use ragmcp::embeddings::{
+    get_documents_without_embedding, store_document_embeddings, store_embedding, OpenAIEmbedder,
+};
+use ragmcp::ingest::contextual::contextualized_text;
 use std::path::Path;
 use anyhow::Result;
 
@@ -66,11 +69,14 @@ async fn main() -> Result<()> {
         config.embeddings.batch_size
     );
     
-    // Get chunks to embed: all chunks if --force, else only those without embeddings
+    // Get chunks to embed: all chunks if --force, else only those without embeddings.
+    // Documents excluded by `[[ingest.rules]] embed = false` are never embedded.
     let query = if args.force {
-        "SELECT chunk_id, chunk_text FROM chunks"
+        "SELECT c.chunk_id, c.chunk_text, c.context_header FROM chunks c \
+         JOIN documents d ON d.doc_id = c.doc_id WHERE d.embed = 1"
     } else {
-        "SELECT chunk_id, chunk_text FROM chunks WHERE embedding IS NULL"
+        "SELECT c.chunk_id, c.chunk_text, c.context_header FROM chunks c \
+         JOIN documents d ON d.doc_id = c.doc_id WHERE c.embedding IS NULL AND d.embed = 1"
     };
     log::info!("Querying chunks...");
     let chunks = db.with_connection(|conn| {
@@ -82,7 +88,9 @@ async fn main() -> Result<()> {
         while let Some(row) = rows.next()? {
             let chunk_id: String = row.get(0)?;
             let chunk_text: String = row.get(1)?;
-            chunks.push((chunk_id, chunk_text));
+            // Embed with the contextual header when one was stored at ingest
+            let context_header: Option<String> = row.get(2)?;
+            chunks.push((chunk_id, contextualized_text(context_header.as_deref(), &chunk_text)));
         }
         
         Ok::<Vec<(String, String)>, ragmcp::error::RagmcpError>(chunks)
@@ -92,6 +100,7 @@ async fn main() -> Result<()> {
     
     if total_chunks == 0 {
         log::info!("No chunks need embedding. All chunks already have embeddings.");
+        embed_documents(&db, &embedder, config.embeddings.batch_size, args.force).await?;
         return Ok(());
     }
     
@@ -147,5 +156,40 @@ async fn main() -> Result<()> {
         log::warn!("Failed to embed: {} chunks", failed);
     }
     
+    embed_documents(&db, &embedder, config.embeddings.batch_size, args.force).await?;
+    
+    Ok(())
+}
+
+/// Generate document-level embeddings (title + summary) for granularity="document" searches
+async fn embed_documents(db: &Db, embedder: &OpenAIEmbedder, batch_size: usize, force: bool) -> Result<()> {
+    if force {
+        db.with_connection(|conn| {
+            conn.execute("UPDATE documents SET embedding = NULL", [])?;
+            Ok::<(), ragmcp::error::RagmcpError>(())
+        }).await?;
+    }
+    let documents = get_documents_without_embedding(db, None).await?;
+    if documents.is_empty() {
+        return Ok(());
+    }
+    
+    log::info!("Embedding {} documents...", documents.len());
+    let mut stored = 0;
+    for batch in documents.chunks(batch_size) {
+        let texts: Vec<String> = batch.iter().map(|(_, text)| text.clone()).collect();
+        match embedder.embed_batch(texts).await {
+            Ok(embeddings) => {
+                let pairs: Vec<(String, Vec<f32>)> = batch
+                    .iter()
+                    .map(|(id, _)| id.clone())
+                    .zip(embeddings)
+                    .collect();
+                stored += store_document_embeddings(db, pairs).await?;
+            }
+            Err(e) => log::error!("Failed to generate document embeddings for batch: {}", e),
+        }
+    }
+    log::info!("Successfully embedded: {} documents", stored);
     Ok(())
 }
@@ -58,13 +58,14 @@ async fn main() -> anyhow::Result<()> {
         let results = hybrid::search_hybrid(
             &db,
             &embedder,
+            config.embeddings.dimensions,
             &query.query,
             None,
             None,
+            None,
             k_retrieve,
             config.search.min_score,
-            config.search.hybrid_bm25_weight,
-            config.search.hybrid_vector_weight,
+            &config.search,
             None,
         )
         .await?;
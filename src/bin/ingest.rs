@@ -1,9 +1,9 @@
 use clap::Parser;
 use ragmcp::Config;
 use ragmcp::db::{Db, migrate};
+use ragmcp::error::RagmcpError;
 use ragmcp::ingest::{
-    discover_files, compute_file_hash, extract_namespace, extract_agent_name,
-    ParserRegistry, chunk_document, insert_document, insert_chunks,
+    deletable_documents, ingest_file, ParserRegistry, SkipReason, SkippedPath,
     get_existing_hashes, classify_files, find_deleted_documents, delete_documents,
 };
 use std::path::Path;
@@ -39,7 +39,10 @@ async fn main() -> Result<()> {
     // Load configuration
     let config = Config::load()?;
     log::info!("Configuration loaded successfully");
-    log::info!("Docs root: {}", config.rag_folder().display());
+    let sources = config.sources();
+    for source in &sources {
+        log::info!("Docs root ({}): {}", source.name(), source.root.display());
+    }
     log::info!("Database path: {}", config.db_path().display());
     
     // Initialize database
@@ -53,13 +56,28 @@ async fn main() -> Result<()> {
     
     log::info!("Database initialized");
     
-    // Discover files
-    log::info!("Discovering files in {}", config.rag_folder().display());
-    let files = discover_files(config.rag_folder())?;
+    // Discover files in every available source
+    let mut files = Vec::new();
+    let mut skipped_paths = Vec::new();
+    for source in &sources {
+        if !source.is_available() {
+            log::warn!(
+                "Source '{}' is unavailable ({} not found); its documents are kept",
+                source.name(),
+                source.root.display()
+            );
+            continue;
+        }
+        log::info!("Discovering files in {}", source.root.display());
+        let discovery = source.discover(&config.ingest)?;
+        files.extend(discovery.files);
+        skipped_paths.extend(discovery.skipped);
+    }
     log::info!("Found {} files to ingest", files.len());
+    report_skipped_paths(&skipped_paths);
     
     if files.is_empty() {
-        log::warn!("No files found to ingest. Check rag_folder path in config.toml.");
+        log::warn!("No files found to ingest. Check rag_folder and [[sources]] in config.toml.");
         return Ok(());
     }
     
@@ -90,7 +108,7 @@ async fn main() -> Result<()> {
         log::info!("No new or modified files to process. Ingestion complete.");
         // Optionally cleanup deleted documents
         let existing_file_paths: HashSet<String> = files.iter().map(|f| f.relative_path.clone()).collect();
-        let deleted = find_deleted_documents(&db, &existing_file_paths).await?;
+        let deleted = deletable_documents(&sources, find_deleted_documents(&db, &existing_file_paths).await?);
         if !deleted.is_empty() {
             log::info!("Found {} documents in DB that no longer exist on disk", deleted.len());
             if cleanup {
@@ -104,7 +122,7 @@ async fn main() -> Result<()> {
     }
     
     log::info!("Processing {} file(s)", total_to_process);
-    let create_parser_registry = ParserRegistry::new();
+    let create_parser_registry = ParserRegistry::with_config(&config.ingest);
     let parser_registry = &create_parser_registry;
     
     let start = Instant::now();
@@ -112,6 +130,7 @@ async fn main() -> Result<()> {
     let mut total_chunks: usize = 0;
     let mut total_tokens: usize = 0;
     let mut errors: usize = 0;
+    let mut unsupported: usize = 0;
     
     for (idx, file) in files_to_process.iter().enumerate() {
         log::info!(
@@ -138,10 +157,16 @@ async fn main() -> Result<()> {
                     tokens
                 );
             }
-            Err(e) => {
-                errors += 1;
-                log::error!("✗ {}: {}", file.relative_path, e);
-            }
+            Err(e) => match e.downcast_ref::<RagmcpError>() {
+                Some(RagmcpError::Skipped(reason)) => {
+                    unsupported += 1;
+                    log::warn!("- {}: skipped ({})", file.relative_path, reason);
+                }
+                _ => {
+                    errors += 1;
+                    log::error!("✗ {}: {}", file.relative_path, e);
+                }
+            },
         }
     }
     
@@ -149,7 +174,7 @@ async fn main() -> Result<()> {
     
     // Optional: cleanup documents that no longer exist on filesystem
     let existing_file_paths: HashSet<String> = files.iter().map(|f| f.relative_path.clone()).collect();
-    let deleted = find_deleted_documents(&db, &existing_file_paths).await?;
+    let deleted = deletable_documents(&sources, find_deleted_documents(&db, &existing_file_paths).await?);
     let deleted_count = if !deleted.is_empty() && cleanup {
         log::info!("Found {} deleted documents on disk", deleted.len());
         delete_documents(&db, &deleted).await?
@@ -164,10 +189,16 @@ async fn main() -> Result<()> {
     let skipped = files.len().saturating_sub(total_to_process);
     log::info!("=== Ingestion Complete ===");
     log::info!("Files discovered: {}", files.len());
+    if !skipped_paths.is_empty() {
+        log::info!("Paths skipped during discovery: {} ({})", skipped_paths.len(), skip_counts(&skipped_paths));
+    }
     log::info!("  New: {}", num_new);
     log::info!("  Modified: {}", num_modified);
     log::info!("  Unchanged (skipped): {}", num_unchanged);
     log::info!("Files processed: {} (success: {}, errors: {})", total_docs, total_docs.saturating_sub(errors), errors);
+    if unsupported > 0 {
+        log::info!("Files skipped (encrypted or no extractable text): {}", unsupported);
+    }
     log::info!("Chunks created: {}", total_chunks);
     log::info!("Tokens indexed: {}", total_tokens);
     log::info!("Time: {:?}", elapsed);
@@ -187,52 +218,45 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Log every path discovery skipped with its reason
+/// 
+/// Files with unsupported extensions are only logged at debug level; they are
+/// counted in the summary.
+fn report_skipped_paths(skipped: &[SkippedPath]) {
+    for path in skipped {
+        if path.reason == SkipReason::UnsupportedExtension {
+            log::debug!("- {}: skipped ({})", path.relative_path, path.reason);
+        } else {
+            log::info!("- {}: skipped ({})", path.relative_path, path.reason);
+        }
+    }
+}
+
+/// Skip counts per reason, e.g. "ignored: 3, too large: 1"
+fn skip_counts(skipped: &[SkippedPath]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for path in skipped {
+        let label = path.reason.label();
+        match counts.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((label, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|(label, count)| format!("{}: {}", label, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Process a single file: parse, chunk, and insert into database
+/// 
+/// Mailboxes are stored as one document per message.
 async fn process_file(
     db: &Db,
     file: &ragmcp::ingest::FileMetadata,
     parser_registry: &ParserRegistry,
     config: &Config,
 ) -> Result<(usize, usize)> {
-    // Read file content
-    let content = std::fs::read_to_string(&file.absolute_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
-    
-    // Compute file hash
-    let file_hash = compute_file_hash(&file.absolute_path)?;
-    
-    // Extract metadata
-    let namespace = extract_namespace(&file.relative_path);
-    let agent_name = extract_agent_name(&file.relative_path);
-    
-    // Parse document
-    let parsed = parser_registry.parse(
-        &content,
-        &file.relative_path,
-        &file.extension,
-    )?;
-    
-    // Chunk document
-    let chunks = chunk_document(&parsed, &config.performance)?;
-    
-    // Estimate total tokens
-    let total_tokens = chunks.iter().map(|c| c.tokens).sum::<usize>();
-    
-    // Insert document
-    let doc_id = insert_document(
-        db,
-        &file.relative_path,
-        &parsed.doc_type,
-        &namespace,
-        agent_name.as_deref(),
-        &parsed.content,
-        total_tokens,
-        &file_hash,
-        file.modified,
-    ).await?;
-    
-    // Insert chunks
-    let chunk_count = insert_chunks(db, &doc_id, chunks).await?;
-    
-    Ok((chunk_count, total_tokens))
+    Ok(ingest_file(db, file, parser_registry, config).await?)
 }
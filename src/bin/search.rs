@@ -1,14 +1,34 @@
 use ragmcp::{Config, db::Db, embeddings::OpenAIEmbedder, search::hybrid};
+use ragmcp::search::documents::{
+    aggregate_chunks, load_titles, rank_by_document_embedding, DocAggregation, DOC_CANDIDATE_FACTOR,
+};
+use ragmcp::search::similar::{resolve_seed, search_similar};
+use ragmcp::search::{MetadataFilter, SearchResult};
 use std::time::Instant;
 
-/// Parse CLI args: optional --namespace <val>, --agent_filter <val>; first positional is the query.
-fn parse_search_args() -> anyhow::Result<(String, Option<String>, Option<String>)> {
+/// Parsed command line: the query (absent with `--like`) and filters
+struct SearchArgs {
+    query: Option<String>,
+    namespace: Option<String>,
+    agent_filter: Option<String>,
+    like: Option<String>,
+    filter: MetadataFilter,
+}
+
+/// Parse CLI args: optional --namespace <val>, --agent_filter <val>, --like <doc_path|chunk_id>,
+/// repeatable --filter <key=value>;
+/// first positional is the query. Boolean flags such as --prefer-recent are read separately in `main`.
+fn parse_search_args() -> anyhow::Result<SearchArgs> {
     let args: Vec<String> = std::env::args().skip(1).collect();
     let mut query = None;
     let mut namespace = None;
     let mut agent_filter = None;
+    let mut like = None;
+    let mut filters = Vec::new();
     let mut next_namespace = false;
     let mut next_agent = false;
+    let mut next_like = false;
+    let mut next_filter = false;
     for arg in &args {
         if next_namespace {
             namespace = Some(arg.clone());
@@ -20,6 +40,16 @@ fn parse_search_args() -> anyhow::Result<(String, Option<String>, Option<String>
             next_agent = false;
             continue;
         }
+        if next_like {
+            like = Some(arg.clone());
+            next_like = false;
+            continue;
+        }
+        if next_filter {
+            filters.push(arg.clone());
+            next_filter = false;
+            continue;
+        }
         if arg == "--namespace" {
             next_namespace = true;
             continue;
@@ -28,6 +58,14 @@ fn parse_search_args() -> anyhow::Result<(String, Option<String>, Option<String>
             next_agent = true;
             continue;
         }
+        if arg == "--like" {
+            next_like = true;
+            continue;
+        }
+        if arg == "--filter" {
+            next_filter = true;
+            continue;
+        }
         if arg.starts_with("--") {
             continue;
         }
@@ -35,13 +73,16 @@ fn parse_search_args() -> anyhow::Result<(String, Option<String>, Option<String>
             query = Some(arg.clone());
         }
     }
-    let query = query.ok_or_else(|| anyhow::anyhow!(
-        "Usage: search <query> [--namespace <ns>] [--agent_filter <agent>]\nExample: search \"module overview\" --agent_filter module-alpha"
-    ))?;
-    if query.trim().is_empty() {
-        anyhow::bail!("Query cannot be empty");
+    if like.is_none() {
+        let query = query.as_deref().ok_or_else(|| anyhow::anyhow!(
+            "Usage: search <query> [--namespace <ns>] [--agent_filter <agent>] [--filter <key=value>]... [--prefer-recent] [--documents]\n       search --like <doc_path|chunk_id> [--namespace <ns>] [--agent_filter <agent>] [--documents]\nExample: search \"module overview\" --agent_filter module-alpha"
+        ))?;
+        if query.trim().is_empty() {
+            anyhow::bail!("Query cannot be empty");
+        }
     }
-    Ok((query, namespace, agent_filter))
+    let filter = MetadataFilter::from_pairs(&filters)?;
+    Ok(SearchArgs { query, namespace, agent_filter, like, filter })
 }
 
 #[tokio::main]
@@ -55,6 +96,18 @@ async fn main() -> anyhow::Result<()> {
     // Initialize database
     let db = Db::new(config.db_path());
 
+    let args = parse_search_args()?;
+    let namespace_ref = args.namespace.as_deref();
+    let agent_filter_ref = args.agent_filter.as_deref();
+    let metadata_filter = (!args.filter.is_empty()).then_some(&args.filter);
+    let document_mode = std::env::args().any(|a| a == "--documents");
+
+    // --like uses stored embeddings as the query vector, so no API key is needed
+    if let Some(like) = args.like.as_deref() {
+        return print_similar(&db, &config, like, namespace_ref, agent_filter_ref, document_mode).await;
+    }
+    let query = args.query.unwrap_or_default();
+
     // Get API key from environment (loaded by config via dotenv)
     let api_key = std::env::var(&config.embeddings.api_key_env)?;
 
@@ -65,36 +118,52 @@ async fn main() -> anyhow::Result<()> {
         config.embeddings.batch_size,
     );
 
-    let (query, namespace, agent_filter) = parse_search_args()?;
+    let mut search_config = config.search.clone();
+    if std::env::args().any(|a| a == "--prefer-recent") {
+        search_config.recency.enabled = true;
+    }
 
-    let namespace_ref = namespace.as_deref();
-    let agent_filter_ref = agent_filter.as_deref();
+    // --documents ranks documents (aggregation from [search.documents]) instead of chunks
+    let k = if document_mode {
+        config.search.default_k * DOC_CANDIDATE_FACTOR
+    } else {
+        config.search.default_k
+    };
 
     // Measure search latency
     let start = Instant::now();
 
-    // Execute hybrid search (optional namespace/agent filter; no chunk cache in CLI)
+    // Execute hybrid search (optional namespace/agent/metadata filter; no chunk cache in CLI)
     let results = hybrid::search_hybrid(
         &db,
         &embedder,
+        config.embeddings.dimensions,
         &query,
         namespace_ref,
         agent_filter_ref,
-        config.search.default_k,
+        metadata_filter,
+        k,
         config.search.min_score,
-        config.search.hybrid_bm25_weight,
-        config.search.hybrid_vector_weight,
+        &search_config,
         None,
     )
     .await?;
 
+    if document_mode {
+        return print_documents(&db, &embedder, &config, &query, namespace_ref, agent_filter_ref, metadata_filter, results, start).await;
+    }
+
     let duration = start.elapsed();
 
     // Display results
     println!("\n╔══════════════════════════════════════════════════════════════════════════════╗");
     println!("║ RAGMcp Hybrid Search Results                                                ║");
     println!("╚══════════════════════════════════════════════════════════════════════════════╝");
-    println!("\nQuery: \"{}\"\n", query);
+    println!("\nQuery: \"{}\"", query);
+    if let Some(filter) = metadata_filter {
+        println!("Filter: {}", filter.describe());
+    }
+    println!();
 
     if results.is_empty() {
         println!("No results found.");
@@ -113,6 +182,10 @@ async fn main() -> anyhow::Result<()> {
             
             println!("Type: {}", result.doc_type);
             
+            for boost in &result.boosts {
+                println!("Boost: {} x{:.3} ({})", boost.kind, boost.factor, boost.detail);
+            }
+            
             // Display chunk preview (first 200 characters)
             let preview_len = result.chunk_text.len().min(200);
             let preview = &result.chunk_text[..preview_len];
@@ -144,3 +217,116 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Aggregate chunk results per document and print the ranked documents
+#[allow(clippy::too_many_arguments)]
+async fn print_documents(
+    db: &Db,
+    embedder: &OpenAIEmbedder,
+    config: &Config,
+    query: &str,
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
+    results: Vec<SearchResult>,
+    start: Instant,
+) -> anyhow::Result<()> {
+    let doc_config = &config.search.documents;
+    let aggregation = DocAggregation::from_name(&doc_config.aggregation).unwrap_or(DocAggregation::Max);
+    let k = config.search.default_k;
+    let mut documents = if aggregation == DocAggregation::Embedding {
+        let query_vec = embedder.embed_with_cache(query, 3).await?;
+        rank_by_document_embedding(db, &query_vec, namespace, agent_filter, metadata_filter, results.clone(), doc_config.sections_per_doc, k).await?
+    } else {
+        Vec::new()
+    };
+    let mut used = aggregation;
+    if documents.is_empty() {
+        // No document embeddings yet (run `embed`): fall back to the best chunk per document
+        if used == DocAggregation::Embedding {
+            used = DocAggregation::Max;
+        }
+        documents = aggregate_chunks(results, used, doc_config.top_n, doc_config.sections_per_doc, k);
+    }
+    load_titles(db, &mut documents).await?;
+    let duration = start.elapsed();
+
+    println!("\nQuery: \"{}\" (documents, aggregation: {})\n", query, used.name());
+    if documents.is_empty() {
+        println!("No results found.");
+    }
+    for doc in &documents {
+        println!("─────────────────────────────────────────────────────────────────────────────");
+        println!("Rank #{}: {} (score: {:.3})", doc.rank, doc.doc_path, doc.score);
+        if let Some(ref title) = doc.title {
+            println!("Title: {}", title);
+        }
+        for section in &doc.sections {
+            println!(
+                "  - {} (score: {:.3})",
+                section.section.as_deref().unwrap_or("(no section)"),
+                section.score
+            );
+        }
+    }
+    println!("\nDocuments: {}", documents.len());
+    println!("Latency: {:?}", duration);
+    Ok(())
+}
+
+/// Print chunks or documents similar to a stored document or chunk (`--like`)
+async fn print_similar(
+    db: &Db,
+    config: &Config,
+    like: &str,
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    document_mode: bool,
+) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let seed = resolve_seed(db, like, config.embeddings.dimensions).await?;
+    let k = config.search.default_k;
+    let fetch_k = if document_mode { k * DOC_CANDIDATE_FACTOR } else { k };
+    let results = search_similar(db, &seed, namespace, agent_filter, fetch_k, 0.0, None).await?;
+
+    println!("\nMore like: {}\n", like);
+    if document_mode {
+        let doc_config = &config.search.documents;
+        let mut documents =
+            aggregate_chunks(results, DocAggregation::Max, doc_config.top_n, doc_config.sections_per_doc, k);
+        load_titles(db, &mut documents).await?;
+        if documents.is_empty() {
+            println!("No results found.");
+        }
+        for doc in &documents {
+            println!("─────────────────────────────────────────────────────────────────────────────");
+            println!("Rank #{}: {} (similarity: {:.3})", doc.rank, doc.doc_path, doc.score);
+            if let Some(ref title) = doc.title {
+                println!("Title: {}", title);
+            }
+            for section in &doc.sections {
+                println!(
+                    "  - {} (similarity: {:.3})",
+                    section.section.as_deref().unwrap_or("(no section)"),
+                    section.score
+                );
+            }
+        }
+        println!("\nDocuments: {}", documents.len());
+    } else {
+        if results.is_empty() {
+            println!("No results found.");
+        }
+        for result in &results {
+            println!("─────────────────────────────────────────────────────────────────────────────");
+            println!("Rank #{}: {} (similarity: {:.3})", result.rank, result.doc_path, result.score);
+            println!("Chunk: {}", result.chunk_id);
+            if let Some(ref section) = result.section {
+                println!("Section: {}", section);
+            }
+        }
+        println!("\nResults: {}", results.len());
+    }
+    println!("Latency: {:?}", start.elapsed());
+    Ok(())
+}
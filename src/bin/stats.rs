@@ -125,6 +125,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  P95 < 1000ms: {}", if p95 < 1000 { "✅ PASS" } else { "❌ FAIL" });
     }
     
+    // Result cache effectiveness (cache hits are logged with a "_cached" suffix)
+    let (cache_hits, cacheable) = db.with_connection(|conn| {
+        conn.query_row(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN retrieval_method IN ('hybrid_cached', 'document_cached') THEN 1 ELSE 0 END), 0),
+                COUNT(*)
+            FROM query_logs
+            WHERE timestamp > datetime('now', '-24 hours')
+                AND retrieval_method IN ('hybrid', 'hybrid_cached', 'document', 'document_cached')
+            "#,
+            [],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        ).map_err(RagmcpError::from)
+    }).await?;
+
+    println!("\nResult Cache (Last 24 Hours):");
+    if !config.search.cache.enabled {
+        println!("  Disabled ([search.cache] enabled = false)");
+    } else if cacheable > 0 {
+        println!(
+            "  Hits: {} / {} searches ({:.1}% hit rate)",
+            cache_hits,
+            cacheable,
+            cache_hits as f64 * 100.0 / cacheable as f64
+        );
+    } else {
+        println!("  No ragmcp_search calls logged");
+    }
+
     // Recent query activity
     let recent_count = db.with_connection(|conn| {
         conn.query_row(
@@ -1,4 +1,4 @@
-//! Watch rag_folder for file changes; re-ingest and re-embed changed files automatically.
+//! Watch rag_folder and [[sources]] roots for file changes; re-ingest and re-embed changed files automatically.
 
 use clap::Parser;
 use ragmcp::watch::run_watcher;
@@ -8,7 +8,7 @@ use anyhow::Result;
 
 #[derive(Parser, Debug)]
 #[command(name = "watch")]
-#[command(about = "Watch rag_folder for changes and auto re-ingest + embed")]
+#[command(about = "Watch rag_folder and [[sources]] for changes and auto re-ingest + embed")]
 struct Args {
     /// Debounce delay in milliseconds before processing a file change
     #[arg(long, default_value = "500")]
@@ -26,7 +26,9 @@ async fn main() -> Result<()> {
 
     log::info!("Starting RAGMcp file watcher");
     let config = Config::load()?;
-    log::info!("Docs root: {}", config.rag_folder().display());
+    for source in config.sources() {
+        log::info!("Docs root ({}): {}", source.name(), source.root.display());
+    }
     log::info!("Debounce: {} ms", args.debounce_ms);
 
     let db = Db::new(config.db_path());
@@ -1,5 +1,7 @@
 pub mod chunk_embedding_cache;
 pub mod embedding_cache;
+pub mod result_cache;
 
 pub use chunk_embedding_cache::ChunkEmbeddingCache;
 pub use embedding_cache::EmbeddingCache;
+pub use result_cache::ResultCache;
@@ -0,0 +1,196 @@
+//! Bounded cache of final search results.
+//!
+//! Entries are tagged with the index generation (`index_state.generation`, bumped by
+//! triggers on every chunk, document or feedback write) they were computed at. A lookup
+//! only hits when the generation is unchanged and the entry is younger than the TTL, so
+//! changes from ingest, watch or create/update doc invalidate the cache without any
+//! explicit coordination between processes.
+
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::{MetadataFilter, SearchResult};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct CachedResults {
+    generation: i64,
+    stored_at: Instant,
+    results: Vec<SearchResult>,
+}
+
+/// Thread-safe LRU cache of search results keyed by query and parameters
+pub struct ResultCache {
+    cache: Mutex<LruCache<String, CachedResults>>,
+    ttl: Duration,
+}
+
+impl ResultCache {
+    /// Create a result cache holding at most `capacity` result sets for `ttl`
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let cap = NonZeroUsize::new(capacity.max(1))
+            .expect("Cache capacity must be at least 1");
+
+        Self {
+            cache: Mutex::new(LruCache::new(cap)),
+            ttl,
+        }
+    }
+
+    /// Build the cache key from the normalized query and every result-affecting parameter
+    ///
+    /// Queries are lowercased and whitespace-collapsed so trivially different spellings
+    /// of the same request share an entry.
+    pub fn key(
+        query: &str,
+        namespace: Option<&str>,
+        agent_filter: Option<&str>,
+        metadata_filter: Option<&MetadataFilter>,
+        k: usize,
+        min_score: f32,
+        prefer_recent: Option<bool>,
+    ) -> String {
+        let normalized = query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        format!(
+            "{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{:?}",
+            normalized,
+            namespace.unwrap_or("*"),
+            agent_filter.unwrap_or("*"),
+            metadata_filter.and_then(MetadataFilter::sql_param).unwrap_or_default(),
+            k,
+            min_score,
+            prefer_recent
+        )
+    }
+
+    /// Cached results for `key`, if computed at `generation` and not expired
+    pub fn get(&self, key: &str, generation: i64) -> Option<Vec<SearchResult>> {
+        let mut cache = self.cache.lock().unwrap();
+        let fresh = match cache.get(key) {
+            Some(entry) => entry.generation == generation && entry.stored_at.elapsed() < self.ttl,
+            None => false,
+        };
+        if fresh {
+            return cache.get(key).map(|entry| entry.results.clone());
+        }
+        // Stale entries are dropped eagerly so they do not hold capacity
+        cache.pop(key);
+        None
+    }
+
+    /// Store results computed at `generation`
+    pub fn put(&self, key: String, generation: i64, results: Vec<SearchResult>) {
+        self.cache.lock().unwrap().put(
+            key,
+            CachedResults {
+                generation,
+                stored_at: Instant::now(),
+                results,
+            },
+        );
+    }
+
+    /// Get the current number of cached result sets
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.cache.lock().unwrap().is_empty()
+    }
+
+    /// Clear all entries from the cache
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+/// Current index generation (0 if the counter table is missing)
+pub async fn index_generation(db: &Db) -> Result<i64> {
+    db.with_connection(|conn| {
+        match conn.query_row("SELECT generation FROM index_state WHERE id = 1", [], |row| {
+            row.get::<_, i64>(0)
+        }) {
+            Ok(generation) => Ok(generation),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(0),
+            Err(e) => Err(RagmcpError::Database(e)),
+        }
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+    use crate::ingest::db_writer::insert_document;
+    use std::path::Path;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn results(chunk_id: &str) -> Vec<SearchResult> {
+        vec![SearchResult {
+            chunk_id: chunk_id.to_string(),
+            doc_path: "doc.md".to_string(),
+            doc_type: "markdown".to_string(),
+            agent_name: None,
+            section: None,
+            chunk_text: "text".to_string(),
+            score: 1.0,
+            rank: 1,
+            boosts: Vec::new(),
+        }]
+    }
+
+    #[test]
+    fn test_key_normalizes_query_and_includes_params() {
+        let a = ResultCache::key("  Rotate   KEYS ", Some("docs"), None, None, 5, 0.65, None);
+        let b = ResultCache::key("rotate keys", Some("docs"), None, None, 5, 0.65, None);
+        assert_eq!(a, b);
+        assert_ne!(a, ResultCache::key("rotate keys", Some("docs"), None, None, 10, 0.65, None));
+        assert_ne!(a, ResultCache::key("rotate keys", None, None, None, 5, 0.65, None));
+        assert_ne!(a, ResultCache::key("rotate keys", Some("docs"), None, None, 5, 0.65, Some(true)));
+        let filter = MetadataFilter::from_pairs(&["status=published"]).unwrap();
+        assert_ne!(a, ResultCache::key("rotate keys", Some("docs"), None, Some(&filter), 5, 0.65, None));
+    }
+
+    #[test]
+    fn test_get_requires_matching_generation() {
+        let cache = ResultCache::new(10, Duration::from_secs(60));
+        cache.put("q".to_string(), 3, results("c1"));
+
+        assert_eq!(cache.get("q", 3).unwrap()[0].chunk_id, "c1");
+        assert!(cache.get("q", 4).is_none(), "index changed since the entry was stored");
+        assert!(cache.is_empty(), "stale entry is evicted");
+    }
+
+    #[test]
+    fn test_get_respects_ttl() {
+        let cache = ResultCache::new(10, Duration::ZERO);
+        cache.put("q".to_string(), 1, results("c1"));
+        assert!(cache.get("q", 1).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_index_generation_bumps_on_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+
+        let before = index_generation(&db).await.unwrap();
+        insert_document(&db, "doc.md", "markdown", "docs", None, "# Doc", 1, "h1", SystemTime::now())
+            .await
+            .unwrap();
+        db.with_connection(|conn| {
+            conn.execute("UPDATE documents SET file_hash = 'h2' WHERE doc_path = 'doc.md'", [])?;
+            Ok::<_, RagmcpError>(())
+        }).await.unwrap();
+
+        assert!(index_generation(&db).await.unwrap() > before);
+    }
+}
@@ -1,5 +1,13 @@
 use anyhow::{Context, Result};
+use crate::search::documents::DocAggregation;
+use crate::ingest::chunker::ChunkStrategy;
+use crate::ingest::rules::IngestRules;
+use crate::ingest::sources::{validate_prefix, Source};
+use crate::ingest::walker::DiscoveryFilter;
+use crate::ingest::tokenizer::Tokenizer;
+use crate::search::language::{detect_language, Language};
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 /// Main configuration structure
@@ -13,6 +21,13 @@ pub struct Config {
     pub http_server: HttpServerConfig,
     #[serde(default)]
     pub pageindex: PageIndexConfig,
+    #[serde(default)]
+    pub ingest: IngestConfig,
+    #[serde(default)]
+    pub duplicates: DuplicatesConfig,
+    /// Additional document roots (`[[sources]]`), mounted next to rag_folder
+    #[serde(default)]
+    pub sources: Vec<SourceConfig>,
 }
 
 /// RAGMcp-specific configuration
@@ -20,12 +35,34 @@ pub struct Config {
 pub struct RagmcpConfig {
     /// Path to the root directory containing documents to index.
     /// Top-level sub-directories become searchable namespaces automatically.
+    /// May be omitted when `[[sources]]` are configured.
+    #[serde(default)]
     pub rag_folder: PathBuf,
     pub db_path: PathBuf,
     #[serde(default = "default_log_level")]
     pub log_level: String,
 }
 
+/// A document root mounted under a prefix (`[[sources]]`)
+///
+/// Its files are stored as `<prefix>/<path below root>`, so the prefix is their
+/// namespace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SourceConfig {
+    pub root: PathBuf,
+    /// Mount prefix: a single directory name, unique among sources
+    pub prefix: String,
+    /// Globs relative to `root`; replace `[ingest] include` for this source
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Globs relative to `root`; added to `[ingest] exclude`
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Reject ragmcp_create_doc / ragmcp_update_doc writes to this source
+    #[serde(default)]
+    pub read_only: bool,
+}
+
 /// Embeddings configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct EmbeddingsConfig {
@@ -36,12 +73,20 @@ pub struct EmbeddingsConfig {
     pub dimensions: usize,
     #[serde(default = "default_cache_capacity")]
     pub cache_capacity: usize,
+    /// Tokenizer for chunk sizing and token counts: "auto" (from `model`),
+    /// "cl100k_base", "o200k_base" or "chars" (~4 characters per token)
+    #[serde(default = "default_tokenizer")]
+    pub tokenizer: String,
 }
 
 fn default_cache_capacity() -> usize {
     1000
 }
 
+fn default_tokenizer() -> String {
+    "auto".to_string()
+}
+
 /// Search configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct SearchConfig {
@@ -49,6 +94,486 @@ pub struct SearchConfig {
     pub min_score: f32,
     pub hybrid_bm25_weight: f32,
     pub hybrid_vector_weight: f32,
+    /// BM25 term-frequency saturation (FTS5 built-in: 1.2)
+    #[serde(default = "default_bm25_k1")]
+    pub bm25_k1: f64,
+    /// BM25 length normalization, 0.0-1.0 (FTS5 built-in: 0.75)
+    #[serde(default = "default_bm25_b")]
+    pub bm25_b: f64,
+    #[serde(default)]
+    pub field_weights: FieldWeights,
+    #[serde(default)]
+    pub recency: RecencyConfig,
+    #[serde(default)]
+    pub feedback: FeedbackConfig,
+    #[serde(default)]
+    pub languages: LanguageConfig,
+    #[serde(default)]
+    pub cache: ResultCacheConfig,
+    #[serde(default)]
+    pub documents: DocumentSearchConfig,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            default_k: 5,
+            min_score: 0.65,
+            hybrid_bm25_weight: 0.5,
+            hybrid_vector_weight: 0.5,
+            bm25_k1: default_bm25_k1(),
+            bm25_b: default_bm25_b(),
+            field_weights: FieldWeights::default(),
+            recency: RecencyConfig::default(),
+            feedback: FeedbackConfig::default(),
+            languages: LanguageConfig::default(),
+            cache: ResultCacheConfig::default(),
+            documents: DocumentSearchConfig::default(),
+        }
+    }
+}
+
+fn default_bm25_k1() -> f64 {
+    1.2
+}
+
+fn default_bm25_b() -> f64 {
+    0.75
+}
+
+/// Per-column BM25 weights (`[search.field_weights]`)
+///
+/// A match in a heavily weighted column counts as that many body matches.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldWeights {
+    #[serde(default = "default_body_weight")]
+    pub body: f64,
+    /// Section breadcrumb (heading path) of the chunk
+    #[serde(default = "default_breadcrumb_weight")]
+    pub breadcrumb: f64,
+    /// Document title (first heading or file name)
+    #[serde(default = "default_title_weight")]
+    pub title: f64,
+    /// Document path segments (directories and file name)
+    #[serde(default = "default_path_weight")]
+    pub path: f64,
+}
+
+fn default_body_weight() -> f64 { 1.0 }
+fn default_breadcrumb_weight() -> f64 { 2.0 }
+fn default_title_weight() -> f64 { 5.0 }
+fn default_path_weight() -> f64 { 3.0 }
+
+impl Default for FieldWeights {
+    fn default() -> Self {
+        Self {
+            body: default_body_weight(),
+            breadcrumb: default_breadcrumb_weight(),
+            title: default_title_weight(),
+            path: default_path_weight(),
+        }
+    }
+}
+
+/// Time-decay boosting applied after fusion (`[search.recency]`)
+///
+/// Scores are multiplied by `1 - weight + weight * 0.5^(age / half_life)`, so fresh
+/// documents keep their score and stale ones lose up to `weight` of it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecencyConfig {
+    /// Apply by default; the `prefer_recent` search parameter overrides per call
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_half_life_days")]
+    pub half_life_days: f64,
+    /// Maximum fraction of the score a very old document can lose (0.0-1.0)
+    #[serde(default = "default_recency_weight")]
+    pub weight: f64,
+    /// Namespace -> half-life in days
+    #[serde(default)]
+    pub namespaces: HashMap<String, f64>,
+}
+
+fn default_half_life_days() -> f64 {
+    90.0
+}
+
+fn default_recency_weight() -> f64 {
+    0.3
+}
+
+impl Default for RecencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            half_life_days: default_half_life_days(),
+            weight: default_recency_weight(),
+            namespaces: HashMap::new(),
+        }
+    }
+}
+
+impl RecencyConfig {
+    /// Half-life for `namespace`, falling back to the global value
+    pub fn half_life_for(&self, namespace: &str) -> f64 {
+        self.namespaces
+            .get(namespace)
+            .copied()
+            .unwrap_or(self.half_life_days)
+    }
+}
+
+/// Learned boost from `ragmcp_feedback` judgements (`[search.feedback]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedbackConfig {
+    /// Kill switch for the search-time boost; feedback is still recorded when off
+    #[serde(default = "default_feedback_enabled")]
+    pub enabled: bool,
+    /// Maximum relative score change from feedback (0.0-1.0)
+    #[serde(default = "default_feedback_weight")]
+    pub weight: f64,
+    /// Minimum query-embedding cosine similarity for feedback to apply
+    #[serde(default = "default_feedback_similarity")]
+    pub similarity_threshold: f64,
+    /// Judgements lose half their influence every `half_life_days`
+    #[serde(default = "default_feedback_half_life")]
+    pub half_life_days: f64,
+    /// Judgements older than this are ignored
+    #[serde(default = "default_feedback_max_age")]
+    pub max_age_days: f64,
+}
+
+fn default_feedback_enabled() -> bool { true }
+fn default_feedback_weight() -> f64 { 0.2 }
+fn default_feedback_similarity() -> f64 { 0.9 }
+fn default_feedback_half_life() -> f64 { 30.0 }
+fn default_feedback_max_age() -> f64 { 180.0 }
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_feedback_enabled(),
+            weight: default_feedback_weight(),
+            similarity_threshold: default_feedback_similarity(),
+            half_life_days: default_feedback_half_life(),
+            max_age_days: default_feedback_max_age(),
+        }
+    }
+}
+
+/// Cache of final `ragmcp_search` results (`[search.cache]`)
+///
+/// Entries are invalidated whenever the index generation changes (any ingest,
+/// watch or create/update doc write) and expire after `ttl_secs` regardless.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResultCacheConfig {
+    #[serde(default = "default_result_cache_enabled")]
+    pub enabled: bool,
+    /// Maximum number of cached result sets
+    #[serde(default = "default_result_cache_capacity")]
+    pub capacity: usize,
+    /// Upper bound on entry age; keeps recency-boosted rankings from drifting
+    #[serde(default = "default_result_cache_ttl")]
+    pub ttl_secs: u64,
+}
+
+fn default_result_cache_enabled() -> bool { true }
+fn default_result_cache_capacity() -> usize { 256 }
+fn default_result_cache_ttl() -> u64 { 600 }
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_result_cache_enabled(),
+            capacity: default_result_cache_capacity(),
+            ttl_secs: default_result_cache_ttl(),
+        }
+    }
+}
+
+/// Document-level retrieval (`granularity: "document"`, `[search.documents]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct DocumentSearchConfig {
+    /// Default aggregation: "max", "sum_top_n" or "embedding"
+    #[serde(default = "default_doc_aggregation")]
+    pub aggregation: String,
+    /// Chunks summed per document by "sum_top_n"
+    #[serde(default = "default_doc_top_n")]
+    pub top_n: usize,
+    /// Best-matching sections returned with each document
+    #[serde(default = "default_doc_sections")]
+    pub sections_per_doc: usize,
+}
+
+fn default_doc_aggregation() -> String { "max".to_string() }
+fn default_doc_top_n() -> usize { 3 }
+fn default_doc_sections() -> usize { 3 }
+
+impl Default for DocumentSearchConfig {
+    fn default() -> Self {
+        Self {
+            aggregation: default_doc_aggregation(),
+            top_n: default_doc_top_n(),
+            sections_per_doc: default_doc_sections(),
+        }
+    }
+}
+
+/// BM25 language settings (`[search.languages]`)
+///
+/// Chooses the stemmer and stop-word list per namespace. Namespaces without an
+/// explicit mapping use `default`, or the detected document language when `detect` is on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LanguageConfig {
+    #[serde(default = "default_language")]
+    pub default: String,
+    /// Namespace -> language name or ISO 639-1 code (e.g. `docs_de = "german"`)
+    #[serde(default)]
+    pub namespaces: HashMap<String, String>,
+    /// Detect document language from stop-word frequency for unmapped namespaces
+    #[serde(default)]
+    pub detect: bool,
+    /// Extra stop words per language, merged with the built-in lists
+    #[serde(default)]
+    pub stop_words: HashMap<String, Vec<String>>,
+}
+
+fn default_language() -> String {
+    "english".to_string()
+}
+
+impl Default for LanguageConfig {
+    fn default() -> Self {
+        Self {
+            default: default_language(),
+            namespaces: HashMap::new(),
+            detect: false,
+            stop_words: HashMap::new(),
+        }
+    }
+}
+
+impl LanguageConfig {
+    /// Configured default language (English if the name is not recognised)
+    pub fn default_language(&self) -> Language {
+        Language::from_name(&self.default).unwrap_or(Language::English)
+    }
+
+    /// Language for a document in `namespace`, detecting from `content` when enabled
+    pub fn resolve(&self, namespace: &str, content: &str) -> Language {
+        if let Some(lang) = self.namespaces.get(namespace).and_then(|n| Language::from_name(n)) {
+            return lang;
+        }
+        if self.detect {
+            if let Some(lang) = detect_language(content) {
+                return lang;
+            }
+        }
+        self.default_language()
+    }
+
+    /// Built-in plus configured stop words for `language` (lowercase)
+    pub fn stop_words_for(&self, language: Language) -> HashSet<String> {
+        let mut words: HashSet<String> =
+            language.stop_words().iter().map(|w| w.to_string()).collect();
+        for (name, extra) in &self.stop_words {
+            if Language::from_name(name) == Some(language) {
+                words.extend(extra.iter().map(|w| w.to_lowercase()));
+            }
+        }
+        words
+    }
+}
+
+/// Ingestion configuration (`[ingest]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngestConfig {
+    #[serde(default)]
+    pub contextual: ContextualConfig,
+    #[serde(default)]
+    pub csv: CsvConfig,
+    /// Per-path overrides (`[[ingest.rules]]`), applied in order
+    #[serde(default)]
+    pub rules: Vec<IngestRule>,
+    /// Globs (relative to rag_folder) a file must match to be ingested; empty = all files
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Globs for files and directories that are never ingested
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Larger files are skipped (bytes, 0 = no limit)
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: u64,
+    /// Skip files and directories whose name starts with `.` (`.git` is always skipped)
+    #[serde(default = "default_true")]
+    pub skip_hidden: bool,
+    /// Honor `.gitignore` and `.ragignore` files in rag_folder and its subdirectories
+    #[serde(default = "default_true")]
+    pub use_ignore_files: bool,
+}
+
+fn default_max_file_size() -> u64 { 20 * 1024 * 1024 }
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self {
+            contextual: ContextualConfig::default(),
+            csv: CsvConfig::default(),
+            rules: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_file_size: default_max_file_size(),
+            skip_hidden: true,
+            use_ignore_files: true,
+        }
+    }
+}
+
+/// Context header prepended to each chunk for embedding and BM25 (`[ingest.contextual]`)
+///
+/// Clients still receive the original chunk text. Changing these settings only affects
+/// newly ingested documents; run `ingest --force` then `embed --force` to rebuild.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContextualConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub include_title: bool,
+    #[serde(default = "default_true")]
+    pub include_path: bool,
+    /// Section heading path of the chunk
+    #[serde(default = "default_true")]
+    pub include_breadcrumb: bool,
+    /// Ask an LLM for a one-line document summary (one chat request per ingested document)
+    #[serde(default)]
+    pub summarize: bool,
+    #[serde(default = "default_summary_model")]
+    pub summary_model: String,
+    /// Document prefix sent to the summary model
+    #[serde(default = "default_summary_max_chars")]
+    pub summary_max_chars: usize,
+}
+
+fn default_true() -> bool { true }
+fn default_summary_model() -> String { "gpt-5.4-nano".to_string() }
+fn default_summary_max_chars() -> usize { 6000 }
+
+impl Default for ContextualConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            include_title: true,
+            include_path: true,
+            include_breadcrumb: true,
+            summarize: false,
+            summary_model: default_summary_model(),
+            summary_max_chars: default_summary_max_chars(),
+        }
+    }
+}
+
+/// CSV/TSV parsing (`[ingest.csv]`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CsvConfig {
+    /// Rows read per file; the rest of a larger file is not indexed
+    #[serde(default = "default_csv_max_rows")]
+    pub max_rows: usize,
+    /// Rows per section (row group)
+    #[serde(default = "default_csv_rows_per_section")]
+    pub rows_per_section: usize,
+}
+
+fn default_csv_max_rows() -> usize { 10_000 }
+fn default_csv_rows_per_section() -> usize { 20 }
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: default_csv_max_rows(),
+            rows_per_section: default_csv_rows_per_section(),
+        }
+    }
+}
+
+/// Overrides for files matching a rule (`[[ingest.rules]]`)
+///
+/// A rule matches when every matcher it sets matches (`glob` and `match_namespace`);
+/// a rule without matchers applies to every file. Later matching rules override the
+/// fields set by earlier ones. Unset fields keep the global settings.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct IngestRule {
+    /// Glob over the document path (`configs/**/*.yaml`; `*` stays within a directory):
+    /// relative to rag_folder, and starting with the mount prefix for `[[sources]]`
+    /// files (`wiki/configs/**/*.yaml`)
+    pub glob: Option<String>,
+    /// Namespace derived from the path (top-level directory, "all" for root files)
+    pub match_namespace: Option<String>,
+    pub chunk_size_tokens: Option<usize>,
+    pub chunk_overlap_tokens: Option<usize>,
+    /// Chunking strategy, as `[performance] chunk_strategy`
+    pub chunk_strategy: Option<String>,
+    /// Parse as this file extension (`parser = "md"`)
+    pub parser: Option<String>,
+    /// Stored doc_type instead of the parser's
+    pub doc_type: Option<String>,
+    /// Stored namespace instead of the top-level directory
+    pub namespace: Option<String>,
+    /// Stored agent name instead of the second-level directory
+    pub agent_name: Option<String>,
+    /// `false` keeps the chunks out of `embed` (keyword search only)
+    pub embed: Option<bool>,
+}
+
+/// Near-duplicate detection (`[duplicates]`, used by the `duplicates` command and
+/// `ragmcp_explain` with `explain_what = "duplicates"`)
+///
+/// A pair of chunks from different documents is reported when either the word-shingle
+/// Jaccard similarity or the embedding cosine similarity reaches its threshold.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DuplicatesConfig {
+    /// Minimum Jaccard similarity of word shingles (0-1)
+    #[serde(default = "default_dup_text_threshold")]
+    pub text_threshold: f32,
+    /// Minimum cosine similarity of chunk embeddings (0-1)
+    #[serde(default = "default_dup_embedding_threshold")]
+    pub embedding_threshold: f32,
+    /// Words per shingle
+    #[serde(default = "default_dup_shingle_size")]
+    pub shingle_size: usize,
+    /// MinHash signature length; must be divisible by `bands`
+    #[serde(default = "default_dup_num_hashes")]
+    pub num_hashes: usize,
+    /// LSH bands: more bands find lower-similarity candidates at more comparisons
+    #[serde(default = "default_dup_bands")]
+    pub bands: usize,
+    /// Chunks with fewer tokens (e.g. "See also" stubs) are ignored
+    #[serde(default = "default_dup_min_tokens")]
+    pub min_tokens: usize,
+    /// Pairs listed in reports
+    #[serde(default = "default_dup_max_pairs")]
+    pub max_pairs: usize,
+}
+
+fn default_dup_text_threshold() -> f32 { 0.8 }
+fn default_dup_embedding_threshold() -> f32 { 0.95 }
+fn default_dup_shingle_size() -> usize { 5 }
+fn default_dup_num_hashes() -> usize { 128 }
+fn default_dup_bands() -> usize { 32 }
+fn default_dup_min_tokens() -> usize { 20 }
+fn default_dup_max_pairs() -> usize { 50 }
+
+impl Default for DuplicatesConfig {
+    fn default() -> Self {
+        Self {
+            text_threshold: default_dup_text_threshold(),
+            embedding_threshold: default_dup_embedding_threshold(),
+            shingle_size: default_dup_shingle_size(),
+            num_hashes: default_dup_num_hashes(),
+            bands: default_dup_bands(),
+            min_tokens: default_dup_min_tokens(),
+            max_pairs: default_dup_max_pairs(),
+        }
+    }
 }
 
 /// Performance tuning configuration
@@ -57,6 +582,41 @@ pub struct PerformanceConfig {
     pub max_latency_ms: u64,
     pub chunk_size_tokens: usize,
     pub chunk_overlap_tokens: usize,
+    /// Chunking strategy: "fixed", "sentence", "recursive", "structured" or "semantic"
+    #[serde(default = "default_chunk_strategy")]
+    pub chunk_strategy: String,
+    /// Strategy per file extension (`md = "structured"`), overriding `chunk_strategy`
+    #[serde(default)]
+    pub chunk_strategies: HashMap<String, String>,
+    /// Semantic chunking starts a new chunk where the embedding distance between two
+    /// adjacent sentences is above this percentile (0-100) of the document's distances
+    #[serde(default = "default_semantic_breakpoint_percentile")]
+    pub semantic_breakpoint_percentile: f32,
+    /// Documents with more sentences are not embedded for semantic chunking; they are
+    /// chunked by sentence instead
+    #[serde(default = "default_semantic_max_sentences")]
+    pub semantic_max_sentences: usize,
+}
+
+fn default_chunk_strategy() -> String {
+    "fixed".to_string()
+}
+
+fn default_semantic_breakpoint_percentile() -> f32 {
+    90.0
+}
+
+fn default_semantic_max_sentences() -> usize {
+    2000
+}
+
+impl PerformanceConfig {
+    /// Chunking strategy name for files with `extension`
+    pub fn chunk_strategy_for(&self, extension: &str) -> &str {
+        self.chunk_strategies
+            .get(&extension.to_lowercase())
+            .unwrap_or(&self.chunk_strategy)
+    }
 }
 
 /// HTTP server configuration
@@ -181,14 +741,18 @@ impl Config {
     /// Validate configuration values
     fn validate(&self) -> Result<()> {
         // Validate that rag_folder exists and is a directory
-        if !self.ragmcp.rag_folder.exists() {
+        let has_rag_folder = !self.ragmcp.rag_folder.as_os_str().is_empty();
+        if !has_rag_folder && self.sources.is_empty() {
+            anyhow::bail!("Set rag_folder in config.toml to your docs directory, or add [[sources]].");
+        }
+        if has_rag_folder && !self.ragmcp.rag_folder.exists() {
             anyhow::bail!(
                 "rag_folder path does not exist: {}. Set rag_folder in config.toml to your docs directory.",
                 self.ragmcp.rag_folder.display()
             );
         }
         
-        if !self.ragmcp.rag_folder.is_dir() {
+        if has_rag_folder && !self.ragmcp.rag_folder.is_dir() {
             anyhow::bail!(
                 "rag_folder must be a directory, not a file: {}",
                 self.ragmcp.rag_folder.display()
@@ -214,6 +778,84 @@ impl Config {
             anyhow::bail!("search.min_score must be between 0.0 and 1.0");
         }
         
+        if self.search.bm25_k1 < 0.0 {
+            anyhow::bail!("search.bm25_k1 must be >= 0.0");
+        }
+        
+        if self.search.bm25_b < 0.0 || self.search.bm25_b > 1.0 {
+            anyhow::bail!("search.bm25_b must be between 0.0 and 1.0");
+        }
+        
+        let recency = &self.search.recency;
+        if recency.weight < 0.0 || recency.weight > 1.0 {
+            anyhow::bail!("search.recency.weight must be between 0.0 and 1.0");
+        }
+        
+        if std::iter::once(&recency.half_life_days).chain(recency.namespaces.values()).any(|h| *h <= 0.0) {
+            anyhow::bail!("search.recency half-life values must be greater than 0");
+        }
+        
+        let documents = &self.search.documents;
+        if DocAggregation::from_name(&documents.aggregation).is_none() {
+            anyhow::bail!(
+                "search.documents.aggregation must be one of: max, sum_top_n, embedding (got '{}')",
+                documents.aggregation
+            );
+        }
+        if documents.top_n == 0 {
+            anyhow::bail!("search.documents.top_n must be at least 1");
+        }
+
+        if let Err(e) = Tokenizer::from_config(&self.embeddings) {
+            anyhow::bail!("embeddings.tokenizer: {}", e);
+        }
+
+        let csv = &self.ingest.csv;
+        if csv.max_rows == 0 || csv.rows_per_section == 0 {
+            anyhow::bail!("ingest.csv.max_rows and ingest.csv.rows_per_section must be at least 1");
+        }
+
+        let duplicates = &self.duplicates;
+        if !(0.0..=1.0).contains(&duplicates.text_threshold)
+            || !(0.0..=1.0).contains(&duplicates.embedding_threshold)
+        {
+            anyhow::bail!("duplicates thresholds must be between 0.0 and 1.0");
+        }
+        if duplicates.shingle_size == 0 {
+            anyhow::bail!("duplicates.shingle_size must be at least 1");
+        }
+        if duplicates.bands == 0 || duplicates.num_hashes % duplicates.bands != 0 {
+            anyhow::bail!(
+                "duplicates.num_hashes ({}) must be a positive multiple of duplicates.bands ({})",
+                duplicates.num_hashes,
+                duplicates.bands
+            );
+        }
+
+        let feedback = &self.search.feedback;
+        if feedback.weight < 0.0 || feedback.weight > 1.0 {
+            anyhow::bail!("search.feedback.weight must be between 0.0 and 1.0");
+        }
+        
+        if feedback.half_life_days <= 0.0 {
+            anyhow::bail!("search.feedback.half_life_days must be greater than 0");
+        }
+        
+        let weights = &self.search.field_weights;
+        if [weights.body, weights.breadcrumb, weights.title, weights.path].iter().any(|w| *w < 0.0) {
+            anyhow::bail!("search.field_weights values must be >= 0.0");
+        }
+        
+        let languages = &self.search.languages;
+        for name in std::iter::once(&languages.default)
+            .chain(languages.namespaces.values())
+            .chain(languages.stop_words.keys())
+        {
+            if Language::from_name(name).is_none() {
+                anyhow::bail!("search.languages: unsupported language '{}'", name);
+            }
+        }
+        
         if self.performance.chunk_size_tokens == 0 {
             anyhow::bail!("performance.chunk_size_tokens must be greater than 0");
         }
@@ -224,6 +866,25 @@ impl Config {
             );
         }
         
+        for name in std::iter::once(&self.performance.chunk_strategy)
+            .chain(self.performance.chunk_strategies.values())
+        {
+            if ChunkStrategy::from_name(name).is_none() {
+                anyhow::bail!(
+                    "performance chunk strategy must be one of: fixed, sentence, recursive, structured, semantic (got '{}')",
+                    name
+                );
+            }
+        }
+        
+        if !(0.0..=100.0).contains(&self.performance.semantic_breakpoint_percentile) {
+            anyhow::bail!("performance.semantic_breakpoint_percentile must be between 0 and 100");
+        }
+        
+        IngestRules::new(&self.ingest.rules).map_err(|e| anyhow::anyhow!("{}", e))?;
+        DiscoveryFilter::from_config(&self.ingest).map_err(|e| anyhow::anyhow!("{}", e))?;
+        self.validate_sources()?;
+        
         Ok(())
     }
     
@@ -236,6 +897,73 @@ impl Config {
     pub fn rag_folder(&self) -> &Path {
         &self.ragmcp.rag_folder
     }
+    
+    /// All document roots: rag_folder (empty prefix, if set) followed by `[[sources]]`
+    pub fn sources(&self) -> Vec<Source> {
+        let mounted: Vec<String> = self.sources.iter().map(|s| s.prefix.clone()).collect();
+        let rag_folder = (!self.ragmcp.rag_folder.as_os_str().is_empty()).then(|| Source {
+            prefix: String::new(),
+            root: self.ragmcp.rag_folder.clone(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            read_only: false,
+            mounted,
+        });
+        rag_folder
+            .into_iter()
+            .chain(self.sources.iter().map(|s| Source {
+                prefix: s.prefix.clone(),
+                root: s.root.clone(),
+                include: s.include.clone(),
+                exclude: s.exclude.clone(),
+                read_only: s.read_only,
+                mounted: Vec::new(),
+            }))
+            .collect()
+    }
+    
+    /// Validate `[[sources]]`: unique prefixes, valid globs, no nested roots
+    ///
+    /// A missing root only logs a warning: shared drives may be unmounted, and their
+    /// documents are kept until the source is available again.
+    fn validate_sources(&self) -> Result<()> {
+        let mut prefixes = HashSet::new();
+        for source in &self.sources {
+            validate_prefix(&source.prefix).map_err(|e| anyhow::anyhow!("{}", e))?;
+            if !prefixes.insert(source.prefix.as_str()) {
+                anyhow::bail!("sources: duplicate prefix '{}'", source.prefix);
+            }
+            if source.root.exists() && !source.root.is_dir() {
+                anyhow::bail!("sources: root of '{}' must be a directory: {}", source.prefix, source.root.display());
+            }
+            if !source.root.exists() {
+                log::warn!("sources: root of '{}' does not exist: {}", source.prefix, source.root.display());
+            }
+        }
+        
+        let sources = self.sources();
+        for source in &sources {
+            DiscoveryFilter::from_config(&source.ingest_config(&self.ingest))
+                .map_err(|e| anyhow::anyhow!("sources '{}': {}", source.name(), e))?;
+        }
+        let roots: Vec<(&Source, PathBuf)> = sources
+            .iter()
+            .filter_map(|s| s.root.canonicalize().ok().map(|root| (s, root)))
+            .collect();
+        for (a, root_a) in &roots {
+            for (b, root_b) in &roots {
+                if a.prefix != b.prefix && root_b.starts_with(root_a) {
+                    anyhow::bail!(
+                        "sources: root of '{}' ({}) is inside the root of '{}'; source roots must not overlap",
+                        b.name(),
+                        root_b.display(),
+                        a.name()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -381,4 +1109,36 @@ chunk_overlap_tokens = 50
             std::env::set_var("RAGMCP_CONFIG", v);
         }
     }
+    
+    #[test]
+    fn test_config_sources() {
+        let temp_dir = TempDir::new().unwrap();
+        let wiki = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("nested")).unwrap();
+        let source = |prefix: &str, root: &Path| {
+            format!(
+                "\n[[sources]]\nprefix = \"{}\"\nroot = \"{}\"\nread_only = true\n",
+                prefix,
+                root.to_str().unwrap().replace('\\', "\\\\")
+            )
+        };
+        let load = |extra: String| -> Config {
+            toml::from_str(&(create_test_config(&temp_dir) + &extra)).unwrap()
+        };
+
+        let config = load(source("wiki", wiki.path()));
+        config.validate_sources().unwrap();
+        let sources = config.sources();
+        assert_eq!(sources.len(), 2);
+        assert_eq!((sources[0].name(), sources[0].read_only), ("rag_folder", false));
+        assert_eq!((sources[1].name(), sources[1].read_only), ("wiki", true));
+        assert!(sources[0].ingest_config(&config.ingest).exclude.contains(&"wiki/**".to_string()));
+
+        // Unavailable shares only warn
+        load(source("share", Path::new("/nonexistent/ragmcp-share"))).validate_sources().unwrap();
+
+        assert!(load(source("wiki", wiki.path()) + &source("wiki", wiki.path())).validate_sources().is_err());
+        assert!(load(source("a/b", wiki.path())).validate_sources().is_err());
+        assert!(load(source("nested", &temp_dir.path().join("nested"))).validate_sources().is_err());
+    }
 }
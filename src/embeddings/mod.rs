@@ -3,5 +3,6 @@ pub mod storage;
 
 pub use openai::OpenAIEmbedder;
 pub use storage::{
-    get_chunks_without_embedding_for_doc, get_embedding, store_embedding, store_embeddings_batch,
+    get_chunks_without_embedding_for_doc, get_documents_without_embedding, get_embedding,
+    store_document_embeddings, store_embedding, store_embeddings_batch,
 };
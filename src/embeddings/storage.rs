@@ -1,6 +1,7 @@
 use crate::db::Db;
 use crate::error::{Result, RagmcpError};
 use rusqlite::params;
+use crate::ingest::contextual::contextualized_text;
 
 /// Store an embedding for a chunk in the database
 /// 
@@ -146,7 +147,9 @@ pub async fn store_embeddings_batch(
     Ok(count)
 }
 
-/// Return (chunk_id, chunk_text) for all chunks of a document that have no embedding yet.
+/// Return (chunk_id, text to embed) for all chunks of a document that have no embedding yet
+/// (none when the document is excluded from embedding).
+/// The text includes the contextual header when one was stored at ingest.
 /// Used by the watch module to embed only new chunks after re-ingestion.
 pub async fn get_chunks_without_embedding_for_doc(
     db: &Db,
@@ -156,10 +159,14 @@ pub async fn get_chunks_without_embedding_for_doc(
     let chunks = db
         .with_connection(move |conn| {
             let mut stmt = conn.prepare(
-                "SELECT chunk_id, chunk_text FROM chunks WHERE doc_id = ?1 AND embedding IS NULL",
+                "SELECT c.chunk_id, c.chunk_text, c.context_header FROM chunks c \
+                 JOIN documents d ON d.doc_id = c.doc_id \
+                 WHERE c.doc_id = ?1 AND c.embedding IS NULL AND d.embed = 1",
             )?;
             let rows = stmt.query_map([&doc_id], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                let text: String = row.get(1)?;
+                let context: Option<String> = row.get(2)?;
+                Ok((row.get::<_, String>(0)?, contextualized_text(context.as_deref(), &text)))
             })?;
             let mut out = Vec::new();
             for row in rows {
@@ -171,11 +178,92 @@ pub async fn get_chunks_without_embedding_for_doc(
     Ok(chunks)
 }
 
+/// Characters of document text embedded when a document has no summary
+const DOC_EMBEDDING_OPENING_CHARS: usize = 1500;
+
+/// Text embedded for document-level retrieval
+///
+/// Title plus the `[ingest.contextual]` summary when one exists, otherwise title plus
+/// the opening of the document.
+pub fn document_embedding_text(
+    title: Option<&str>,
+    doc_path: &str,
+    summary: Option<&str>,
+    content: &str,
+) -> String {
+    let title = title.filter(|t| !t.trim().is_empty()).unwrap_or(doc_path);
+    match summary.filter(|s| !s.trim().is_empty()) {
+        Some(summary) => format!("{}\n{}", title, summary),
+        None => {
+            let opening: String = content.chars().take(DOC_EMBEDDING_OPENING_CHARS).collect();
+            format!("{}\n{}", title, opening)
+        }
+    }
+}
+
+/// Return (doc_id, text to embed) for documents that have no document-level embedding yet.
+/// Pass `doc_id` to restrict to a single document (watch module).
+pub async fn get_documents_without_embedding(
+    db: &Db,
+    doc_id: Option<&str>,
+) -> Result<Vec<(String, String)>> {
+    let doc_id = doc_id.map(String::from);
+    db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT doc_id, title, doc_path, summary, content_text
+            FROM documents
+            WHERE embedding IS NULL AND embed = 1 AND (?1 IS NULL OR doc_id = ?1)
+            "#,
+        )?;
+        let rows = stmt.query_map(params![doc_id], |row| {
+            let title: Option<String> = row.get(1)?;
+            let doc_path: String = row.get(2)?;
+            let summary: Option<String> = row.get(3)?;
+            let content: String = row.get(4)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                document_embedding_text(title.as_deref(), &doc_path, summary.as_deref(), &content),
+            ))
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok::<Vec<(String, String)>, RagmcpError>(out)
+    })
+    .await
+}
+
+/// Store document-level embeddings; returns the number of documents updated
+pub async fn store_document_embeddings(
+    db: &Db,
+    embeddings: Vec<(String, Vec<f32>)>,
+) -> Result<usize> {
+    if embeddings.is_empty() {
+        return Ok(0);
+    }
+    db.with_connection(move |conn| {
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        {
+            let mut stmt = tx.prepare("UPDATE documents SET embedding = ?1 WHERE doc_id = ?2")?;
+            for (doc_id, embedding) in embeddings {
+                let bytes: Vec<u8> = embedding.iter().flat_map(|f| f.to_le_bytes()).collect();
+                count += stmt.execute(params![bytes, doc_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok::<usize, RagmcpError>(count)
+    })
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::migrate;
-    use crate::ingest::db_writer::{insert_document, insert_chunks};
+    use crate::ingest::db_writer::{insert_document, insert_chunks, set_embedding_enabled};
     use crate::ingest::chunker::Chunk;
     use std::path::Path;
     use tempfile::TempDir;
@@ -214,6 +302,7 @@ mod tests {
             tokens: 5,
             section_header: Some("Test".to_string()),
             chunk_type: Some("test".to_string()),
+            page: None,
         }];
         
         insert_chunks(db, &doc_id, chunks).await.unwrap();
@@ -416,18 +505,21 @@ mod tests {
                 tokens: 2,
                 section_header: Some("A".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Chunk B".to_string(),
                 tokens: 2,
                 section_header: Some("B".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Chunk C".to_string(),
                 tokens: 2,
                 section_header: Some("C".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
         ];
         insert_chunks(&db, &doc_id, chunks).await.unwrap();
@@ -443,5 +535,64 @@ mod tests {
         assert_eq!(without.len(), 1, "only one chunk without embedding");
         assert_eq!(without[0].0, format!("{}::2", doc_id));
         assert_eq!(without[0].1, "Chunk C");
+
+        // `[[ingest.rules]] embed = false` drops stored embeddings and excludes the document
+        set_embedding_enabled(&db, &doc_id, false).await.unwrap();
+        assert!(get_chunks_without_embedding_for_doc(&db, &doc_id).await.unwrap().is_empty());
+        assert!(get_documents_without_embedding(&db, Some(&doc_id)).await.unwrap().is_empty());
+        assert!(get_embedding(&db, &chunk_a).await.is_err());
+        set_embedding_enabled(&db, &doc_id, true).await.unwrap();
+        assert_eq!(get_chunks_without_embedding_for_doc(&db, &doc_id).await.unwrap().len(), 3);
+    }
+
+    /// Document embeddings are built once and cleared when the document is re-ingested.
+    #[tokio::test]
+    async fn test_document_embeddings_cleared_on_reingest() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let doc_id = insert_document(
+            &db,
+            "guides/setup.md",
+            "markdown",
+            "guides",
+            None,
+            "# Setup Guide\n\nInstall the CLI.",
+            10,
+            "hash_1",
+            std::time::SystemTime::now(),
+        )
+        .await
+        .unwrap();
+
+        let pending = get_documents_without_embedding(&db, Some(&doc_id)).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].1.starts_with("Setup Guide\n# Setup Guide"));
+
+        let stored = store_document_embeddings(&db, vec![(doc_id.clone(), vec![0.5; 4])]).await.unwrap();
+        assert_eq!(stored, 1);
+        assert!(get_documents_without_embedding(&db, None).await.unwrap().is_empty());
+
+        insert_document(
+            &db,
+            "guides/setup.md",
+            "markdown",
+            "guides",
+            None,
+            "# Setup Guide\n\nInstall the CLI v2.",
+            10,
+            "hash_2",
+            std::time::SystemTime::now(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_documents_without_embedding(&db, None).await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_document_embedding_text_prefers_summary() {
+        assert_eq!(
+            document_embedding_text(Some("Setup"), "setup.md", Some("How to install."), "long body"),
+            "Setup\nHow to install."
+        );
+        assert_eq!(document_embedding_text(None, "setup.md", None, "body"), "setup.md\nbody");
     }
 }
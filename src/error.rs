@@ -42,6 +42,10 @@ pub enum RagmcpError {
     /// Invalid input
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    
+    /// File deliberately not ingested (e.g. encrypted or image-only PDF)
+    #[error("Skipped: {0}")]
+    Skipped(String),
 }
 
 /// Convenient Result type using RagmcpError
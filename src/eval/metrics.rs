@@ -77,6 +77,7 @@ mod tests {
             chunk_text: String::new(),
             score: 0.0,
             rank,
+            boosts: Vec::new(),
         }
     }
 
@@ -153,6 +154,7 @@ mod tests {
             chunk_text: String::new(),
             score: 0.0,
             rank: 0,
+            boosts: Vec::new(),
         };
         assert!(query.is_relevant(&first));
         let results = vec![vec![first, make_result("y", 1)]];
@@ -182,6 +184,7 @@ mod tests {
                 chunk_text: String::new(),
                 score: 0.0,
                 rank: 1,
+                boosts: Vec::new(),
             },
         ]];
         let mrr = mean_reciprocal_rank(&[query], &results);
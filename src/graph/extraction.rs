@@ -1,6 +1,7 @@
 //! Relation extraction from document content (regex-based).
 
 use regex::Regex;
+use serde_json::Value;
 use uuid::Uuid;
 
 use super::Relation;
@@ -33,6 +34,45 @@ pub fn extract_routing_relations(agent_name: &str, content: &str) -> Vec<Relatio
     relations
 }
 
+/// `metadata_json` tagging the relations extracted from the e-mail at `doc_path`,
+/// used to replace them on re-ingestion and remove them with the document.
+pub fn email_relation_source(doc_path: &str) -> String {
+    serde_json::json!({ "extracted_from": format!("email:{}", doc_path) }).to_string()
+}
+
+/// Extract thread relations from the metadata of a parsed e-mail.
+/// Yields `message:<id> --in_thread--> thread:<root id>`,
+/// `message:<id> --replies_to--> message:<parent id>` and
+/// `person:<sender address> --sent--> message:<id>`; nothing without a Message-ID.
+pub fn extract_thread_relations(doc_path: &str, metadata: &Value) -> Vec<Relation> {
+    let Some(message_id) = metadata["message_id"].as_str() else {
+        return Vec::new();
+    };
+    let message = format!("message:{}", message_id);
+    let mut edges = Vec::new();
+    if let Some(thread_id) = metadata["thread_id"].as_str() {
+        edges.push((message.clone(), "in_thread", format!("thread:{}", thread_id)));
+    }
+    if let Some(parent) = metadata["in_reply_to"].as_str() {
+        edges.push((message.clone(), "replies_to", format!("message:{}", parent)));
+    }
+    if let Some(sender) = metadata["from_address"].as_str() {
+        edges.push((format!("person:{}", sender), "sent", message.clone()));
+    }
+
+    let source = email_relation_source(doc_path);
+    edges
+        .into_iter()
+        .map(|(source_entity, relation_type, target_entity)| Relation {
+            relation_id: Uuid::new_v4().to_string(),
+            source_entity,
+            relation_type: relation_type.to_string(),
+            target_entity,
+            metadata_json: Some(source.clone()),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +134,32 @@ mod tests {
         let meta = relations[0].metadata_json.as_deref().unwrap();
         assert!(meta.contains("my_agent"));
     }
+
+    #[test]
+    fn test_extract_thread_relations() {
+        let metadata = serde_json::json!({
+            "message_id": "c3@example.com",
+            "in_reply_to": "b2@example.com",
+            "thread_id": "a1@example.com",
+            "from_address": "bo@example.com"
+        });
+        let relations = extract_thread_relations("mail/ops.mbox#3", &metadata);
+        let edges: Vec<(&str, &str, &str)> = relations
+            .iter()
+            .map(|r| (r.source_entity.as_str(), r.relation_type.as_str(), r.target_entity.as_str()))
+            .collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("message:c3@example.com", "in_thread", "thread:a1@example.com"),
+                ("message:c3@example.com", "replies_to", "message:b2@example.com"),
+                ("person:bo@example.com", "sent", "message:c3@example.com"),
+            ]
+        );
+        assert_eq!(
+            relations[0].metadata_json.as_deref(),
+            Some(r#"{"extracted_from":"email:mail/ops.mbox#3"}"#)
+        );
+        assert!(extract_thread_relations("x.eml", &serde_json::json!({ "subject": "hi" })).is_empty());
+    }
 }
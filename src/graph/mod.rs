@@ -1,12 +1,12 @@
 //! Knowledge graph module: relation extraction and BFS traversal.
 //!
-//! Extracts relationships from document content (e.g. agent routing chains)
-//! and traverses the entity_relations graph with depth limits.
+//! Extracts relationships from document content (e.g. agent routing chains) and
+//! e-mail headers (message threads), and traverses the entity_relations graph with depth limits.
 
 mod extraction;
 mod traversal;
 
-pub use extraction::extract_routing_relations;
+pub use extraction::{email_relation_source, extract_routing_relations, extract_thread_relations};
 pub use traversal::traverse_graph;
 
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,96 @@
+use super::{ChunkSizing, Chunker};
+use crate::error::Result;
+use crate::ingest::tokenizer::Tokenizer;
+
+/// Fixed token windows, cut at a word or sentence boundary near the end of the window
+/// when there is one (`chunk_strategy = "fixed"`, the default)
+pub struct FixedChunker;
+
+impl Chunker for FixedChunker {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>> {
+        chunk_text(text, &sizing.tokenizer, sizing.size_tokens, sizing.overlap_tokens)
+    }
+}
+
+/// Chunk text with overlap
+/// 
+/// Chunks hold at most `size_tokens` tokens (a single token-run that cannot be
+/// cut, e.g. one long CJK character sequence split mid-character, may exceed it).
+/// Within the last 20% of a chunk the cut is moved back to a word or sentence
+/// boundary when there is one. Consecutive chunks share `overlap_tokens` tokens
+/// to maintain context continuity.
+/// 
+/// Cuts only happen at token boundaries that are also character boundaries,
+/// so multi-byte UTF-8 characters are never split.
+pub(super) fn chunk_text(
+    text: &str,
+    tokenizer: &Tokenizer,
+    size_tokens: usize,
+    overlap_tokens: usize,
+) -> Result<Vec<String>> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let size_tokens = size_tokens.max(1);
+    // An overlap of a full chunk would never advance
+    let overlap_tokens = overlap_tokens.min(size_tokens / 2);
+    let boundaries = tokenizer.boundaries(text);
+    let last = boundaries.len() - 1;
+    
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    
+    loop {
+        let limit = boundaries[start].1 + size_tokens;
+        
+        // Furthest boundary within the token budget (always at least one step)
+        let mut end = start + 1;
+        while end < last && boundaries[end + 1].1 <= limit {
+            end += 1;
+        }
+        
+        // Try to break at word boundaries if possible
+        if end < last {
+            let search_from = limit.saturating_sub(size_tokens / 5);
+            if let Some(boundary) = (start + 1..=end)
+                .rev()
+                .take_while(|&i| boundaries[i].1 >= search_from)
+                .find(|&i| is_break(text, boundaries[i].0))
+            {
+                end = boundary;
+            }
+        }
+        
+        let chunk_str = text[boundaries[start].0..boundaries[end].0].trim();
+        if !chunk_str.is_empty() {
+            chunks.push(chunk_str.to_string());
+        }
+        
+        if end >= last {
+            break;
+        }
+        
+        // Move start back by the overlap; always past the previous start
+        let target = boundaries[end].1.saturating_sub(overlap_tokens);
+        let mut next = end;
+        while next > start + 1 && boundaries[next - 1].1 >= target {
+            next -= 1;
+        }
+        start = next;
+    }
+    
+    Ok(chunks)
+}
+
+/// Whether cutting `text` at `byte` falls on a word or sentence boundary
+fn is_break(text: &str, byte: usize) -> bool {
+    let before = text[..byte].chars().next_back();
+    let after = text[byte..].chars().next();
+    before.is_some_and(|c| c.is_whitespace() || matches!(c, '.' | '!' | '?'))
+        || after.is_some_and(char::is_whitespace)
+}
@@ -0,0 +1,446 @@
+//! Chunking strategies.
+//!
+//! Sections produced by the parsers are split into chunks by a `Chunker`, selected by
+//! `[performance] chunk_strategy`, per file extension by `[performance.chunk_strategies]`
+//! and per path by `[[ingest.rules]]`:
+//!
+//! - `fixed`: token windows cut near a word or sentence boundary
+//! - `sentence`: whole sentences packed up to the chunk size
+//! - `recursive`: split at paragraphs, then lines, sentences and words until pieces fit
+//! - `structured`: like `recursive`, but code fences and tables are kept whole
+//! - `semantic`: break where the embeddings of adjacent sentences diverge
+
+mod fixed;
+mod recursive;
+mod semantic;
+mod sentence;
+mod structured;
+
+pub use fixed::FixedChunker;
+pub use recursive::RecursiveChunker;
+pub use semantic::SemanticChunker;
+pub use sentence::SentenceChunker;
+pub use structured::StructuredChunker;
+
+use crate::config::Config;
+use crate::error::Result;
+use super::parsers::ParsedDocument;
+use super::rules::FileSettings;
+use super::tokenizer::Tokenizer;
+use fixed::chunk_text;
+use std::ops::Range;
+
+/// A chunk of text with metadata
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    pub text: String,
+    pub tokens: usize,
+    pub section_header: Option<String>,
+    pub chunk_type: Option<String>,
+    /// Page the chunk came from (paginated formats only)
+    pub page: Option<u32>,
+}
+
+/// Chunk size limits shared by all strategies
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkSizing {
+    pub tokenizer: Tokenizer,
+    pub size_tokens: usize,
+    pub overlap_tokens: usize,
+}
+
+impl ChunkSizing {
+    /// Sizes from `[performance]`, counted with the `[embeddings]` tokenizer
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self {
+            tokenizer: Tokenizer::from_config(&config.embeddings)?,
+            size_tokens: config.performance.chunk_size_tokens.max(1),
+            overlap_tokens: config.performance.chunk_overlap_tokens,
+        })
+    }
+
+    fn count(&self, text: &str) -> usize {
+        self.tokenizer.count(text)
+    }
+}
+
+/// Splits the text of one section into chunk texts
+pub trait Chunker: Send + Sync {
+    /// Strategy name (`chunk_strategy` value)
+    fn name(&self) -> &'static str;
+
+    /// Split `text` into trimmed, non-empty chunks within `sizing`
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>>;
+}
+
+/// Chunking strategy selected in `[performance]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkStrategy {
+    Fixed,
+    Sentence,
+    Recursive,
+    Structured,
+    Semantic,
+}
+
+impl ChunkStrategy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fixed" => Some(Self::Fixed),
+            "sentence" => Some(Self::Sentence),
+            "recursive" => Some(Self::Recursive),
+            "structured" => Some(Self::Structured),
+            "semantic" => Some(Self::Semantic),
+            _ => None,
+        }
+    }
+
+    /// Chunker for this strategy; `Semantic` without sentence embeddings packs sentences
+    /// (see `chunker_for`, which embeds them)
+    pub fn chunker(self, config: &Config) -> Box<dyn Chunker> {
+        match self {
+            Self::Fixed => Box::new(FixedChunker),
+            Self::Sentence => Box::new(SentenceChunker),
+            Self::Recursive => Box::new(RecursiveChunker),
+            Self::Structured => Box::new(StructuredChunker),
+            Self::Semantic => Box::new(SemanticChunker::new(
+                Default::default(),
+                config.performance.semantic_breakpoint_percentile,
+            )),
+        }
+    }
+}
+
+/// Chunker for `strategy`, ready to split `parsed`
+///
+/// The semantic strategy embeds every sentence of `parsed` first (one embeddings
+/// request per batch); without an API key it falls back to sentence packing.
+pub async fn chunker_for(parsed: &ParsedDocument, strategy: ChunkStrategy, config: &Config) -> Box<dyn Chunker> {
+    match strategy {
+        ChunkStrategy::Semantic => Box::new(SemanticChunker::prepare(parsed, config).await),
+        other => other.chunker(config),
+    }
+}
+
+/// Chunk `parsed` with the strategy and sizes resolved for its file (see `FileSettings`)
+///
+/// Returns the chunks and the token count of the whole document.
+pub async fn chunk_with_config(
+    parsed: &ParsedDocument,
+    settings: &FileSettings,
+    config: &Config,
+) -> Result<(Vec<Chunk>, usize)> {
+    let sizing = ChunkSizing {
+        size_tokens: settings.chunk_size_tokens.max(1),
+        overlap_tokens: settings.chunk_overlap_tokens,
+        ..ChunkSizing::from_config(config)?
+    };
+    let chunker = chunker_for(parsed, settings.chunk_strategy, config).await;
+    let chunks = chunk_document(parsed, chunker.as_ref(), &sizing)?;
+    Ok((chunks, sizing.count(&parsed.content)))
+}
+
+/// Chunk a parsed document section by section
+/// 
+/// Chunks never span two sections; token counts come from `sizing.tokenizer`.
+pub fn chunk_document(
+    parsed: &ParsedDocument,
+    chunker: &dyn Chunker,
+    sizing: &ChunkSizing,
+) -> Result<Vec<Chunk>> {
+    let mut chunks = Vec::new();
+    
+    for section in &parsed.sections {
+        // Chunk by semantic boundaries (section level)
+        let section_chunks = chunker.split(&section.content, sizing)?;
+        
+        for chunk_text in section_chunks {
+            let tokens = sizing.count(&chunk_text);
+            
+            chunks.push(Chunk {
+                text: chunk_text,
+                tokens,
+                section_header: Some(section.header.clone()),
+                chunk_type: section.section_type.clone(),
+                page: section.page,
+            });
+        }
+    }
+    
+    // If document has no sections or sections produced no chunks, chunk the full content
+    if chunks.is_empty() {
+        let full_chunks = chunker.split(&parsed.content, sizing)?;
+        
+        for chunk_text in full_chunks {
+            let tokens = sizing.count(&chunk_text);
+            chunks.push(Chunk {
+                text: chunk_text,
+                tokens,
+                section_header: None,
+                chunk_type: None,
+                page: None,
+            });
+        }
+    }
+    
+    Ok(chunks)
+}
+
+/// Greedily pack consecutive `units` (adjacent byte ranges of `text`) into chunks of
+/// at most `sizing.size_tokens` tokens
+///
+/// A unit larger than a chunk is cut into fixed windows. With `overlap`, a chunk starts
+/// with the trailing units of the previous chunk that fit in `sizing.overlap_tokens`.
+/// `text` is tokenized once and ranges are measured by the tokens between their ends,
+/// which can differ by a token from encoding the range on its own.
+fn pack(text: &str, units: &[Range<usize>], sizing: &ChunkSizing, overlap: bool) -> Result<Vec<String>> {
+    let boundaries = sizing.tokenizer.boundaries(text);
+    // Tokens that end at or before `offset`
+    let tokens_at = |offset: usize| match boundaries.binary_search_by_key(&offset, |b| b.0) {
+        Ok(idx) => boundaries[idx].1,
+        Err(idx) => boundaries[idx - 1].1,
+    };
+    let count = |range: Range<usize>| tokens_at(range.end).saturating_sub(tokens_at(range.start));
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    
+    while start < units.len() {
+        let first = units[start].start;
+        if count(units[start].clone()) > sizing.size_tokens {
+            chunks.extend(chunk_text(
+                &text[units[start].clone()],
+                &sizing.tokenizer,
+                sizing.size_tokens,
+                sizing.overlap_tokens,
+            )?);
+            start += 1;
+            continue;
+        }
+        
+        let mut end = start + 1;
+        while end < units.len() && count(first..units[end].end) <= sizing.size_tokens {
+            end += 1;
+        }
+        let chunk = text[first..units[end - 1].end].trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+        if end >= units.len() {
+            break;
+        }
+        
+        // Step back over the units repeated as overlap, always past the previous start
+        let mut next = end;
+        if overlap {
+            let last = units[end - 1].end;
+            while next > start + 1 && count(units[next - 1].start..last) <= sizing.overlap_tokens {
+                next -= 1;
+            }
+        }
+        start = next;
+    }
+    
+    Ok(chunks)
+}
+
+/// Estimate token count from text
+/// 
+/// Uses approximation: 1 token ≈ 4 characters. This is what the `chars`
+/// tokenizer counts; BPE tokenizers (see `Tokenizer`) are exact.
+pub fn estimate_tokens(text: &str) -> usize {
+    // Rough estimation: 1 token ≈ 4 characters
+    (text.len() + 3) / 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PerformanceConfig;
+    use crate::ingest::parsers::Section;
+    
+    fn test_config() -> PerformanceConfig {
+        PerformanceConfig {
+            max_latency_ms: 1000,
+            chunk_size_tokens: 300,
+            chunk_overlap_tokens: 50,
+            chunk_strategy: "fixed".to_string(),
+            chunk_strategies: [("md".to_string(), "structured".to_string())].into_iter().collect(),
+            semantic_breakpoint_percentile: 90.0,
+            semantic_max_sentences: 2000,
+        }
+    }
+    
+    #[test]
+    fn test_estimate_tokens() {
+        // 4 chars = 1 token
+        assert_eq!(estimate_tokens("test"), 1);
+        // 8 chars = 2 tokens
+        assert_eq!(estimate_tokens("testtest"), 2);
+        // 10 chars = 3 tokens (rounds up)
+        assert_eq!(estimate_tokens("testtest12"), 3);
+    }
+    
+    #[test]
+    fn test_chunk_text() {
+        let config = test_config();
+        let text = "a ".repeat(1000); // ~2000 chars = ~500 tokens
+        
+        let chunks = chunk_text(&text, &Tokenizer::Chars, config.chunk_size_tokens, config.chunk_overlap_tokens).unwrap();
+        
+        assert!(!chunks.is_empty());
+        // Should have multiple chunks for 500 tokens with 300 token size
+        assert!(chunks.len() >= 2);
+        
+        // Check overlap between chunks
+        // Overlap exists if the end of chunk[0] appears at the start of chunk[1]
+        if chunks.len() > 1 {
+            // Find the longest suffix of chunk[0] that is a prefix of chunk[1]
+            let chunk0 = &chunks[0];
+            let chunk1 = &chunks[1];
+            let mut overlap_found = false;
+            
+            // Check for overlap by finding common substring at boundaries
+            let min_overlap = (config.chunk_overlap_tokens * 4).min(chunk0.len().min(chunk1.len()));
+            for overlap_len in (1..=min_overlap).rev() {
+                if chunk0.len() >= overlap_len && chunk1.len() >= overlap_len {
+                    // Safe slicing: ensure we slice at character boundaries
+                    let suffix_start = chunk0.len().saturating_sub(overlap_len);
+                    // Find nearest char boundary before suffix_start
+                    let mut suffix_start_safe = suffix_start;
+                    while suffix_start_safe < chunk0.len() && !chunk0.is_char_boundary(suffix_start_safe) {
+                        suffix_start_safe += 1;
+                    }
+                    if suffix_start_safe > 0 && !chunk0.is_char_boundary(suffix_start_safe) {
+                        suffix_start_safe = suffix_start_safe.saturating_sub(1);
+                        while suffix_start_safe > 0 && !chunk0.is_char_boundary(suffix_start_safe) {
+                            suffix_start_safe -= 1;
+                        }
+                    }
+                    
+                    // Find nearest char boundary at or after overlap_len
+                    let mut prefix_end_safe = overlap_len;
+                    while prefix_end_safe < chunk1.len() && !chunk1.is_char_boundary(prefix_end_safe) {
+                        prefix_end_safe += 1;
+                    }
+                    
+                    if let (Some(suffix), Some(prefix)) = (
+                        chunk0.get(suffix_start_safe..),
+                        chunk1.get(..prefix_end_safe)
+                    ) {
+                        if suffix == prefix {
+                            overlap_found = true;
+                            break;
+                        }
+                    }
+                }
+            }
+            
+            // If chunks are adjacent (no gap), there should be some overlap
+            // Even if exact match isn't found, chunks should be close together
+            assert!(
+                overlap_found || chunks.len() == 2,
+                "Expected overlap between chunks or only 2 chunks total"
+            );
+        }
+    }
+    
+    #[test]
+    fn test_chunk_document() {
+        let config = test_config();
+        let parsed = ParsedDocument {
+            content: "Full content".to_string(),
+            sections: vec![
+                Section {
+                    header: "Section 1".to_string(),
+                    content: "a ".repeat(1000), // ~500 tokens
+                    section_type: Some("test".to_string()),
+                    page: None,
+                },
+            ],
+            doc_type: "test".to_string(),
+            metadata: None,
+        };
+        
+        let sizing = ChunkSizing {
+            tokenizer: Tokenizer::from_name("cl100k_base").unwrap(),
+            size_tokens: config.chunk_size_tokens,
+            overlap_tokens: config.chunk_overlap_tokens,
+        };
+        let chunks = chunk_document(&parsed, &FixedChunker, &sizing).unwrap();
+        
+        assert!(!chunks.is_empty());
+        assert_eq!(chunks[0].section_header, Some("Section 1".to_string()));
+        assert_eq!(chunks[0].chunk_type, Some("test".to_string()));
+        
+        // Check token estimates are reasonable
+        for chunk in &chunks {
+            assert!(chunk.tokens > 0);
+            assert!(chunk.tokens <= config.chunk_size_tokens * 2); // Allow some flexibility
+        }
+    }
+    
+    #[test]
+    fn test_chunk_empty_text() {
+        let chunks = chunk_text("", &Tokenizer::Chars, 300, 50).unwrap();
+        assert!(chunks.is_empty());
+    }
+    
+    #[test]
+    fn test_chunk_text_bpe_token_limits() {
+        let tokenizer = Tokenizer::from_name("cl100k_base").unwrap();
+        let mut text: String = (0..60).map(|i| format!("let value_{} = compute({});\n", i, i)).collect();
+        text.push_str(&"検索拡張生成は文書を小さな断片に分割します。".repeat(20));
+        
+        let chunks = chunk_text(&text, &tokenizer, 100, 20).unwrap();
+        
+        assert!(chunks.len() > 3);
+        for chunk in &chunks {
+            assert!(tokenizer.count(chunk) <= 100, "chunk has {} tokens", tokenizer.count(chunk));
+        }
+        // Neighbouring chunks overlap: the last full line of one starts the next
+        let last_line = chunks[0].lines().last().unwrap();
+        assert!(chunks[1].contains(last_line));
+        assert!(text.trim_end().ends_with(chunks.last().unwrap().as_str()));
+    }
+    
+    #[test]
+    fn test_strategy_selection() {
+        let config = test_config();
+        assert_eq!(config.chunk_strategy_for("MD"), "structured");
+        assert_eq!(config.chunk_strategy_for("rs"), "fixed");
+        assert_eq!(ChunkStrategy::from_name("Recursive"), Some(ChunkStrategy::Recursive));
+        assert_eq!(ChunkStrategy::from_name("token"), None);
+    }
+    
+    #[test]
+    fn test_every_strategy_respects_chunk_size() {
+        let sizing = ChunkSizing {
+            tokenizer: Tokenizer::from_name("cl100k_base").unwrap(),
+            size_tokens: 50,
+            overlap_tokens: 10,
+        };
+        let mut text = String::new();
+        for i in 0..8 {
+            text.push_str(&format!("Step {} restarts the worker pool. Then it checks the queue depth.\n\n", i));
+            text.push_str(&format!("```sh\nsystemctl restart worker@{}\n```\n\n- verify\n- report\n\n", i));
+        }
+        let chunkers: [&dyn Chunker; 5] = [
+            &FixedChunker,
+            &SentenceChunker,
+            &RecursiveChunker,
+            &StructuredChunker,
+            &SemanticChunker::new(Default::default(), 90.0),
+        ];
+        
+        for chunker in chunkers {
+            let chunks = chunker.split(&text, &sizing).unwrap();
+            assert!(chunks.len() > 4, "{}", chunker.name());
+            for chunk in &chunks {
+                assert!(!chunk.is_empty() && chunk.trim() == chunk);
+                assert!(sizing.tokenizer.count(chunk) <= 50, "{}: {:?}", chunker.name(), chunk);
+            }
+            assert!(chunks.last().unwrap().ends_with("- report"), "{}", chunker.name());
+        }
+    }
+}
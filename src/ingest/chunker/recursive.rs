@@ -0,0 +1,101 @@
+use super::sentence::sentence_ranges;
+use super::{pack, ChunkSizing, Chunker};
+use crate::error::Result;
+use std::ops::Range;
+
+/// Recursive separator splitting (`chunk_strategy = "recursive"`)
+///
+/// Text that does not fit a chunk is split at paragraphs; pieces that still do not fit
+/// are split at lines, then sentences, then words. The pieces are then packed back
+/// together up to the chunk size with overlap.
+pub struct RecursiveChunker;
+
+impl Chunker for RecursiveChunker {
+    fn name(&self) -> &'static str {
+        "recursive"
+    }
+
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut units = Vec::new();
+        split_units(text, 0..text.len(), 0, sizing, &mut units);
+        pack(text, &units, sizing, true)
+    }
+}
+
+/// Split `range` of `text` with the separator at `level` (paragraph, line, sentence,
+/// word) and recurse into pieces that are still larger than a chunk
+pub(super) fn split_units(
+    text: &str,
+    range: Range<usize>,
+    level: usize,
+    sizing: &ChunkSizing,
+    units: &mut Vec<Range<usize>>,
+) {
+    if sizing.count(&text[range.clone()]) <= sizing.size_tokens || level > 3 {
+        units.push(range);
+        return;
+    }
+    let offset = range.start;
+    let slice = &text[range];
+    let pieces: Vec<Range<usize>> = match level {
+        0 => split_after(slice, "\n\n"),
+        1 => split_after(slice, "\n"),
+        2 => sentence_ranges(slice),
+        _ => split_after(slice, " "),
+    };
+    for piece in pieces {
+        split_units(text, piece.start + offset..piece.end + offset, level + 1, sizing, units);
+    }
+}
+
+/// Adjacent ranges covering `text`, each ending after an occurrence of `separator`
+fn split_after(text: &str, separator: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for (pos, _) in text.match_indices(separator) {
+        let end = pos + separator.len();
+        if end > start {
+            ranges.push(start..end);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        ranges.push(start..text.len());
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::tokenizer::Tokenizer;
+
+    #[test]
+    fn test_recursive_prefers_paragraphs() {
+        let paragraph = |n: usize| format!("Paragraph {} explains one idea in a few words.", n);
+        let text: String = (1..=6).map(|n| paragraph(n) + "\n\n").collect();
+        let sizing = ChunkSizing { tokenizer: Tokenizer::from_name("cl100k_base").unwrap(), size_tokens: 30, overlap_tokens: 0 };
+
+        let chunks = RecursiveChunker.split(&text, &sizing).unwrap();
+
+        // Two paragraphs fit a chunk; no paragraph is cut
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], format!("{}\n\n{}", paragraph(1), paragraph(2)));
+        assert!(chunks.iter().all(|c| sizing.tokenizer.count(c) <= 30));
+    }
+
+    #[test]
+    fn test_recursive_falls_back_to_words() {
+        let text = "word ".repeat(100);
+        let sizing = ChunkSizing { tokenizer: Tokenizer::Chars, size_tokens: 20, overlap_tokens: 5 };
+
+        let chunks = RecursiveChunker.split(&text, &sizing).unwrap();
+
+        assert!(chunks.len() > 5);
+        assert!(chunks.iter().all(|c| c.split(' ').all(|w| w == "word")));
+        assert!(chunks.iter().all(|c| Tokenizer::Chars.count(c) <= 20));
+    }
+}
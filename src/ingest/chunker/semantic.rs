@@ -0,0 +1,253 @@
+use super::sentence::{sentence_ranges, SentenceChunker};
+use super::{pack, ChunkSizing, Chunker};
+use crate::cache::EmbeddingCache;
+use crate::config::Config;
+use crate::embeddings::OpenAIEmbedder;
+use crate::error::Result;
+use crate::ingest::parsers::ParsedDocument;
+use crate::search::vector::cosine_similarity;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Sentence embeddings kept per process (about 60 MB at 1536 dimensions)
+const SENTENCE_CACHE_CAPACITY: usize = 10_000;
+
+/// Embedding-similarity breakpoint chunking (`chunk_strategy = "semantic"`)
+///
+/// Adjacent sentences are compared by the cosine distance of their embeddings; a new
+/// chunk starts where the distance is above the `semantic_breakpoint_percentile` of all
+/// distances in the section, i.e. where the topic shifts. Groups larger than a chunk are
+/// packed by sentence. Sentences without an embedding fall back to `SentenceChunker`.
+pub struct SemanticChunker {
+    embeddings: HashMap<String, Vec<f32>>,
+    percentile: f32,
+}
+
+impl SemanticChunker {
+    /// Chunker using precomputed sentence embeddings (keyed by trimmed sentence text)
+    pub fn new(embeddings: HashMap<String, Vec<f32>>, percentile: f32) -> Self {
+        Self { embeddings, percentile }
+    }
+
+    /// Embed every sentence of `parsed` with the configured embedding model
+    ///
+    /// Sentences embedded before by this process (e.g. the unchanged part of a file the
+    /// watcher re-ingests) come from a cache keyed by the sentence hash; only the rest is
+    /// sent to the API. Documents over `semantic_max_sentences`, a missing API key or a
+    /// failed request leave the chunker without embeddings, so it packs sentences instead.
+    pub async fn prepare(parsed: &ParsedDocument, config: &Config) -> Self {
+        let percentile = config.performance.semantic_breakpoint_percentile;
+        let texts: Vec<&str> = if parsed.sections.is_empty() {
+            vec![parsed.content.as_str()]
+        } else {
+            parsed.sections.iter().map(|s| s.content.as_str()).collect()
+        };
+        let mut sentences: Vec<String> = texts
+            .iter()
+            .flat_map(|text| sentence_ranges(text).into_iter().map(move |r| text[r].trim().to_string()))
+            .filter(|s| !s.is_empty())
+            .collect();
+        sentences.sort();
+        sentences.dedup();
+        if sentences.len() > config.performance.semantic_max_sentences {
+            log::warn!(
+                "{} sentences exceed semantic_max_sentences ({}); using sentence chunking",
+                sentences.len(),
+                config.performance.semantic_max_sentences
+            );
+            return Self::new(HashMap::new(), percentile);
+        }
+
+        let model = &config.embeddings.model;
+        let cache = sentence_cache();
+        let mut embeddings = HashMap::new();
+        let mut missing = Vec::new();
+        for sentence in sentences {
+            match cache.get(&sentence_key(model, &sentence)) {
+                Some(vector) => {
+                    embeddings.insert(sentence, vector);
+                }
+                None => missing.push(sentence),
+            }
+        }
+        if missing.is_empty() {
+            return Self::new(embeddings, percentile);
+        }
+
+        let api_key = match std::env::var(&config.embeddings.api_key_env) {
+            Ok(key) => key,
+            Err(_) => {
+                log::warn!(
+                    "{} not set; semantic chunking falls back to sentence chunking",
+                    config.embeddings.api_key_env
+                );
+                return Self::new(HashMap::new(), percentile);
+            }
+        };
+        let embedder = OpenAIEmbedder::new(api_key, model.clone(), config.embeddings.batch_size);
+        match embedder.embed_batch(missing.clone()).await {
+            Ok(vectors) => {
+                for (sentence, vector) in missing.into_iter().zip(vectors) {
+                    cache.put(sentence_key(model, &sentence), vector.clone());
+                    embeddings.insert(sentence, vector);
+                }
+                Self::new(embeddings, percentile)
+            }
+            Err(e) => {
+                log::warn!("Sentence embeddings failed, using sentence chunking: {}", e);
+                Self::new(HashMap::new(), percentile)
+            }
+        }
+    }
+}
+
+fn sentence_cache() -> &'static EmbeddingCache {
+    static CACHE: OnceLock<EmbeddingCache> = OnceLock::new();
+    CACHE.get_or_init(|| EmbeddingCache::new(SENTENCE_CACHE_CAPACITY))
+}
+
+/// Cache key of a sentence embedded with `model`
+fn sentence_key(model: &str, sentence: &str) -> String {
+    format!("{}:{:x}", model, Sha256::digest(sentence.as_bytes()))
+}
+
+impl Chunker for SemanticChunker {
+    fn name(&self) -> &'static str {
+        "semantic"
+    }
+
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>> {
+        let sentences: Vec<_> = sentence_ranges(text)
+            .into_iter()
+            .filter(|r| !text[r.clone()].trim().is_empty())
+            .collect();
+        let vectors: Option<Vec<&Vec<f32>>> =
+            sentences.iter().map(|r| self.embeddings.get(text[r.clone()].trim())).collect();
+        let Some(vectors) = vectors.filter(|v| v.len() > 1) else {
+            return SentenceChunker.split(text, sizing);
+        };
+
+        let distances: Vec<f32> = vectors.windows(2).map(|w| 1.0 - cosine_similarity(w[0], w[1])).collect();
+        let threshold = percentile(&distances, self.percentile);
+
+        let mut chunks = Vec::new();
+        let mut group_start = 0;
+        for (idx, distance) in distances.iter().enumerate() {
+            if *distance > threshold {
+                chunks.extend(pack(text, &sentences[group_start..=idx], sizing, false)?);
+                group_start = idx + 1;
+            }
+        }
+        chunks.extend(pack(text, &sentences[group_start..], sizing, false)?);
+        Ok(chunks)
+    }
+}
+
+/// `p`-th percentile (0-100) of `values`, linearly interpolated
+fn percentile(values: &[f32], p: f32) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::tokenizer::Tokenizer;
+
+    #[test]
+    fn test_semantic_breaks_at_topic_shift() {
+        let text = "The gateway routes requests. It retries on timeouts. \
+Billing runs monthly. Invoices are e-mailed.";
+        let embeddings: HashMap<String, Vec<f32>> = [
+            ("The gateway routes requests.", vec![1.0, 0.1]),
+            ("It retries on timeouts.", vec![0.9, 0.2]),
+            ("Billing runs monthly.", vec![0.1, 1.0]),
+            ("Invoices are e-mailed.", vec![0.2, 0.9]),
+        ]
+        .into_iter()
+        .map(|(s, v)| (s.to_string(), v))
+        .collect();
+        let sizing = ChunkSizing { tokenizer: Tokenizer::from_name("cl100k_base").unwrap(), size_tokens: 300, overlap_tokens: 50 };
+
+        let chunks = SemanticChunker::new(embeddings, 50.0).split(text, &sizing).unwrap();
+
+        assert_eq!(
+            chunks,
+            vec![
+                "The gateway routes requests. It retries on timeouts.",
+                "Billing runs monthly. Invoices are e-mailed.",
+            ]
+        );
+        // Unknown sentences: plain sentence packing
+        let fallback = SemanticChunker::new(HashMap::new(), 50.0).split(text, &sizing).unwrap();
+        assert_eq!(fallback, vec![text]);
+    }
+
+    fn config(max_sentences: usize) -> Config {
+        toml::from_str(&format!(
+            r#"
+[ragmcp]
+rag_folder = "."
+db_path = "./test.db"
+
+[embeddings]
+provider = "openai"
+model = "semantic-cache-test"
+api_key_env = "RAGMCP_TEST_UNSET_KEY"
+batch_size = 100
+dimensions = 2
+
+[search]
+default_k = 5
+min_score = 0.65
+hybrid_bm25_weight = 0.5
+hybrid_vector_weight = 0.5
+
+[performance]
+max_latency_ms = 1000
+chunk_size_tokens = 300
+chunk_overlap_tokens = 50
+semantic_max_sentences = {}
+"#,
+            max_sentences
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prepare_uses_cache_and_sentence_cap() {
+        let text = "Queues drain nightly. Workers restart on failure. Budgets close quarterly.";
+        let parsed = ParsedDocument {
+            content: text.to_string(),
+            sections: Vec::new(),
+            doc_type: "text".to_string(),
+            metadata: None,
+        };
+        for (sentence, vector) in [
+            ("Queues drain nightly.", vec![1.0, 0.0]),
+            ("Workers restart on failure.", vec![0.9, 0.1]),
+            ("Budgets close quarterly.", vec![0.0, 1.0]),
+        ] {
+            sentence_cache().put(sentence_key("semantic-cache-test", sentence), vector);
+        }
+
+        // Every sentence is cached, so no API key is needed
+        let chunker = SemanticChunker::prepare(&parsed, &config(10)).await;
+        assert_eq!(chunker.embeddings.len(), 3);
+        assert_eq!(chunker.embeddings["Budgets close quarterly."], vec![0.0, 1.0]);
+
+        let capped = SemanticChunker::prepare(&parsed, &config(2)).await;
+        assert!(capped.embeddings.is_empty());
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[0.1, 0.3, 0.2], 50.0), 0.2);
+        assert_eq!(percentile(&[0.1, 0.3], 100.0), 0.3);
+        assert!((percentile(&[0.0, 1.0], 25.0) - 0.25).abs() < 1e-6);
+    }
+}
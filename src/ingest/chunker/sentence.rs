@@ -0,0 +1,132 @@
+use super::{pack, ChunkSizing, Chunker};
+use crate::error::Result;
+use std::ops::Range;
+
+/// Whole sentences packed up to the chunk size, overlapping by whole sentences
+/// (`chunk_strategy = "sentence"`)
+///
+/// Lines of lists, tables and headings count as sentences of their own, so they are
+/// not glued to the following paragraph mid-line.
+pub struct SentenceChunker;
+
+impl Chunker for SentenceChunker {
+    fn name(&self) -> &'static str {
+        "sentence"
+    }
+
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        pack(text, &sentence_ranges(text), sizing, true)
+    }
+}
+
+/// Adjacent byte ranges covering `text`, one per sentence
+///
+/// A sentence ends after `.`, `!` or `?` followed by whitespace and a word that does not
+/// start in lowercase (so `e.g. this` stays together), after CJK full stops, and at line
+/// breaks before a blank line or around block lines (list items, headings, tables, quotes,
+/// code fences). Whitespace after a sentence belongs to the next one.
+pub(super) fn sentence_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut line_start = 0;
+
+    for (pos, c) in text.char_indices() {
+        let end = pos + c.len_utf8();
+        let rest = &text[end..];
+        let cut = match c {
+            '.' | '!' | '?' => {
+                rest.starts_with(char::is_whitespace)
+                    && !rest.trim_start().starts_with(char::is_lowercase)
+            }
+            '。' | '！' | '？' => true,
+            '\n' => {
+                let next_line = rest.lines().next().unwrap_or("");
+                next_line.trim().is_empty()
+                    || is_block_line(next_line)
+                    || is_block_line(&text[line_start..pos])
+            }
+            _ => false,
+        };
+        if c == '\n' {
+            line_start = end;
+        }
+        if cut && !text[start..end].trim().is_empty() {
+            ranges.push(start..end);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        match ranges.last_mut() {
+            // Trailing whitespace joins the last sentence
+            Some(last) if text[start..].trim().is_empty() => last.end = text.len(),
+            _ => ranges.push(start..text.len()),
+        }
+    }
+    ranges
+}
+
+/// List item, heading, table row, quote or code fence line
+fn is_block_line(line: &str) -> bool {
+    let line = line.trim_start();
+    let ordered = line
+        .split_once(['.', ')'])
+        .is_some_and(|(n, rest)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()) && rest.starts_with(' '));
+    ordered
+        || ["- ", "* ", "+ ", "#", ">", "|", "```", "~~~"]
+            .iter()
+            .any(|marker| line.starts_with(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::tokenizer::Tokenizer;
+
+    fn sentences(text: &str) -> Vec<&str> {
+        sentence_ranges(text).into_iter().map(|r| text[r].trim()).collect()
+    }
+
+    #[test]
+    fn test_sentence_ranges() {
+        let text = "Restart the gateway. Check logs, e.g. the access log! Done?\n\
+Steps:\n- drain\n- restart\n\nA wrapped\nparagraph line. 検索します。次へ";
+        assert_eq!(
+            sentences(text),
+            vec![
+                "Restart the gateway.",
+                "Check logs, e.g. the access log!",
+                "Done?",
+                "Steps:",
+                "- drain",
+                "- restart",
+                "A wrapped\nparagraph line.",
+                "検索します。",
+                "次へ",
+            ]
+        );
+        let ranges = sentence_ranges(text);
+        assert_eq!(ranges.first().unwrap().start, 0);
+        assert_eq!(ranges.last().unwrap().end, text.len());
+        assert!(ranges.windows(2).all(|w| w[0].end == w[1].start));
+    }
+
+    #[test]
+    fn test_sentence_chunks_keep_sentences_whole() {
+        let text: String = (1..=30).map(|i| format!("Sentence number {} is about topic {}. ", i, i)).collect();
+        let sizing = ChunkSizing { tokenizer: Tokenizer::from_name("cl100k_base").unwrap(), size_tokens: 40, overlap_tokens: 10 };
+
+        let chunks = SentenceChunker.split(&text, &sizing).unwrap();
+
+        assert!(chunks.len() > 5);
+        for chunk in &chunks {
+            assert!(chunk.starts_with("Sentence number") && chunk.ends_with('.'), "{}", chunk);
+            assert!(sizing.tokenizer.count(chunk) <= 40);
+        }
+        // The last sentence of a chunk is repeated as overlap
+        let last = chunks[0].rsplit(". ").next().unwrap();
+        assert!(chunks[1].starts_with(last.trim_end_matches('.')));
+    }
+}
@@ -0,0 +1,185 @@
+use super::recursive::split_units;
+use super::{chunk_text, pack, ChunkSizing, Chunker};
+use crate::error::Result;
+use std::ops::Range;
+
+/// Recursive splitting that keeps code fences and tables whole
+/// (`chunk_strategy = "structured"`)
+///
+/// A fenced code block or table that fits a chunk is never cut. One that does not fit is
+/// split between lines, and every part repeats the opening fence (and closing fence) or
+/// the table header, so each chunk is still a readable block on its own.
+pub struct StructuredChunker;
+
+impl Chunker for StructuredChunker {
+    fn name(&self) -> &'static str {
+        "structured"
+    }
+
+    fn split(&self, text: &str, sizing: &ChunkSizing) -> Result<Vec<String>> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut chunks = Vec::new();
+        let mut units = Vec::new();
+        for block in blocks(text) {
+            let range = block.range();
+            match block {
+                Block::Text(_) => split_units(text, range, 0, sizing, &mut units),
+                _ if sizing.count(&text[range.clone()]) <= sizing.size_tokens => units.push(range),
+                Block::Code(_) | Block::Table(_) => {
+                    chunks.extend(pack(text, &units, sizing, true)?);
+                    units.clear();
+                    chunks.extend(split_block(&text[range], &block, sizing)?);
+                }
+            }
+        }
+        chunks.extend(pack(text, &units, sizing, true)?);
+        Ok(chunks)
+    }
+}
+
+enum Block {
+    Text(Range<usize>),
+    Code(Range<usize>),
+    Table(Range<usize>),
+}
+
+impl Block {
+    fn range(&self) -> Range<usize> {
+        match self {
+            Block::Text(r) | Block::Code(r) | Block::Table(r) => r.clone(),
+        }
+    }
+}
+
+/// Adjacent text, code fence and table blocks covering `text`
+fn blocks(text: &str) -> Vec<Block> {
+    let mut lines = Vec::new();
+    let mut pos = 0;
+    for line in text.split_inclusive('\n') {
+        lines.push((pos, line));
+        pos += line.len();
+    }
+
+    let mut blocks = Vec::new();
+    let mut text_start = 0;
+    let mut idx = 0;
+    while idx < lines.len() {
+        let (start, line) = lines[idx];
+        let trimmed = line.trim_start();
+        let block_end = if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            // Through the closing fence (or the end of the text if it is missing)
+            let fence = &trimmed[..3];
+            let close = lines[idx + 1..].iter().position(|(_, l)| l.trim_start().starts_with(fence));
+            Some((close.map_or(lines.len(), |c| idx + c + 2), true))
+        } else if trimmed.starts_with('|') {
+            let rows = lines[idx..].iter().take_while(|(_, l)| l.trim_start().starts_with('|')).count();
+            (rows >= 2).then_some((idx + rows, false))
+        } else {
+            None
+        };
+
+        match block_end {
+            Some((end_idx, is_code)) => {
+                if text_start < start {
+                    blocks.push(Block::Text(text_start..start));
+                }
+                let end = lines.get(end_idx).map_or(text.len(), |(p, _)| *p);
+                blocks.push(if is_code { Block::Code(start..end) } else { Block::Table(start..end) });
+                text_start = end;
+                idx = end_idx;
+            }
+            None => idx += 1,
+        }
+    }
+    if text_start < text.len() {
+        blocks.push(Block::Text(text_start..text.len()));
+    }
+    blocks
+}
+
+/// Split an oversized code fence or table between lines, repeating its head (opening
+/// fence, table header and separator row) and tail (closing fence) in every part
+fn split_block(block: &str, kind: &Block, sizing: &ChunkSizing) -> Result<Vec<String>> {
+    let lines: Vec<&str> = block.trim_end().lines().collect();
+    let (head, tail) = match kind {
+        Block::Code(_) => {
+            let closed = lines.len() > 1 && lines[lines.len() - 1].trim_start().starts_with(&lines[0].trim_start()[..3]);
+            (1, usize::from(closed))
+        }
+        _ => {
+            let separator = lines.get(1).is_some_and(|l| l.trim().chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')));
+            (1 + usize::from(separator), 0)
+        }
+    };
+    let head_lines = &lines[..head];
+    let tail_lines = &lines[lines.len() - tail..];
+    let body = &lines[head..lines.len() - tail];
+
+    let render = |rows: &[&str]| -> String {
+        head_lines.iter().chain(rows).chain(tail_lines).copied().collect::<Vec<_>>().join("\n")
+    };
+    // Rows are counted once (plus their line break) instead of re-encoding each part as it grows
+    let head_tokens = sizing.count(&render(&[]));
+    let row_tokens: Vec<usize> = body.iter().map(|row| sizing.count(row) + 1).collect();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    while start < body.len() {
+        let mut tokens = head_tokens + row_tokens[start];
+        let mut end = start + 1;
+        while end < body.len() && tokens + row_tokens[end] <= sizing.size_tokens {
+            tokens += row_tokens[end];
+            end += 1;
+        }
+        let part = render(&body[start..end]);
+        if sizing.count(&part) > sizing.size_tokens {
+            // A single line longer than a chunk
+            parts.extend(chunk_text(&part, &sizing.tokenizer, sizing.size_tokens, sizing.overlap_tokens)?);
+        } else {
+            parts.push(part);
+        }
+        start = end;
+    }
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::tokenizer::Tokenizer;
+
+    fn sizing(size_tokens: usize) -> ChunkSizing {
+        ChunkSizing { tokenizer: Tokenizer::from_name("cl100k_base").unwrap(), size_tokens, overlap_tokens: 0 }
+    }
+
+    #[test]
+    fn test_code_fence_and_table_stay_whole() {
+        let text = "Install the package first and then configure it.\n\n\
+```sh\napt install ragmcp\nragmcp --version\n```\n\n\
+| flag | meaning |\n| --- | --- |\n| -v | verbose |\n\nThat is all.";
+
+        let chunks = StructuredChunker.split(text, &sizing(25)).unwrap();
+
+        assert!(chunks.iter().any(|c| c.contains("```sh\napt install ragmcp\nragmcp --version\n```")));
+        assert!(chunks.iter().any(|c| c.starts_with("| flag | meaning |") && c.contains("| -v | verbose |")));
+        assert!(chunks.iter().all(|c| c.matches("```").count() % 2 == 0));
+    }
+
+    #[test]
+    fn test_oversized_blocks_repeat_fence_and_header() {
+        let code: String = (0..40).map(|i| format!("let value_{} = {};\n", i, i)).collect();
+        let rows: String = (0..40).map(|i| format!("| E{} | error {} |\n", i, i)).collect();
+        let text = format!("```rust\n{}```\n\n| code | message |\n|---|---|\n{}", code, rows);
+
+        let chunks = StructuredChunker.split(&text, &sizing(60)).unwrap();
+
+        let code_parts: Vec<&String> = chunks.iter().filter(|c| c.starts_with("```rust\n")).collect();
+        let table_parts: Vec<&String> = chunks.iter().filter(|c| c.starts_with("| code | message |\n|---|---|\n")).collect();
+        assert!(code_parts.len() > 1 && table_parts.len() > 1);
+        assert_eq!(code_parts.len() + table_parts.len(), chunks.len());
+        assert!(code_parts.iter().all(|c| c.ends_with("\n```")));
+        assert!(chunks.iter().all(|c| sizing(60).tokenizer.count(c) <= 60));
+        assert!(chunks.iter().any(|c| c.contains("let value_39 = 39;")));
+    }
+}
@@ -0,0 +1,340 @@
+//! Contextual chunk headers.
+//!
+//! A chunk like "set it to 30 seconds" carries no hint of which document or section it
+//! came from. When `[ingest.contextual]` is enabled, each chunk gets a short header
+//! (document title and path, section breadcrumb, optionally an LLM one-line summary)
+//! stored in `chunks.context_header`. The header is prepended for embedding and BM25
+//! indexing only; `chunks.chunk_text` and therefore search results keep the original text.
+
+use crate::config::{Config, ContextualConfig};
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use reqwest::Client;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+#[derive(Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: Option<String>,
+}
+
+/// Build the context header for one chunk, or `None` if every part is disabled or empty
+pub fn context_header(
+    config: &ContextualConfig,
+    title: Option<&str>,
+    doc_path: &str,
+    breadcrumb: Option<&str>,
+    summary: Option<&str>,
+) -> Option<String> {
+    let mut lines = Vec::new();
+
+    let title = title.filter(|t| config.include_title && !t.trim().is_empty());
+    match (title, config.include_path) {
+        (Some(title), true) => lines.push(format!("Document: {} ({})", title.trim(), doc_path)),
+        (Some(title), false) => lines.push(format!("Document: {}", title.trim())),
+        (None, true) => lines.push(format!("Document: {}", doc_path)),
+        (None, false) => {}
+    }
+    if config.include_breadcrumb {
+        if let Some(breadcrumb) = breadcrumb.filter(|b| !b.trim().is_empty()) {
+            lines.push(format!("Section: {}", breadcrumb.trim()));
+        }
+    }
+    if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+        lines.push(format!("Summary: {}", summary.trim()));
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Ask the chat completions API for a one-line summary of a document
+pub async fn summarize_document(
+    api_key: &str,
+    model: &str,
+    title: &str,
+    content: &str,
+    max_chars: usize,
+) -> Result<String> {
+    // Truncate on a char boundary so multi-byte text is not split
+    let excerpt: String = content.chars().take(max_chars).collect();
+    let request = ChatRequest {
+        model: model.to_string(),
+        messages: vec![
+            json!({
+                "role": "system",
+                "content": "Summarize the document in one sentence of at most 30 words. \
+                    State what it is about and what it is for. Reply with the sentence only."
+            }),
+            json!({
+                "role": "user",
+                "content": format!("Title: {}\n\n{}", title, excerpt)
+            }),
+        ],
+    };
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()
+        .map_err(|e| RagmcpError::Embedding(format!("HTTP client error: {}", e)))?;
+    let response = client
+        .post("https://api.openai.com/v1/chat/completions")
+        .header("Authorization", format!("Bearer {}", api_key))
+        .header("Content-Type", "application/json")
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| RagmcpError::Embedding(format!("Network error: {}", e)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read error response".to_string());
+        return Err(RagmcpError::Embedding(format!(
+            "OpenAI API error {}: {}",
+            status, body
+        )));
+    }
+
+    let result: ChatResponse = response
+        .json()
+        .await
+        .map_err(|e| RagmcpError::Embedding(format!("Failed to parse response: {}", e)))?;
+    let summary = result
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|c| c.message.content)
+        .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    Ok(summary)
+}
+
+/// Build context headers for every chunk of a document
+///
+/// No-op unless `[ingest.contextual]` is enabled. Call after `insert_chunks` and before
+/// `index_chunk_language` so the stemmed index sees the header too. A failed summary
+/// request is logged and the header is built without it. Returns the number of chunks updated.
+pub async fn contextualize_chunks(
+    db: &Db,
+    doc_id: &str,
+    content: &str,
+    config: &Config,
+) -> Result<usize> {
+    let contextual = &config.ingest.contextual;
+    if !contextual.enabled {
+        return Ok(0);
+    }
+
+    let summary = if contextual.summarize {
+        let (title, doc_path) = document_title_and_path(db, doc_id).await?;
+        match std::env::var(&config.embeddings.api_key_env) {
+            Ok(api_key) => match summarize_document(
+                &api_key,
+                &contextual.summary_model,
+                title.as_deref().unwrap_or(&doc_path),
+                content,
+                contextual.summary_max_chars,
+            )
+            .await
+            {
+                Ok(summary) if !summary.is_empty() => Some(summary),
+                Ok(_) => None,
+                Err(e) => {
+                    log::warn!("Summary for {} failed, header without summary: {}", doc_path, e);
+                    None
+                }
+            },
+            Err(_) => {
+                log::warn!(
+                    "{} not set; skipping document summary for {}",
+                    config.embeddings.api_key_env,
+                    doc_path
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    apply_context_headers(db, doc_id, contextual, summary).await
+}
+
+/// Store `summary` on the document and a context header on each of its chunks
+pub async fn apply_context_headers(
+    db: &Db,
+    doc_id: &str,
+    contextual: &ContextualConfig,
+    summary: Option<String>,
+) -> Result<usize> {
+    let (title, doc_path) = document_title_and_path(db, doc_id).await?;
+    let contextual = contextual.clone();
+    let doc_id = doc_id.to_string();
+    db.with_connection(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE documents SET summary = ?1 WHERE doc_id = ?2",
+            params![summary, doc_id],
+        )?;
+        let rows: Vec<(String, Option<String>)> = {
+            let mut select = tx.prepare(
+                "SELECT chunk_id, section_header FROM chunks WHERE doc_id = ?1",
+            )?;
+            let rows = select
+                .query_map(params![doc_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<std::result::Result<_, _>>()?;
+            rows
+        };
+        {
+            let mut update = tx.prepare(
+                "UPDATE chunks SET context_header = ?1 WHERE chunk_id = ?2",
+            )?;
+            for (chunk_id, section_header) in &rows {
+                let header = context_header(
+                    &contextual,
+                    title.as_deref(),
+                    &doc_path,
+                    section_header.as_deref(),
+                    summary.as_deref(),
+                );
+                update.execute(params![header, chunk_id])?;
+            }
+        }
+        tx.commit()?;
+        Ok::<usize, RagmcpError>(rows.len())
+    }).await
+}
+
+async fn document_title_and_path(db: &Db, doc_id: &str) -> Result<(Option<String>, String)> {
+    let doc_id = doc_id.to_string();
+    db.with_connection(move |conn| {
+        conn.query_row(
+            "SELECT title, doc_path FROM documents WHERE doc_id = ?1",
+            params![doc_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => RagmcpError::DocumentNotFound(doc_id.clone()),
+            other => RagmcpError::Database(other),
+        })
+    }).await
+}
+
+/// Text sent to the embedding model and BM25 for a chunk
+pub fn contextualized_text(context_header: Option<&str>, chunk_text: &str) -> String {
+    match context_header {
+        Some(header) => format!("{}\n{}", header, chunk_text),
+        None => chunk_text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+    use crate::ingest::chunker::Chunk;
+    use crate::ingest::db_writer::{insert_chunks, insert_document};
+    use std::path::Path;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_context_header_parts() {
+        let config = ContextualConfig::default();
+        let header = context_header(
+            &config,
+            Some("Deploy Guide"),
+            "ops/deploy.md",
+            Some("Rollback > Timeouts"),
+            Some("How to deploy and roll back the API."),
+        )
+        .unwrap();
+        assert_eq!(
+            header,
+            "Document: Deploy Guide (ops/deploy.md)\nSection: Rollback > Timeouts\nSummary: How to deploy and roll back the API."
+        );
+
+        let bare = ContextualConfig {
+            include_title: false,
+            include_path: false,
+            include_breadcrumb: false,
+            ..ContextualConfig::default()
+        };
+        assert_eq!(context_header(&bare, Some("T"), "p.md", Some("S"), None), None);
+    }
+
+    #[tokio::test]
+    async fn test_apply_context_headers_indexes_header_but_keeps_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+
+        let content = "# Gateway Timeouts\n\nSet it to 30 seconds.";
+        let doc_id = insert_document(&db, "ops/gateway.md", "markdown", "ops", None, content, 10, "h1", SystemTime::now())
+            .await
+            .unwrap();
+        let chunk = Chunk {
+            text: "Set it to 30 seconds.".to_string(),
+            tokens: 6,
+            section_header: Some("Gateway Timeouts".to_string()),
+            chunk_type: Some("section".to_string()),
+            page: None,
+        };
+        insert_chunks(&db, &doc_id, vec![chunk]).await.unwrap();
+
+        let config = ContextualConfig {
+            enabled: true,
+            ..ContextualConfig::default()
+        };
+        assert_eq!(apply_context_headers(&db, &doc_id, &config, None).await.unwrap(), 1);
+
+        let (text, header, matches): (String, Option<String>, i64) = db.with_connection(|conn| {
+            let (text, header) = conn.query_row(
+                "SELECT chunk_text, context_header FROM chunks",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+            let matches = conn.query_row(
+                "SELECT COUNT(*) FROM chunks_fts WHERE chunk_text MATCH 'gateway'",
+                [],
+                |row| row.get(0),
+            )?;
+            Ok::<_, RagmcpError>((text, header, matches))
+        }).await.unwrap();
+
+        assert_eq!(text, "Set it to 30 seconds.", "original text is kept");
+        assert_eq!(
+            header.as_deref(),
+            Some("Document: Gateway Timeouts (ops/gateway.md)\nSection: Gateway Timeouts")
+        );
+        assert_eq!(matches, 1, "header terms are searchable in the body column");
+    }
+}
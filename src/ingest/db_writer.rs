@@ -3,8 +3,11 @@ use chrono::{Utc, TimeZone};
 use sha2::{Sha256, Digest};
 use crate::error::{Result, RagmcpError};
 use crate::db::Db;
-use crate::graph::extract_routing_relations;
+use crate::graph::{email_relation_source, extract_routing_relations, extract_thread_relations};
+use crate::search::language::{analyze, Language};
+use std::collections::HashSet;
 use super::chunker::Chunk;
+use super::contextual::contextualized_text;
 
 /// Insert or update a document in the database
 /// 
@@ -47,6 +50,7 @@ pub async fn insert_document(
     let tokens_clone = tokens;
     let modified_str = modified_chrono.to_rfc3339();
     let file_hash_clone = file_hash.to_string();
+    let title = document_title(doc_path, content);
     
     db.with_connection(move |conn| {
         // Delete old chunks if document exists (CASCADE should handle this, but be explicit)
@@ -60,8 +64,8 @@ pub async fn insert_document(
             r#"
             INSERT INTO documents (
                 doc_id, doc_path, doc_type, namespace, agent_name,
-                content_text, content_tokens, last_modified, file_hash
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                content_text, content_tokens, last_modified, file_hash, title
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
             ON CONFLICT(doc_path) DO UPDATE SET
                 doc_id = excluded.doc_id,
                 doc_type = excluded.doc_type,
@@ -70,7 +74,9 @@ pub async fn insert_document(
                 content_text = excluded.content_text,
                 content_tokens = excluded.content_tokens,
                 last_modified = excluded.last_modified,
-                file_hash = excluded.file_hash
+                file_hash = excluded.file_hash,
+                title = excluded.title,
+                embedding = NULL
             "#,
             params![
                 doc_id_clone,
@@ -82,6 +88,7 @@ pub async fn insert_document(
                 tokens_clone as i64,
                 modified_str,
                 file_hash_clone,
+                title,
             ],
         )?;
         
@@ -116,6 +123,98 @@ pub async fn insert_document(
     Ok(doc_id.clone())
 }
 
+/// Store parser-provided document metadata in `documents.metadata_json`
+/// 
+/// Clears stale metadata when `metadata` is `None`. A string `title` in the metadata
+/// (e.g. HTML `<title>`) replaces the heading/file-name title used for BM25.
+pub async fn store_document_metadata(
+    db: &Db,
+    doc_id: &str,
+    metadata: Option<&serde_json::Value>,
+) -> Result<()> {
+    let doc_id = doc_id.to_string();
+    let title = metadata
+        .and_then(|m| m.get("title"))
+        .and_then(|t| t.as_str())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string);
+    let metadata_json = metadata.map(|m| m.to_string());
+    db.with_connection(move |conn| {
+        conn.execute(
+            "UPDATE documents SET metadata_json = ?1, title = COALESCE(?2, title) WHERE doc_id = ?3",
+            params![metadata_json, title, doc_id],
+        )?;
+        Ok::<(), RagmcpError>(())
+    }).await
+}
+
+/// Record whether the chunks of a document are embedded (`[[ingest.rules]] embed`)
+/// 
+/// Disabling drops the document's existing chunk and document embeddings so vector
+/// search no longer returns it.
+pub async fn set_embedding_enabled(db: &Db, doc_id: &str, enabled: bool) -> Result<()> {
+    let doc_id = doc_id.to_string();
+    db.with_connection(move |conn| {
+        conn.execute(
+            "UPDATE documents SET embed = ?1, embedding = CASE WHEN ?1 THEN embedding END WHERE doc_id = ?2",
+            params![enabled, doc_id],
+        )?;
+        if !enabled {
+            conn.execute("UPDATE chunks SET embedding = NULL WHERE doc_id = ?1", params![doc_id])?;
+        }
+        Ok::<(), RagmcpError>(())
+    }).await
+}
+
+/// Replace the thread relations (`in_thread`, `replies_to`, `sent`) extracted from an
+/// e-mail document with those found in its current metadata
+pub async fn store_thread_relations(
+    db: &Db,
+    doc_path: &str,
+    metadata: Option<&serde_json::Value>,
+) -> Result<usize> {
+    let source = email_relation_source(doc_path);
+    let relations = metadata
+        .map(|m| extract_thread_relations(doc_path, m))
+        .unwrap_or_default();
+    let count = relations.len();
+    db.with_connection(move |conn| {
+        conn.execute("DELETE FROM entity_relations WHERE metadata_json = ?1", params![source])?;
+        for rel in &relations {
+            conn.execute(
+                "INSERT OR REPLACE INTO entity_relations (relation_id, source_entity, relation_type, target_entity, metadata_json) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![rel.relation_id, rel.source_entity, rel.relation_type, rel.target_entity, rel.metadata_json],
+            )?;
+        }
+        Ok::<(), RagmcpError>(())
+    }).await?;
+    Ok(count)
+}
+
+/// Derive a searchable document title
+/// 
+/// Uses the first Markdown-style `# ` heading in the first few lines, otherwise the
+/// file name without extension with `_`/`-` turned into spaces.
+pub fn document_title(doc_path: &str, content: &str) -> String {
+    let heading = content
+        .lines()
+        .take(20)
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("# "))
+        .map(str::trim)
+        .filter(|h| !h.is_empty());
+    if let Some(heading) = heading {
+        return heading.to_string();
+    }
+    std::path::Path::new(doc_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(doc_path)
+        .replace(['_', '-'], " ")
+}
+
 /// Insert chunks in batches
 /// 
 /// Inserts chunks in batches of 100 for efficiency.
@@ -141,8 +240,8 @@ pub async fn insert_chunks(
                 r#"
                 INSERT INTO chunks (
                     chunk_id, doc_id, chunk_index, chunk_text,
-                    chunk_tokens, section_header, chunk_type
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    chunk_tokens, section_header, chunk_type, page
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
                 "#
             )?;
             
@@ -157,6 +256,7 @@ pub async fn insert_chunks(
                     chunk.tokens as i64,
                     chunk.section_header,
                     chunk.chunk_type,
+                    chunk.page,
                 ])?;
             }
             
@@ -169,6 +269,73 @@ pub async fn insert_chunks(
     Ok(count)
 }
 
+/// Record the document language and build its stemmed BM25 index
+/// 
+/// English documents are served by the Porter-stemmed `chunks_fts` table, so only the
+/// language column is set. Other languages get their chunks stemmed and stop-word
+/// filtered into `chunks_fts_stemmed`. Call after `insert_chunks`.
+pub async fn index_chunk_language(
+    db: &Db,
+    doc_id: &str,
+    language: Language,
+    stop_words: HashSet<String>,
+) -> Result<usize> {
+    let doc_id = doc_id.to_string();
+    db.with_connection(move |conn| {
+        conn.execute(
+            "UPDATE documents SET language = ?1 WHERE doc_id = ?2",
+            params![language.code(), doc_id],
+        )?;
+        // Chunk triggers remove stale rows on re-ingest; clear explicitly in case the
+        // document switched language without its chunks changing
+        conn.execute(
+            "DELETE FROM chunks_fts_stemmed WHERE chunk_id IN (SELECT chunk_id FROM chunks WHERE doc_id = ?1)",
+            params![doc_id],
+        )?;
+        if language.uses_porter_table() {
+            return Ok::<usize, RagmcpError>(0);
+        }
+        
+        let (title, path): (Option<String>, String) = conn.query_row(
+            "SELECT title, doc_path FROM documents WHERE doc_id = ?1",
+            params![doc_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let stem = |text: &str| analyze(text, language, &stop_words).join(" ");
+        let stemmed_title = title.as_deref().map(stem);
+        let stemmed_path = stem(&path);
+        
+        let mut select = conn.prepare(
+            "SELECT chunk_id, chunk_text, section_header, context_header FROM chunks WHERE doc_id = ?1",
+        )?;
+        let rows: Vec<(String, String, Option<String>)> = select
+            .query_map(params![doc_id], |row| {
+                let text: String = row.get(1)?;
+                let context: Option<String> = row.get(3)?;
+                Ok((row.get(0)?, contextualized_text(context.as_deref(), &text), row.get(2)?))
+            })?
+            .collect::<std::result::Result<_, _>>()?;
+        
+        let mut insert = conn.prepare(
+            r#"
+            INSERT INTO chunks_fts_stemmed (chunk_id, language, chunk_text, breadcrumb, title, path)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+        )?;
+        for (chunk_id, text, header) in &rows {
+            insert.execute(params![
+                chunk_id,
+                language.code(),
+                stem(text),
+                header.as_deref().map(stem),
+                stemmed_title,
+                stemmed_path,
+            ])?;
+        }
+        Ok(rows.len())
+    }).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +356,12 @@ mod tests {
         (db, temp_dir)
     }
     
+    #[test]
+    fn test_document_title() {
+        assert_eq!(document_title("guides/setup.md", "intro\n# Getting Started\nbody"), "Getting Started");
+        assert_eq!(document_title("agents/code_review-bot.xml", "<agent/>"), "code review bot");
+    }
+    
     #[tokio::test]
     async fn test_insert_document() {
         let (db, _temp_dir) = setup_test_db().await;
@@ -246,12 +419,14 @@ mod tests {
                 tokens: 10,
                 section_header: Some("Section 1".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Chunk 2".to_string(),
                 tokens: 10,
                 section_header: Some("Section 2".to_string()),
                 chunk_type: None,
+                page: None,
             },
         ];
         
@@ -275,6 +450,48 @@ mod tests {
         }).await.unwrap();
     }
     
+    #[tokio::test]
+    async fn test_store_document_metadata() {
+        let (db, _temp_dir) = setup_test_db().await;
+        
+        let doc_id = insert_document(
+            &db,
+            "wiki/gateway.html",
+            "html",
+            "wiki",
+            None,
+            "# Gateway\n\nbody",
+            10,
+            "hash",
+            std::time::SystemTime::now(),
+        ).await.unwrap();
+        
+        let metadata = serde_json::json!({ "title": "Gateway Guide", "links": [{ "href": "a.html" }] });
+        store_document_metadata(&db, &doc_id, Some(&metadata)).await.unwrap();
+        
+        let id = doc_id.clone();
+        let (title, json) = db.with_connection(move |conn| {
+            conn.query_row(
+                "SELECT title, metadata_json FROM documents WHERE doc_id = ?1",
+                params![id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+            ).map_err(RagmcpError::from)
+        }).await.unwrap();
+        assert_eq!(title, "Gateway Guide");
+        assert!(json.unwrap().contains("a.html"));
+        
+        // Documents without metadata clear what a previous parse stored
+        store_document_metadata(&db, &doc_id, None).await.unwrap();
+        let json = db.with_connection(move |conn| {
+            conn.query_row(
+                "SELECT metadata_json FROM documents WHERE doc_id = ?1",
+                params![doc_id],
+                |row| row.get::<_, Option<String>>(0),
+            ).map_err(RagmcpError::from)
+        }).await.unwrap();
+        assert!(json.is_none());
+    }
+    
     #[tokio::test]
     async fn test_insert_document_upsert() {
         let (db, _temp_dir) = setup_test_db().await;
@@ -346,4 +563,24 @@ mod tests {
         assert_eq!(relations[0].target_entity, "agent:agent-b");
         assert_eq!(relations[0].relation_type, "routes_to");
     }
+
+    #[tokio::test]
+    async fn test_store_thread_relations_replaces_previous() {
+        let (db, _temp_dir) = setup_test_db().await;
+        let metadata = serde_json::json!({
+            "message_id": "b2@example.com",
+            "in_reply_to": "a1@example.com",
+            "thread_id": "a1@example.com"
+        });
+        assert_eq!(store_thread_relations(&db, "mail/ops.mbox#2", Some(&metadata)).await.unwrap(), 2);
+        assert_eq!(store_thread_relations(&db, "mail/ops.mbox#2", Some(&metadata)).await.unwrap(), 2);
+
+        let relations = traverse_graph(&db, "message:b2@example.com", None, 1).await.unwrap();
+        assert_eq!(relations.len(), 2);
+        let replies = traverse_graph(&db, "message:b2@example.com", Some(vec!["replies_to".to_string()]), 1).await.unwrap();
+        assert_eq!(replies[0].target_entity, "message:a1@example.com");
+
+        store_thread_relations(&db, "mail/ops.mbox#2", None).await.unwrap();
+        assert!(traverse_graph(&db, "message:b2@example.com", None, 1).await.unwrap().is_empty());
+    }
 }
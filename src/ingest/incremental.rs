@@ -7,7 +7,8 @@ use std::collections::{HashMap, HashSet};
 
 use crate::db::Db;
 use crate::error::{Result, RagmcpError};
-use crate::ingest::{compute_file_hash, FileMetadata};
+use crate::graph::email_relation_source;
+use crate::ingest::{compute_file_hash, source_path, FileMetadata};
 
 /// Result of classifying discovered files against the database.
 #[derive(Debug, Default)]
@@ -23,7 +24,8 @@ pub struct FileClassification {
 /// Load all document paths and their stored file hashes from the database.
 ///
 /// Returns a map of `doc_path` → `file_hash` for O(1) lookup during classification.
-/// Paths are stored as in the database (no normalization).
+/// Paths are stored as in the database (no normalization); the messages of a mailbox
+/// (`inbox.mbox#<part>`) share the file hash and are keyed by the mailbox path.
 pub async fn get_existing_hashes(db: &Db) -> Result<HashMap<String, String>> {
     db.with_connection(|conn| {
         let mut stmt = conn.prepare("SELECT doc_path, file_hash FROM documents")?;
@@ -33,7 +35,7 @@ pub async fn get_existing_hashes(db: &Db) -> Result<HashMap<String, String>> {
         let mut map = HashMap::new();
         for row in rows {
             let (path, hash) = row?;
-            map.insert(path, hash);
+            map.insert(source_path(&path).to_string(), hash);
         }
         Ok::<HashMap<String, String>, RagmcpError>(map)
     })
@@ -68,6 +70,7 @@ pub fn classify_files(
 ///
 /// `existing_files` is the set of relative paths of files currently discovered on disk.
 /// Returns doc_paths that are in the database but not in that set (candidates for cleanup).
+/// Documents split from one file (mailbox messages) are matched by their file path.
 pub async fn find_deleted_documents(
     db: &Db,
     existing_files: &HashSet<String>,
@@ -86,7 +89,7 @@ pub async fn find_deleted_documents(
 
     let deleted: Vec<String> = db_paths
         .into_iter()
-        .filter(|p| !existing_files.contains(p))
+        .filter(|p| !existing_files.contains(source_path(p)))
         .collect();
 
     Ok(deleted)
@@ -94,7 +97,8 @@ pub async fn find_deleted_documents(
 
 /// Delete documents by path from the database.
 ///
-/// Chunks are removed automatically via foreign key CASCADE.
+/// Chunks are removed automatically via foreign key CASCADE; thread relations
+/// extracted from e-mail documents are removed with them.
 /// Returns the number of documents deleted.
 pub async fn delete_documents(db: &Db, doc_paths: &[String]) -> Result<usize> {
     if doc_paths.is_empty() {
@@ -107,6 +111,10 @@ pub async fn delete_documents(db: &Db, doc_paths: &[String]) -> Result<usize> {
     db.with_connection(move |conn| {
         for doc_path in &paths {
             conn.execute("DELETE FROM documents WHERE doc_path = ?1", rusqlite::params![doc_path])?;
+            conn.execute(
+                "DELETE FROM entity_relations WHERE metadata_json = ?1",
+                rusqlite::params![email_relation_source(doc_path)],
+            )?;
         }
         Ok::<usize, RagmcpError>(count)
     })
@@ -115,6 +123,46 @@ pub async fn delete_documents(db: &Db, doc_paths: &[String]) -> Result<usize> {
     Ok(count)
 }
 
+/// Document IDs ingested from the file at `path`: the document itself or, for a
+/// mailbox, one per message.
+pub async fn document_ids_for_source(db: &Db, path: &str) -> Result<Vec<String>> {
+    let path = path.to_string();
+    db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT doc_id FROM documents \
+             WHERE doc_path = ?1 OR substr(doc_path, 1, length(?1) + 1) = ?1 || '#' ORDER BY doc_path",
+        )?;
+        let ids = stmt
+            .query_map([&path], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok::<Vec<String>, RagmcpError>(ids)
+    })
+    .await
+}
+
+/// Delete documents split from the file at `path` that were not produced by the latest
+/// parse (messages removed from a mailbox). Returns the number of documents deleted.
+pub async fn delete_stale_parts(db: &Db, path: &str, current: &[String]) -> Result<usize> {
+    let path_owned = path.to_string();
+    let existing: Vec<String> = db
+        .with_connection(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT doc_path FROM documents WHERE substr(doc_path, 1, length(?1) + 1) = ?1 || '#'",
+            )?;
+            let paths = stmt
+                .query_map([&path_owned], |row| row.get::<_, String>(0))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok::<Vec<String>, RagmcpError>(paths)
+        })
+        .await?;
+
+    let stale: Vec<String> = existing
+        .into_iter()
+        .filter(|p| source_path(p) == path && !current.contains(p))
+        .collect();
+    delete_documents(db, &stale).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +319,7 @@ mod tests {
                 tokens: 1,
                 section_header: None,
                 chunk_type: None,
+                page: None,
             }],
         )
         .await
@@ -288,4 +337,35 @@ mod tests {
             .unwrap();
         assert_eq!(chunk_count, 0);
     }
+
+    #[tokio::test]
+    async fn test_mailbox_parts_share_source_path() {
+        let (db, _temp_dir) = setup_test_db().await;
+        for doc_path in ["mail/ops.mbox#1", "mail/ops.mbox#2", "mail/ops.mbox#3", "mail/ops.mbox.md"] {
+            crate::ingest::db_writer::insert_document(
+                &db,
+                doc_path,
+                "email",
+                "mail",
+                None,
+                "body",
+                1,
+                "mbox_hash",
+                std::time::SystemTime::now(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let hashes = get_existing_hashes(&db).await.unwrap();
+        assert_eq!(hashes.get("mail/ops.mbox"), Some(&"mbox_hash".to_string()));
+        let on_disk: HashSet<String> = ["mail/ops.mbox".into(), "mail/ops.mbox.md".into()].into_iter().collect();
+        assert!(find_deleted_documents(&db, &on_disk).await.unwrap().is_empty());
+        assert_eq!(document_ids_for_source(&db, "mail/ops.mbox").await.unwrap().len(), 3);
+
+        let current = vec!["mail/ops.mbox#1".to_string(), "mail/ops.mbox#2".to_string()];
+        assert_eq!(delete_stale_parts(&db, "mail/ops.mbox", &current).await.unwrap(), 1);
+        assert_eq!(document_ids_for_source(&db, "mail/ops.mbox").await.unwrap().len(), 2);
+        assert_eq!(document_ids_for_source(&db, "mail/ops.mbox.md").await.unwrap().len(), 1);
+    }
 }
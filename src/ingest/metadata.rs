@@ -91,6 +91,19 @@ pub fn extract_agent_name(relative_path: &str) -> Option<String> {
     None
 }
 
+/// Path of the file a document was ingested from.
+///
+/// Files holding several documents (mailboxes) store each one as `path#<part>`, where
+/// the part is a Message-ID hash or a position (hex digits); the suffix is removed so
+/// hashes and deletions can be matched against files on disk.
+/// Other doc paths are returned unchanged.
+pub fn source_path(doc_path: &str) -> &str {
+    match doc_path.rsplit_once('#') {
+        Some((path, part)) if !part.is_empty() && part.bytes().all(|b| b.is_ascii_hexdigit()) => path,
+        _ => doc_path,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +183,13 @@ mod tests {
         );
         assert_eq!(extract_agent_name("System\\README.md"), None);
     }
+    
+    #[test]
+    fn test_source_path() {
+        assert_eq!(source_path("mail/ops.mbox#12"), "mail/ops.mbox");
+        assert_eq!(source_path("mail/ops.mbox#3f9a0c1b2d4e"), "mail/ops.mbox");
+        assert_eq!(source_path("mail/reply.eml"), "mail/reply.eml");
+        assert_eq!(source_path("notes/c#-tips.md"), "notes/c#-tips.md");
+        assert_eq!(source_path("issue#"), "issue#");
+    }
 }
@@ -2,66 +2,129 @@ pub mod walker;
 pub mod metadata;
 pub mod parsers;
 pub mod chunker;
+pub mod tokenizer;
+pub mod rules;
+pub mod sources;
 pub mod db_writer;
 pub mod incremental;
+pub mod contextual;
 
-pub use walker::{FileMetadata, discover_files};
+pub use walker::{
+    Discovery, DiscoveryFilter, FileMetadata, SkipReason, SkippedPath, SUPPORTED_EXTENSIONS, discover_files,
+};
 pub use incremental::{
-    FileClassification, classify_files, delete_documents, find_deleted_documents, get_existing_hashes,
+    FileClassification, classify_files, delete_documents, delete_stale_parts, document_ids_for_source,
+    find_deleted_documents, get_existing_hashes,
 };
-pub use metadata::{compute_file_hash, extract_namespace, extract_agent_name};
+pub use metadata::{compute_file_hash, extract_namespace, extract_agent_name, source_path};
 pub use parsers::{ParserRegistry, ParsedDocument, Section};
-pub use chunker::{Chunk, chunk_document, estimate_tokens};
-pub use db_writer::{insert_document, insert_chunks};
+pub use chunker::{
+    Chunk, ChunkSizing, ChunkStrategy, Chunker, chunk_document, chunk_with_config, chunker_for, estimate_tokens,
+};
+pub use tokenizer::Tokenizer;
+pub use rules::{FileSettings, IngestRules};
+pub use sources::{Source, deletable_documents, source_for_doc_path, source_for_file};
+pub use db_writer::{
+    insert_document, insert_chunks, index_chunk_language, set_embedding_enabled, store_document_metadata,
+    store_thread_relations,
+};
+pub use contextual::contextualize_chunks;
 
 /// Convenience function to ingest a single file
 /// 
-/// Orchestrates the full pipeline: parse → chunk → insert
+/// Orchestrates the full pipeline: parse → chunk → insert, with the settings the
+/// `[[ingest.rules]]` resolve for the file. Files holding several documents (mailboxes)
+/// insert one document per part; parts that disappeared since the previous ingestion
+/// are deleted. Returns the total chunk and token counts.
 pub async fn ingest_file(
     db: &crate::db::Db,
     file: &FileMetadata,
     parser_registry: &ParserRegistry,
     config: &crate::Config,
 ) -> crate::error::Result<(usize, usize)> {
-    // Read file content
-    let content = std::fs::read_to_string(&file.absolute_path)
+    // Read raw file bytes (binary formats such as PDF are parsed from bytes)
+    let bytes = std::fs::read(&file.absolute_path)
         .map_err(crate::error::RagmcpError::Io)?;
     
     // Compute file hash
     let file_hash = compute_file_hash(&file.absolute_path)?;
     
-    // Extract metadata
-    let namespace = extract_namespace(&file.relative_path);
-    let agent_name = extract_agent_name(&file.relative_path);
+    // Per-path overrides (parser, chunking, mapping, embedding)
+    let settings = FileSettings::resolve(&file.relative_path, &file.extension, config)?;
     
-    // Parse document
-    let parsed = parser_registry.parse(
-        &content,
+    // Parse document(s)
+    let documents = parser_registry.parse_documents(
+        &bytes,
         &file.relative_path,
-        &file.extension,
+        &settings.parser_extension,
     )?;
     
-    // Chunk document
-    let chunks = chunk_document(&parsed, &config.performance)?;
+    let mut total_chunks = 0;
+    let mut total_tokens = 0;
+    for (doc_path, parsed) in &documents {
+        let (chunk_count, tokens) =
+            ingest_document(db, file, doc_path, parsed, &file_hash, &settings, config).await?;
+        total_chunks += chunk_count;
+        total_tokens += tokens;
+    }
     
-    // Estimate total tokens
-    let total_tokens = chunks.iter().map(|c| c.tokens).sum::<usize>();
+    // Messages removed from a mailbox since the last ingestion
+    let doc_paths: Vec<String> = documents.into_iter().map(|(doc_path, _)| doc_path).collect();
+    delete_stale_parts(db, &file.relative_path, &doc_paths).await?;
+    
+    Ok((total_chunks, total_tokens))
+}
+
+/// Chunk and store one parsed document of `file` under `doc_path`
+/// 
+/// Namespace, agent, doc_type, chunking and embedding follow `settings`.
+/// Returns the number of chunks and tokens inserted.
+pub async fn ingest_document(
+    db: &crate::db::Db,
+    file: &FileMetadata,
+    doc_path: &str,
+    parsed: &ParsedDocument,
+    file_hash: &str,
+    settings: &FileSettings,
+    config: &crate::Config,
+) -> crate::error::Result<(usize, usize)> {
+    let namespace = &settings.namespace;
+    
+    // Chunk document with the strategy resolved for its path and file type
+    let (chunks, total_tokens) = chunk_with_config(parsed, settings, config).await?;
     
     // Insert document
     let doc_id = insert_document(
         db,
-        &file.relative_path,
-        &parsed.doc_type,
-        &namespace,
-        agent_name.as_deref(),
+        doc_path,
+        settings.doc_type.as_deref().unwrap_or(&parsed.doc_type),
+        namespace,
+        settings.agent_name.as_deref(),
         &parsed.content,
         total_tokens,
-        &file_hash,
+        file_hash,
         file.modified,
     ).await?;
     
+    // Store parser metadata (title, meta tags, links); clears stale metadata otherwise
+    store_document_metadata(db, &doc_id, parsed.metadata.as_ref()).await?;
+    set_embedding_enabled(db, &doc_id, settings.embed).await?;
+    
+    // Link e-mails to their thread in the knowledge graph
+    if parsed.doc_type == "email" {
+        store_thread_relations(db, doc_path, parsed.metadata.as_ref()).await?;
+    }
+    
     // Insert chunks
     let chunk_count = insert_chunks(db, &doc_id, chunks).await?;
     
+    // Prepend document/section context for embedding and BM25 (if enabled)
+    contextualize_chunks(db, &doc_id, &parsed.content, config).await?;
+    
+    // Build the language-specific BM25 index
+    let languages = &config.search.languages;
+    let language = languages.resolve(namespace, &parsed.content);
+    index_chunk_language(db, &doc_id, language, languages.stop_words_for(language)).await?;
+    
     Ok((chunk_count, total_tokens))
 }
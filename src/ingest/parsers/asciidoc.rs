@@ -0,0 +1,265 @@
+use super::section_builder::SectionBuilder;
+use super::{Parser, ParsedDocument};
+use crate::error::Result;
+use regex::Regex;
+use serde_json::json;
+use std::sync::OnceLock;
+
+/// Admonition labels usable as `[NOTE]` blocks or `NOTE:` paragraphs
+const ADMONITIONS: &[&str] = &["NOTE", "TIP", "IMPORTANT", "WARNING", "CAUTION"];
+
+/// AsciiDoc parser
+///
+/// Sections follow `=` headings (`= Title` is h1, `== Section` h2, ...). Document
+/// attributes (`:version: 2.1`) are collected into the `attributes` metadata and
+/// the first heading becomes the `title`. `[source,lang]` listing blocks and `....`
+/// literal blocks become fenced code, `[NOTE]` example blocks become `NOTE: ...`
+/// paragraphs, tables are rendered as `| a | b |` rows and `include::` targets are
+/// listed in the `includes` metadata. Comments and passthrough blocks are dropped.
+pub struct AsciiDocParser;
+
+impl Parser for AsciiDocParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        matches!(extension, "adoc" | "asciidoc")
+    }
+
+    fn parse(&self, content: &str, _path: &str) -> Result<ParsedDocument> {
+        static HEADING: OnceLock<Regex> = OnceLock::new();
+        static ATTRIBUTE: OnceLock<Regex> = OnceLock::new();
+        static MACRO: OnceLock<Regex> = OnceLock::new();
+        let heading_re = HEADING.get_or_init(|| Regex::new(r"^(={1,6})\s+(\S.*)$").expect("Invalid regex pattern"));
+        let attribute_re = ATTRIBUTE
+            .get_or_init(|| Regex::new(r"^:(!?[\w-]+!?):\s*(.*)$").expect("Invalid regex pattern"));
+        let macro_re = MACRO
+            .get_or_init(|| Regex::new(r"^(include|image|toc|video|audio)::(.*?)\[.*\]$").expect("Invalid regex pattern"));
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut builder = SectionBuilder::default();
+        let mut attributes = serde_json::Map::new();
+        let mut includes: Vec<String> = Vec::new();
+        let mut block_style: Option<String> = None;
+
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+            i += 1;
+
+            // Line comments; `////` opens a comment block handled below
+            if line.starts_with("//") && line != "////" && !line.starts_with("///") {
+                continue;
+            }
+
+            if let Some(c) = heading_re.captures(line) {
+                builder.start_section(c[2].trim().to_string(), c[1].len());
+                block_style = None;
+                continue;
+            }
+
+            if let Some(c) = attribute_re.captures(line) {
+                attributes.insert(c[1].to_string(), json!(c[2].trim()));
+                continue;
+            }
+
+            if let Some(c) = macro_re.captures(line) {
+                if &c[1] == "include" {
+                    let target = c[2].trim().to_string();
+                    builder.push_line(&format!("[include: {}]", target));
+                    includes.push(target);
+                }
+                continue;
+            }
+
+            if let Some(style) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                // Block attribute line such as [source,rust] or [NOTE]; applies to the next block
+                if !style.starts_with('[') {
+                    block_style = Some(style.to_string());
+                    continue;
+                }
+            }
+
+            if let Some(delimiter) = block_delimiter(line) {
+                let start = i;
+                while i < lines.len() && lines[i].trim_end() != line {
+                    i += 1;
+                }
+                let body = &lines[start..i];
+                i += 1;
+                let style = block_style.take().unwrap_or_default();
+                let mut attrs = style.split(',').map(str::trim);
+                let kind = attrs.next().unwrap_or("");
+
+                match delimiter {
+                    '/' | '+' => {}
+                    '-' if line == "--" => push_lines(&mut builder, body),
+                    '-' | '.' => {
+                        let language = if matches!(kind, "source" | "") { attrs.next().unwrap_or("") } else { "" };
+                        builder.push_code(language, body);
+                    }
+                    '|' => builder.push_paragraph(&table_rows(body).join("\n")),
+                    '=' if ADMONITIONS.contains(&kind) => {
+                        let text = body.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>();
+                        builder.push_paragraph(&format!("{}: {}", kind, text.join(" ")));
+                    }
+                    _ => push_lines(&mut builder, body),
+                }
+                continue;
+            }
+
+            if line.is_empty() {
+                block_style = None;
+            }
+            // Block titles (.Example) read as plain labels
+            let line = match line.strip_prefix('.') {
+                Some(title) if title.starts_with(char::is_alphanumeric) => title,
+                _ => line,
+            };
+            builder.push_line(line);
+        }
+
+        let title = builder.first_heading.clone();
+        let sections = builder.finish(content);
+        let mut metadata = serde_json::Map::new();
+        if let Some(title) = title {
+            metadata.insert("title".to_string(), json!(title));
+        }
+        if !attributes.is_empty() {
+            metadata.insert("attributes".to_string(), serde_json::Value::Object(attributes));
+        }
+        if !includes.is_empty() {
+            metadata.insert("includes".to_string(), json!(includes));
+        }
+
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: "asciidoc".to_string(),
+            metadata: (!metadata.is_empty()).then_some(serde_json::Value::Object(metadata)),
+        })
+    }
+}
+
+/// Delimiter character if `line` opens a delimited block (`----`, `====`, `|===`, `--`, ...)
+fn block_delimiter(line: &str) -> Option<char> {
+    if line == "--" {
+        return Some('-');
+    }
+    if line == "|===" {
+        return Some('|');
+    }
+    let first = line.chars().next()?;
+    (matches!(first, '-' | '.' | '=' | '*' | '_' | '+' | '/')
+        && line.len() >= 4
+        && line.chars().all(|c| c == first))
+    .then_some(first)
+}
+
+fn push_lines(builder: &mut SectionBuilder, lines: &[&str]) {
+    builder.push_line("");
+    for line in lines {
+        builder.push_line(line);
+    }
+    builder.push_line("");
+}
+
+/// Render `|a |b` table lines as `| a | b |` rows; cells on their own lines are joined
+/// into the preceding row
+fn table_rows(lines: &[&str]) -> Vec<String> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut new_row = true;
+    for line in lines.iter().map(|l| l.trim()) {
+        if line.is_empty() {
+            new_row = true;
+            continue;
+        }
+        let cells: Vec<String> = line
+            .split('|')
+            .skip(usize::from(line.starts_with('|')))
+            .map(|c| c.trim().to_string())
+            .collect();
+        match rows.last_mut() {
+            Some(row) if !new_row => row.extend(cells),
+            _ => rows.push(cells),
+        }
+        // A line holding several cells is a complete row
+        new_row = line.matches('|').count() > 1;
+    }
+    rows.into_iter().map(|cells| format!("| {} |", cells.join(" | "))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"= Gateway Guide
+:version: 2.1
+:toc:
+
+// internal note, not rendered
+
+The gateway routes requests.
+
+== Installation
+
+[source,bash]
+----
+pip install gateway
+----
+
+[NOTE]
+====
+Requires Python 3.10
+or newer.
+====
+
+include::partials/license.adoc[]
+
+=== Ports
+
+|===
+|Name |Port
+
+|http |8080
+|===
+
+////
+Hidden comment block
+////
+
+== Usage
+
+Run it.
+"#;
+
+    #[test]
+    fn test_asciidoc_sections_and_metadata() {
+        let doc = AsciiDocParser.parse(DOC, "docs/guide.adoc").unwrap();
+        let headings: Vec<(&str, &str)> = doc
+            .sections
+            .iter()
+            .map(|s| (s.header.as_str(), s.section_type.as_deref().unwrap_or("")))
+            .collect();
+
+        assert_eq!(doc.doc_type, "asciidoc");
+        assert_eq!(
+            headings,
+            vec![("Gateway Guide", "h1"), ("Installation", "h2"), ("Ports", "h3"), ("Usage", "h2")]
+        );
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata["title"], "Gateway Guide");
+        assert_eq!(metadata["attributes"]["version"], "2.1");
+        assert_eq!(metadata["includes"][0], "partials/license.adoc");
+        assert_eq!(doc.sections[0].content, "The gateway routes requests.");
+    }
+
+    #[test]
+    fn test_asciidoc_blocks() {
+        let doc = AsciiDocParser.parse(DOC, "docs/guide.adoc").unwrap();
+        let install = &doc.sections[1].content;
+
+        assert!(install.contains("```bash\npip install gateway\n```"));
+        assert!(install.contains("NOTE: Requires Python 3.10 or newer."));
+        assert!(install.contains("[include: partials/license.adoc]"));
+        assert_eq!(doc.sections[2].content, "| Name | Port |\n| http | 8080 |");
+        assert!(!doc.content.is_empty() && !doc.sections[2].content.contains("Hidden"));
+    }
+}
@@ -0,0 +1,779 @@
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Maximum length of a signature used in a section header
+const MAX_SIGNATURE_CHARS: usize = 200;
+
+/// Source languages understood by the code parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Rust,
+    Python,
+    TypeScript,
+    JavaScript,
+    Go,
+}
+
+impl Lang {
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "rs" => Some(Lang::Rust),
+            "py" => Some(Lang::Python),
+            "ts" => Some(Lang::TypeScript),
+            "js" => Some(Lang::JavaScript),
+            "go" => Some(Lang::Go),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Lang::Rust => "rust",
+            Lang::Python => "python",
+            Lang::TypeScript => "typescript",
+            Lang::JavaScript => "javascript",
+            Lang::Go => "go",
+        }
+    }
+
+    /// Separator between a container and its members in qualified symbol names
+    fn path_separator(self) -> &'static str {
+        match self {
+            Lang::Rust => "::",
+            _ => ".",
+        }
+    }
+}
+
+/// Structure-aware source code parser
+///
+/// Splits `.rs`, `.py`, `.ts`, `.js` and `.go` files into one section per
+/// function, struct/enum/trait/interface/type, class and impl block. Methods of
+/// classes, impls, traits and inline modules get their own sections nested under
+/// the container. The section header is the item's signature followed by the first
+/// line of its doc comment, and `section_type` (stored as `chunk_type`) is the
+/// qualified symbol name, e.g. `ParserRegistry::parse`. Imports, constants and other
+/// top-level statements are collected into a leading "module" section.
+///
+/// Items are found with a lightweight scanner (brace depth for C-like languages,
+/// indentation for Python) rather than a full grammar, so unusual formatting may
+/// merge items into the module section, but never loses text.
+pub struct CodeParser;
+
+impl Parser for CodeParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        Lang::from_extension(extension).is_some()
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        let extension = path.rsplit_once('.').map(|(_, e)| e.to_lowercase()).unwrap_or_default();
+        let lang = Lang::from_extension(&extension)
+            .ok_or_else(|| RagmcpError::Parse(format!("Unsupported source file: {}", path)))?;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let items = match lang {
+            Lang::Python => python_items(&lines, 0..lines.len(), 0),
+            _ => {
+                let depths = brace_depths(content, lang);
+                brace_items(&lines, &depths, 0..lines.len(), 0, lang, None)
+            }
+        };
+
+        let mut sections = Vec::new();
+        let module = remaining_lines(&lines, 0..lines.len(), &items);
+        if !module.trim().is_empty() {
+            sections.push(Section {
+                header: "module".to_string(),
+                content: module,
+                section_type: None,
+                page: None,
+            });
+        }
+        for item in &items {
+            push_item_sections(&mut sections, &lines, item, None, lang);
+        }
+
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: lang.name().to_string(),
+            metadata: None,
+        })
+    }
+}
+
+/// A function, type or container found in the source
+#[derive(Debug, Clone)]
+struct Item {
+    /// Symbol name used in qualified names (the implemented type for impl blocks)
+    name: String,
+    signature: String,
+    doc: Option<String>,
+    /// First line, including doc comments and attributes
+    start: usize,
+    /// Exclusive end line
+    end: usize,
+    children: Vec<Item>,
+}
+
+fn push_item_sections(sections: &mut Vec<Section>, lines: &[&str], item: &Item, parent: Option<(&str, &str)>, lang: Lang) {
+    let qualified = match parent {
+        Some((_, parent_name)) => format!("{}{}{}", parent_name, lang.path_separator(), item.name),
+        None => item.name.clone(),
+    };
+    let mut header = match parent {
+        Some((parent_signature, _)) => format!("{} > {}", parent_signature, item.signature),
+        None => item.signature.clone(),
+    };
+    if let Some(doc) = &item.doc {
+        header.push_str(" — ");
+        header.push_str(doc);
+    }
+
+    // Containers keep their own section only for text outside members (fields, attributes)
+    let own = remaining_lines(lines, item.start..item.end, &item.children);
+    let has_own_text = own
+        .lines()
+        .map(str::trim)
+        .any(|l| !l.is_empty() && !matches!(l, "{" | "}" | "};") && !l.ends_with('{') && !l.ends_with(':'));
+    if item.children.is_empty() || has_own_text {
+        sections.push(Section {
+            header,
+            content: own,
+            section_type: Some(qualified.clone()),
+            page: None,
+        });
+    }
+
+    for child in &item.children {
+        push_item_sections(sections, lines, child, Some((&item.signature, &qualified)), lang);
+    }
+}
+
+/// Lines of `range` not covered by `items`, joined with newlines
+fn remaining_lines(lines: &[&str], range: std::ops::Range<usize>, items: &[Item]) -> String {
+    range
+        .filter(|i| !items.iter().any(|item| (item.start..item.end).contains(i)))
+        .map(|i| lines[i])
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
+
+/// Bracket depth at the start and end of a line, and whether it opens a `{` block
+#[derive(Debug, Clone, Copy, Default)]
+struct LineDepth {
+    start: usize,
+    end: usize,
+    opens_block: bool,
+}
+
+/// Track bracket depth per line, ignoring brackets in strings and comments
+fn brace_depths(content: &str, lang: Lang) -> Vec<LineDepth> {
+    let mut depth: usize = 0;
+    let mut block_comment = false;
+    // Open string: (closing quote, escapes allowed, raw-string hashes)
+    let mut string: Option<(char, bool, usize)> = None;
+    let mut result = Vec::new();
+
+    for line in content.lines() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut info = LineDepth { start: depth, ..Default::default() };
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            if block_comment {
+                if c == '*' && next == Some('/') {
+                    block_comment = false;
+                    i += 1;
+                }
+            } else if let Some((quote, escapes, hashes)) = string {
+                if escapes && c == '\\' {
+                    i += 1;
+                } else if c == quote && chars[i + 1..].iter().take(hashes).filter(|&&h| h == '#').count() == hashes {
+                    string = None;
+                    i += hashes;
+                }
+            } else {
+                match c {
+                    '/' if next == Some('/') => break,
+                    '/' if next == Some('*') => {
+                        block_comment = true;
+                        i += 1;
+                    }
+                    'r' if lang == Lang::Rust && !is_ident_char(i.checked_sub(1).map(|p| chars[p]))
+                        && matches!(next, Some('"') | Some('#')) =>
+                    {
+                        let hashes = chars[i + 1..].iter().take_while(|&&h| h == '#').count();
+                        if chars.get(i + 1 + hashes) == Some(&'"') {
+                            string = Some(('"', false, hashes));
+                            i += 1 + hashes;
+                        }
+                    }
+                    '"' => string = Some(('"', true, 0)),
+                    '`' if lang != Lang::Rust => string = Some(('`', lang != Lang::Go, 0)),
+                    '\'' if lang == Lang::Rust => {
+                        // Char literal ('x', '\n') vs. lifetime ('a)
+                        if next == Some('\\') {
+                            i += 2;
+                            while i < chars.len() && chars[i] != '\'' {
+                                i += 1;
+                            }
+                        } else if chars.get(i + 2) == Some(&'\'') {
+                            i += 2;
+                        }
+                    }
+                    '\'' => string = Some(('\'', true, 0)),
+                    '{' | '(' | '[' => {
+                        depth += 1;
+                        if c == '{' {
+                            info.opens_block = true;
+                        }
+                    }
+                    '}' | ')' | ']' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            i += 1;
+        }
+        // Only template literals, raw strings and Rust strings may span lines
+        if matches!(string, Some(('\'', _, _))) {
+            string = None;
+        }
+        info.end = depth;
+        result.push(info);
+    }
+    result
+}
+
+fn is_ident_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Kind of item a line starts
+#[derive(Debug, Clone, PartialEq)]
+enum ItemStart {
+    /// Functions and types without member sections
+    Leaf(String),
+    /// impl blocks, traits, modules and classes whose members get their own sections
+    Container(String),
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).expect("Invalid regex pattern"))
+}
+
+/// Detect the start of an item in a C-like language; `parent` is set inside containers
+fn brace_item_start(line: &str, lang: Lang, parent: Option<Lang>) -> Option<ItemStart> {
+    static RUST_FN: OnceLock<Regex> = OnceLock::new();
+    static RUST_TYPE: OnceLock<Regex> = OnceLock::new();
+    static RUST_IMPL: OnceLock<Regex> = OnceLock::new();
+    static RUST_MACRO: OnceLock<Regex> = OnceLock::new();
+    static GO_FUNC: OnceLock<Regex> = OnceLock::new();
+    static GO_TYPE: OnceLock<Regex> = OnceLock::new();
+    static JS_FUNCTION: OnceLock<Regex> = OnceLock::new();
+    static JS_CLASS: OnceLock<Regex> = OnceLock::new();
+    static JS_TYPE: OnceLock<Regex> = OnceLock::new();
+    static JS_ARROW: OnceLock<Regex> = OnceLock::new();
+    static JS_METHOD: OnceLock<Regex> = OnceLock::new();
+    static JS_FIELD_ARROW: OnceLock<Regex> = OnceLock::new();
+
+    match lang {
+        Lang::Rust => {
+            let vis = r#"^(?:pub(?:\([^)]*\))?\s+)?"#;
+            let fn_re = regex(&RUST_FN, &format!(
+                r#"{}(?:default\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s+([A-Za-z_]\w*)"#,
+                vis
+            ));
+            if let Some(c) = fn_re.captures(line) {
+                return Some(ItemStart::Leaf(c[1].to_string()));
+            }
+            let type_re = regex(&RUST_TYPE, &format!(
+                r"{}(?:unsafe\s+)?(?:auto\s+)?(struct|enum|union|trait|mod)\s+([A-Za-z_]\w*)",
+                vis
+            ));
+            if let Some(c) = type_re.captures(line) {
+                return match &c[1] {
+                    // `mod foo;` declarations stay in the module section
+                    "mod" if line.trim_end().ends_with(';') => None,
+                    "trait" | "mod" => Some(ItemStart::Container(c[2].to_string())),
+                    _ => Some(ItemStart::Leaf(c[2].to_string())),
+                };
+            }
+            let impl_re = regex(&RUST_IMPL, r"^(?:unsafe\s+)?impl\b(?:\s*<[^{]*?>)?\s+([^{]+)");
+            if let Some(c) = impl_re.captures(line) {
+                let target = c[1].split(" where").next().unwrap_or("");
+                let target = target.rsplit(" for ").next().unwrap_or(target);
+                return Some(ItemStart::Container(type_name(target)));
+            }
+            let macro_re = regex(&RUST_MACRO, r"^macro_rules!\s*([A-Za-z_]\w*)");
+            macro_re.captures(line).map(|c| ItemStart::Leaf(c[1].to_string()))
+        }
+        Lang::Go => {
+            let func_re = regex(&GO_FUNC, r"^func\s+(?:\(([^)]*)\)\s*)?([A-Za-z_]\w*)");
+            if let Some(c) = func_re.captures(line) {
+                let name = match c.get(1) {
+                    Some(receiver) => {
+                        let receiver = receiver.as_str().split_whitespace().last().unwrap_or("");
+                        format!("{}.{}", type_name(receiver), &c[2])
+                    }
+                    None => c[2].to_string(),
+                };
+                return Some(ItemStart::Leaf(name));
+            }
+            let type_re = regex(&GO_TYPE, r"^type\s+([A-Za-z_]\w*)");
+            type_re.captures(line).map(|c| ItemStart::Leaf(c[1].to_string()))
+        }
+        Lang::TypeScript | Lang::JavaScript if parent.is_some() => {
+            const NOT_METHODS: &[&str] = &["if", "for", "while", "switch", "catch", "return", "function", "new", "super", "await"];
+            let modifiers = r"^(?:(?:public|private|protected|static|readonly|abstract|override|async|declare|get|set)\s+)*";
+            let method_re = regex(&JS_METHOD, &format!(r"{}\*?\s*(#?[A-Za-z_$][\w$]*)\s*\??\s*(?:<[^>]*>)?\s*\(", modifiers));
+            if let Some(c) = method_re.captures(line) {
+                return (!NOT_METHODS.contains(&&c[1])).then(|| ItemStart::Leaf(c[1].to_string()));
+            }
+            let field_re = regex(&JS_FIELD_ARROW, &format!(
+                r"{}(#?[A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::[^=]+)?=>",
+                modifiers
+            ));
+            field_re.captures(line).map(|c| ItemStart::Leaf(c[1].to_string()))
+        }
+        Lang::TypeScript | Lang::JavaScript => {
+            let export = r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?";
+            let function_re = regex(&JS_FUNCTION, &format!(r"{}(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)", export));
+            if let Some(c) = function_re.captures(line) {
+                return Some(ItemStart::Leaf(c[1].to_string()));
+            }
+            let class_re = regex(&JS_CLASS, &format!(r"{}(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)", export));
+            if let Some(c) = class_re.captures(line) {
+                return Some(ItemStart::Container(c[1].to_string()));
+            }
+            let type_re = regex(&JS_TYPE, &format!(r"{}(?:const\s+)?(?:interface|enum|type)\s+([A-Za-z_$][\w$]*)", export));
+            if let Some(c) = type_re.captures(line) {
+                return Some(ItemStart::Leaf(c[1].to_string()));
+            }
+            let arrow_re = regex(&JS_ARROW, &format!(
+                r"{}(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:function\b|\(|[A-Za-z_$][\w$]*\s*=>)",
+                export
+            ));
+            arrow_re.captures(line).map(|c| ItemStart::Leaf(c[1].to_string()))
+        }
+        Lang::Python => None,
+    }
+}
+
+/// Bare type name of `crate::search::Engine<T>` or `*Server`
+fn type_name(path: &str) -> String {
+    let path = path.trim().trim_start_matches(['*', '&']);
+    let path = path.split('<').next().unwrap_or(path);
+    path.rsplit("::").next().unwrap_or(path).trim().to_string()
+}
+
+/// Lines that belong to the item below them (doc comments, attributes, decorators)
+fn is_leading_line(trimmed: &str, lang: Lang) -> bool {
+    match lang {
+        Lang::Python => trimmed.starts_with('#') || trimmed.starts_with('@'),
+        _ => {
+            trimmed.starts_with("//")
+                || trimmed.starts_with("/*")
+                || trimmed.starts_with('*')
+                || (lang == Lang::Rust && trimmed.starts_with("#["))
+                || (matches!(lang, Lang::TypeScript | Lang::JavaScript) && trimmed.starts_with('@'))
+        }
+    }
+}
+
+/// Find items among the lines of `range` that sit at bracket depth `depth`
+fn brace_items(
+    lines: &[&str],
+    depths: &[LineDepth],
+    range: std::ops::Range<usize>,
+    depth: usize,
+    lang: Lang,
+    parent: Option<Lang>,
+) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut leading: Option<usize> = None;
+    let mut i = range.start;
+
+    while i < range.end {
+        let trimmed = lines[i].trim();
+        if depths[i].start != depth || trimmed.is_empty() {
+            leading = None;
+            i += 1;
+            continue;
+        }
+        let Some(kind) = brace_item_start(trimmed, lang, parent) else {
+            if is_leading_line(trimmed, lang) && !trimmed.starts_with("//!") {
+                leading.get_or_insert(i);
+            } else {
+                leading = None;
+            }
+            i += 1;
+            continue;
+        };
+
+        // Item ends where depth returns to this level after a block, or at a `;`
+        let mut end = i;
+        let mut opened = false;
+        while end < range.end {
+            opened |= depths[end].opens_block;
+            let line = lines[end].trim_end();
+            let ends_statement = line.ends_with(';')
+                || (lang != Lang::Rust && !opened && !ends_with_continuation(line));
+            if depths[end].end <= depth && (opened || ends_statement) {
+                break;
+            }
+            end += 1;
+        }
+        let end = (end + 1).min(range.end);
+        let start = leading.take().unwrap_or(i);
+
+        let (name, children) = match kind {
+            ItemStart::Leaf(name) => (name, Vec::new()),
+            ItemStart::Container(name) => {
+                let children = brace_items(lines, depths, i + 1..end, depth + 1, lang, Some(lang));
+                (name, children)
+            }
+        };
+        items.push(Item {
+            name,
+            signature: brace_signature(&lines[i..end]),
+            doc: doc_summary(&lines[start..i]),
+            start,
+            end,
+            children,
+        });
+        i = end;
+    }
+    items
+}
+
+fn ends_with_continuation(line: &str) -> bool {
+    line.is_empty()
+        || line.ends_with(['=', ',', '(', '[', '{', '<', '+', '-', '*', '/', '|', '&', '?', ':', '>'])
+}
+
+/// Item declaration up to its body, on one line
+fn brace_signature(lines: &[&str]) -> String {
+    let mut signature = String::new();
+    let mut parens = 0usize;
+    'lines: for line in lines {
+        for c in line.trim().chars() {
+            match c {
+                '(' | '[' => parens += 1,
+                ')' | ']' => parens = parens.saturating_sub(1),
+                '{' if parens == 0 => break 'lines,
+                ';' if parens == 0 => break 'lines,
+                _ => {}
+            }
+            signature.push(c);
+        }
+        signature.push(' ');
+    }
+    shorten_signature(&signature)
+}
+
+fn shorten_signature(signature: &str) -> String {
+    let signature = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+    let signature = signature.trim_end_matches([' ', '=', ':']).replace("( ", "(").replace(" )", ")");
+    if signature.chars().count() > MAX_SIGNATURE_CHARS {
+        let cut: String = signature.chars().take(MAX_SIGNATURE_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        signature
+    }
+}
+
+/// First sentence-like line of a doc comment block
+fn doc_summary(lines: &[&str]) -> Option<String> {
+    lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.starts_with("#[") && !l.starts_with('@'))
+        .map(|l| {
+            l.trim_start_matches("///")
+                .trim_start_matches("//")
+                .trim_start_matches("/**")
+                .trim_start_matches("/*")
+                .trim_end_matches("*/")
+                .trim_start_matches('*')
+                .trim_start_matches('#')
+                .trim()
+        })
+        .find(|l| !l.is_empty() && !l.starts_with('@'))
+        .map(str::to_string)
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Find `def`/`class` items among the lines of `range` indented exactly `indent`
+fn python_items(lines: &[&str], range: std::ops::Range<usize>, indent: usize) -> Vec<Item> {
+    static PY_ITEM: OnceLock<Regex> = OnceLock::new();
+    let item_re = regex(&PY_ITEM, r"^(?:async\s+)?(def|class)\s+([A-Za-z_]\w*)");
+
+    let mut items = Vec::new();
+    let mut leading: Option<usize> = None;
+    let mut i = range.start;
+
+    while i < range.end {
+        let line = lines[i];
+        let trimmed = line.trim();
+        if trimmed.is_empty() || indent_of(line) != indent {
+            leading = None;
+            i += 1;
+            continue;
+        }
+        let Some(c) = item_re.captures(trimmed) else {
+            if is_leading_line(trimmed, Lang::Python) {
+                leading.get_or_insert(i);
+            } else {
+                leading = None;
+            }
+            i += 1;
+            continue;
+        };
+        let is_class = &c[1] == "class";
+        let name = c[2].to_string();
+
+        // Signature may span lines until the `:` outside parentheses
+        let mut signature_end = i;
+        let mut parens = 0i32;
+        while signature_end < range.end {
+            for ch in lines[signature_end].chars() {
+                match ch {
+                    '(' | '[' => parens += 1,
+                    ')' | ']' => parens -= 1,
+                    _ => {}
+                }
+            }
+            if parens <= 0 && lines[signature_end].trim_end().ends_with(':') {
+                break;
+            }
+            signature_end += 1;
+        }
+        let signature_end = signature_end.min(range.end - 1);
+
+        // Body: following lines indented deeper (blank lines included)
+        let mut end = signature_end + 1;
+        while end < range.end {
+            let body_line = lines[end];
+            if !body_line.trim().is_empty() && indent_of(body_line) <= indent {
+                break;
+            }
+            end += 1;
+        }
+        while end > signature_end + 1 && lines[end - 1].trim().is_empty() {
+            end -= 1;
+        }
+
+        let start = leading.take().unwrap_or(i);
+        let body_indent = lines[signature_end + 1..end]
+            .iter()
+            .find(|l| !l.trim().is_empty())
+            .map(|l| indent_of(l));
+        let children = match body_indent {
+            Some(body_indent) if is_class => python_items(lines, signature_end + 1..end, body_indent),
+            _ => Vec::new(),
+        };
+        let doc = python_docstring(&lines[signature_end + 1..end]).or_else(|| doc_summary(&lines[start..i]));
+        items.push(Item {
+            name,
+            signature: shorten_signature(&lines[i..=signature_end].join(" ")),
+            doc,
+            start,
+            end,
+            children,
+        });
+        i = end;
+    }
+    items
+}
+
+/// First line of a docstring opening the body
+fn python_docstring(body: &[&str]) -> Option<String> {
+    let mut lines = body.iter().map(|l| l.trim()).filter(|l| !l.is_empty());
+    let first = lines.next()?;
+    let first = first.trim_start_matches(['r', 'u', 'R', 'U']);
+    let quote = ["\"\"\"", "'''"].into_iter().find(|q| first.starts_with(q))?;
+    let text = first[3..].trim_end_matches(quote).trim();
+    if !text.is_empty() {
+        return Some(text.to_string());
+    }
+    lines.next().map(|l| l.trim_end_matches(quote).trim().to_string()).filter(|l| !l.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str, path: &str) -> ParsedDocument {
+        CodeParser.parse(content, path).unwrap()
+    }
+
+    fn types(doc: &ParsedDocument) -> Vec<&str> {
+        doc.sections.iter().filter_map(|s| s.section_type.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_rust_items_and_impl_methods() {
+        let source = r#"use std::fmt;
+
+const LIMIT: usize = 10;
+
+/// Registry of parsers.
+#[derive(Default)]
+pub struct Registry {
+    names: Vec<String>, // "{" in a comment
+}
+
+impl Registry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self { names: vec!["}".to_string()] }
+    }
+
+    fn find<'a>(&'a self, name: &str) -> Option<&'a String> {
+        let brace = '{';
+        self.names.iter().find(|n| n.as_str() == name && brace != '}')
+    }
+}
+
+pub async fn run(
+    registry: &Registry,
+) -> Result<(), String> {
+    Ok(())
+}
+"#;
+        let doc = parse(source, "src/registry.rs");
+        assert_eq!(doc.doc_type, "rust");
+        assert_eq!(types(&doc), vec!["Registry", "Registry::new", "Registry::find", "run"]);
+
+        assert_eq!(doc.sections[0].header, "module");
+        assert!(doc.sections[0].content.contains("const LIMIT"));
+        assert_eq!(doc.sections[1].header, "pub struct Registry — Registry of parsers.");
+        assert!(doc.sections[1].content.starts_with("/// Registry of parsers.\n#[derive(Default)]"));
+        assert_eq!(doc.sections[2].header, "impl Registry > pub fn new() -> Self — Create an empty registry.");
+        assert_eq!(doc.sections[4].header, "pub async fn run(registry: &Registry,) -> Result<(), String>");
+        assert!(doc.sections[4].content.ends_with("Ok(())\n}"));
+    }
+
+    #[test]
+    fn test_rust_trait_impl_named_by_type() {
+        let source = "impl Parser for crate::HtmlParser<'_> {\n    fn can_parse(&self) -> bool {\n        true\n    }\n}\n";
+        let doc = parse(source, "lib.rs");
+        assert_eq!(types(&doc), vec!["HtmlParser::can_parse"]);
+        assert!(doc.sections[0].header.starts_with("impl Parser for crate::HtmlParser<'_> > fn can_parse"));
+    }
+
+    #[test]
+    fn test_python_classes_and_docstrings() {
+        let source = r#"import os
+
+@dataclass
+class Gateway:
+    """Routes requests."""
+
+    timeout: int = 30
+
+    def route(self, request,
+              retries=3):
+        """
+        Route one request.
+        """
+        return request
+
+def main():
+    # entry point
+    Gateway().route(None)
+"#;
+        let doc = parse(source, "gateway.py");
+        assert_eq!(doc.doc_type, "python");
+        assert_eq!(types(&doc), vec!["Gateway", "Gateway.route", "main"]);
+        assert_eq!(doc.sections[1].header, "class Gateway — Routes requests.");
+        assert!(doc.sections[1].content.starts_with("@dataclass\nclass Gateway:"));
+        assert!(doc.sections[1].content.contains("timeout: int = 30"));
+        assert_eq!(
+            doc.sections[2].header,
+            "class Gateway > def route(self, request, retries=3) — Route one request."
+        );
+        assert!(doc.sections[3].content.ends_with("Gateway().route(None)"));
+    }
+
+    #[test]
+    fn test_typescript_functions_and_classes() {
+        let source = r#"import { x } from "./x";
+
+/**
+ * Fetch a page.
+ * @param url target
+ */
+export async function fetchPage(url: string): Promise<string> {
+  return `${url} {`;
+}
+
+export const handler = async (event: Event) => {
+  return 1;
+};
+
+export class Client {
+  private base = "/api";
+
+  constructor(base: string) {
+    this.base = base;
+  }
+
+  async get(path: string) {
+    if (path) {
+      return fetchPage(this.base + path);
+    }
+  }
+}
+
+interface Options {
+  retries: number;
+}
+"#;
+        let doc = parse(source, "client.ts");
+        assert_eq!(doc.doc_type, "typescript");
+        assert_eq!(
+            types(&doc),
+            vec!["fetchPage", "handler", "Client", "Client.constructor", "Client.get", "Options"]
+        );
+        assert_eq!(
+            doc.sections[1].header,
+            "export async function fetchPage(url: string): Promise<string> — Fetch a page."
+        );
+        assert!(doc.sections[3].content.contains("private base"));
+    }
+
+    #[test]
+    fn test_go_functions_and_methods() {
+        let source = r#"package main
+
+// Server handles requests.
+type Server struct {
+	addr string
+}
+
+// Start listens on addr.
+func (s *Server) Start() error {
+	return nil
+}
+
+func main() {
+	s := &Server{addr: ":8080"}
+	_ = s.Start()
+}
+"#;
+        let doc = parse(source, "main.go");
+        assert_eq!(types(&doc), vec!["Server", "Server.Start", "main"]);
+        assert_eq!(doc.sections[0].content, "package main");
+        assert_eq!(doc.sections[2].header, "func (s *Server) Start() error — Start listens on addr.");
+    }
+}
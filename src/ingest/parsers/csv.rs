@@ -0,0 +1,239 @@
+use super::{Parser, ParsedDocument, Section};
+use crate::config::CsvConfig;
+use crate::error::{Result, RagmcpError};
+use serde_json::json;
+
+/// Delimiters tried when sniffing a `.csv` file
+const CANDIDATE_DELIMITERS: &[u8] = b",;\t|";
+
+/// Lines inspected to detect the delimiter
+const SNIFF_LINES: usize = 20;
+
+/// CSV/TSV parser for reference tables
+///
+/// The delimiter is detected from the first lines (`.tsv` is always tab separated),
+/// and the first row is used as column names when it looks like a header (all cells
+/// filled, distinct and not numeric); otherwise columns are named `column 1`, ...
+/// Rows are grouped into sections of `rows_per_section` rows headed `Rows 1-20`, each
+/// row rendered as `column: value` pairs so every chunk describes itself. Files with
+/// more than `max_rows` rows are truncated.
+pub struct CsvParser {
+    max_rows: usize,
+    rows_per_section: usize,
+}
+
+impl CsvParser {
+    pub fn new(config: &CsvConfig) -> Self {
+        Self {
+            max_rows: config.max_rows.max(1),
+            rows_per_section: config.rows_per_section.max(1),
+        }
+    }
+}
+
+impl Default for CsvParser {
+    fn default() -> Self {
+        Self::new(&CsvConfig::default())
+    }
+}
+
+impl Parser for CsvParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        matches!(extension, "csv" | "tsv")
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        let delimiter = if path.to_lowercase().ends_with(".tsv") {
+            b'\t'
+        } else {
+            detect_delimiter(content)
+        };
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(content.trim_start_matches('\u{feff}').as_bytes());
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut truncated = false;
+        for record in reader.records() {
+            let record = record.map_err(|e| RagmcpError::Parse(format!("CSV error in {}: {}", path, e)))?;
+            if record.iter().all(|c| c.trim().is_empty()) {
+                continue;
+            }
+            // One extra row: the header is not counted against the limit
+            if rows.len() > self.max_rows {
+                truncated = true;
+                break;
+            }
+            rows.push(record.iter().map(|c| c.trim().to_string()).collect());
+        }
+
+        let has_header = rows.first().is_some_and(|r| looks_like_header(r));
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let columns: Vec<String> = match has_header {
+            true => (0..width)
+                .map(|i| match rows[0].get(i) {
+                    Some(name) if !name.is_empty() => name.clone(),
+                    _ => format!("column {}", i + 1),
+                })
+                .collect(),
+            false => (1..=width).map(|i| format!("column {}", i)).collect(),
+        };
+        let data = &rows[usize::from(has_header)..];
+        let truncated = truncated || data.len() > self.max_rows;
+        let data = &data[..data.len().min(self.max_rows)];
+        if truncated {
+            log::warn!("{}: only the first {} rows are indexed", path, self.max_rows);
+        }
+
+        let sections: Vec<Section> = data
+            .chunks(self.rows_per_section)
+            .enumerate()
+            .map(|(idx, group)| {
+                let first = idx * self.rows_per_section + 1;
+                let content = group
+                    .iter()
+                    .map(|row| render_row(&columns, row))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Section {
+                    header: format!("Rows {}-{}", first, first + group.len() - 1),
+                    content,
+                    section_type: Some("rows".to_string()),
+                    page: None,
+                }
+            })
+            .collect();
+        let sections = if sections.is_empty() {
+            vec![Section {
+                header: "content".to_string(),
+                content: content.to_string(),
+                section_type: None,
+                page: None,
+            }]
+        } else {
+            sections
+        };
+
+        let delimiter_name = match delimiter {
+            b'\t' => "tab".to_string(),
+            d => (d as char).to_string(),
+        };
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: if delimiter == b'\t' { "tsv" } else { "csv" }.to_string(),
+            metadata: Some(json!({
+                "columns": columns,
+                "has_header": has_header,
+                "delimiter": delimiter_name,
+                "rows": data.len(),
+                "truncated": truncated,
+            })),
+        })
+    }
+}
+
+/// Pick the candidate delimiter that splits the sample lines into the most consistent
+/// number of fields (quoted fields are ignored)
+fn detect_delimiter(content: &str) -> u8 {
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).take(SNIFF_LINES).collect();
+    let mut best = (b',', 0usize);
+    for &delimiter in CANDIDATE_DELIMITERS {
+        let counts: Vec<usize> = lines.iter().map(|l| count_unquoted(l, delimiter)).collect();
+        let Some(&first) = counts.first() else { break };
+        if first == 0 {
+            continue;
+        }
+        // Lines agreeing with the first line's field count, weighted by that count
+        let score = counts.iter().filter(|&&c| c == first).count() * 100 + first;
+        if score > best.1 {
+            best = (delimiter, score);
+        }
+    }
+    best.0
+}
+
+fn count_unquoted(line: &str, delimiter: u8) -> usize {
+    let mut in_quotes = false;
+    line.bytes()
+        .filter(|&b| {
+            if b == b'"' {
+                in_quotes = !in_quotes;
+            }
+            b == delimiter && !in_quotes
+        })
+        .count()
+}
+
+/// A header row has distinct, non-empty, non-numeric cells
+fn looks_like_header(row: &[String]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    row.iter().all(|cell| {
+        !cell.is_empty() && cell.parse::<f64>().is_err() && seen.insert(cell.to_lowercase())
+    })
+}
+
+/// `column: value` pairs of a row; empty values are left out
+fn render_row(columns: &[String], row: &[String]) -> String {
+    columns
+        .iter()
+        .zip(row)
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(column, value)| format!("{}: {}", column, value.replace('\n', " ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_header_and_row_groups() {
+        let mut content = String::from("code;message;severity\n");
+        for i in 1..=5 {
+            content.push_str(&format!("E{};\"Timeout; retry {}\";high\n", 1000 + i, i));
+        }
+        let parser = CsvParser::new(&CsvConfig { max_rows: 4, rows_per_section: 3 });
+        let doc = parser.parse(&content, "errors/codes.csv").unwrap();
+
+        assert_eq!(doc.doc_type, "csv");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Rows 1-3");
+        assert_eq!(
+            doc.sections[0].content.lines().next(),
+            Some("code: E1001; message: Timeout; retry 1; severity: high")
+        );
+        assert_eq!(doc.sections[1].header, "Rows 4-4");
+
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata["delimiter"], ";");
+        assert_eq!(metadata["columns"][2], "severity");
+        assert_eq!(metadata["rows"], 4);
+        assert_eq!(metadata["truncated"], true);
+    }
+
+    #[test]
+    fn test_tsv_without_header() {
+        let content = "1001\tTimeout\t30\n1002\t\t60\n";
+        let doc = CsvParser::default().parse(content, "limits.tsv").unwrap();
+
+        assert_eq!(doc.doc_type, "tsv");
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(
+            doc.sections[0].content,
+            "column 1: 1001; column 2: Timeout; column 3: 30\ncolumn 1: 1002; column 3: 60"
+        );
+        assert_eq!(doc.metadata.unwrap()["has_header"], false);
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter("a,b,c\n1,2,3\n"), b',');
+        assert_eq!(detect_delimiter("a|b\n\"x|y\"|z\n"), b'|');
+        assert_eq!(detect_delimiter("a\tb\tc\n1\t2\t3\n"), b'\t');
+        assert_eq!(detect_delimiter("single column\n"), b',');
+    }
+}
@@ -0,0 +1,156 @@
+use super::office::{attr, document_properties, for_each_event, Package, TextFlow, XmlEvent};
+use super::section_builder::document;
+use super::{Parser, ParsedDocument};
+use crate::error::{Result, RagmcpError};
+use std::collections::HashMap;
+
+/// Word (DOCX) parser
+///
+/// Reads `word/document.xml` and turns paragraphs styled as `Title` or `Heading N`
+/// (or carrying an outline level) into heading sections. Body paragraphs, list items
+/// (`- item`) and tables (`| a | b |` rows) form the section content. Title and
+/// author from `docProps/core.xml` are kept as metadata.
+pub struct DocxParser;
+
+impl Parser for DocxParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "docx"
+    }
+
+    fn parse(&self, _content: &str, path: &str) -> Result<ParsedDocument> {
+        Err(RagmcpError::Parse(format!("{} is a Word document and must be parsed from bytes", path)))
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let mut package = Package::open(bytes, path)?;
+        let heading_styles = match package.read("word/styles.xml")? {
+            Some(xml) => heading_styles(&xml)?,
+            None => HashMap::new(),
+        };
+        let xml = package.read_required("word/document.xml")?;
+
+        let mut flow = TextFlow::default();
+        let mut in_run = false;
+        let mut in_text = false;
+        for_each_event(&xml, |event| match event {
+            XmlEvent::Start(name, attrs) => match name.as_str() {
+                "pStyle" => {
+                    if let Some(level) = attr(&attrs, "w:val").and_then(|id| {
+                        heading_styles.get(id).copied().or_else(|| heading_level(id))
+                    }) {
+                        flow.set_heading(level);
+                    }
+                }
+                "outlineLvl" => {
+                    // Level 9 means body text
+                    if let Some(level) = attr(&attrs, "w:val").and_then(|v| v.parse::<usize>().ok()) {
+                        if level < 9 {
+                            flow.set_heading(level + 1);
+                        }
+                    }
+                }
+                "numPr" => flow.set_list_item(),
+                "r" => in_run = true,
+                "t" => in_text = in_run,
+                "tab" if in_run => flow.text("\t"),
+                "br" | "cr" if in_run => flow.text("\n"),
+                "tbl" => flow.start_table(),
+                _ => {}
+            },
+            XmlEvent::Text(text) if in_text => flow.text(&text),
+            XmlEvent::End(name) => match name.as_str() {
+                "t" => in_text = false,
+                "r" => in_run = false,
+                "p" => flow.end_paragraph(),
+                "tc" => flow.end_cell(),
+                "tr" => flow.end_row(),
+                "tbl" => flow.end_table(),
+                _ => {}
+            },
+            _ => {}
+        })?;
+
+        let metadata = document_properties(&mut package, "docProps/core.xml")?;
+        document(flow.builder.finish(""), path, "docx", metadata)
+    }
+}
+
+/// Heading level of every paragraph style named `Title` or `heading N` in `word/styles.xml`,
+/// keyed by style id (ids are localized, names are not)
+fn heading_styles(xml: &[u8]) -> Result<HashMap<String, usize>> {
+    let mut styles = HashMap::new();
+    let mut style_id: Option<String> = None;
+    for_each_event(xml, |event| match event {
+        XmlEvent::Start(name, attrs) if name == "style" => {
+            style_id = attr(&attrs, "w:styleId").map(str::to_string);
+        }
+        XmlEvent::Start(name, attrs) if name == "name" => {
+            if let (Some(id), Some(level)) = (&style_id, attr(&attrs, "w:val").and_then(heading_level)) {
+                styles.insert(id.clone(), level);
+            }
+        }
+        _ => {}
+    })?;
+    Ok(styles)
+}
+
+/// Level for `Title` / `heading 2` / `Heading2` style names
+fn heading_level(name: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    if name == "title" {
+        return Some(1);
+    }
+    name.strip_prefix("heading")?.trim().parse().ok().filter(|l| (1..=9).contains(l))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::parsers::office::build_package;
+
+    const DOCUMENT: &str = r#"<w:document xmlns:w="w"><w:body>
+<w:p><w:pPr><w:pStyle w:val="Titel"/></w:pPr><w:r><w:t>Gateway Spec</w:t></w:r></w:p>
+<w:p><w:r><w:t xml:space="preserve">Routes </w:t></w:r><w:r><w:t>requests &amp; retries.</w:t></w:r></w:p>
+<w:p><w:pPr><w:pStyle w:val="Heading2"/></w:pPr><w:r><w:t>Limits</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/></w:numPr></w:pPr><w:r><w:t>100 rps</w:t></w:r></w:p>
+<w:tbl><w:tr><w:tc><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Value</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>timeout</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>30s</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+<w:p><w:r><w:delText>removed</w:delText><w:instrText>PAGE</w:instrText></w:r></w:p>
+</w:body></w:document>"#;
+
+    const STYLES: &str = r#"<w:styles xmlns:w="w"><w:style w:styleId="Titel"><w:name w:val="Title"/></w:style></w:styles>"#;
+
+    #[test]
+    fn test_docx_headings_and_tables() {
+        let bytes = build_package(&[
+            ("word/document.xml", DOCUMENT),
+            ("word/styles.xml", STYLES),
+            ("docProps/core.xml", r#"<cp:coreProperties xmlns:dc="d" xmlns:cp="c"><dc:creator>Ops</dc:creator></cp:coreProperties>"#),
+        ]);
+        let doc = DocxParser.parse_bytes(&bytes, "spec.docx").unwrap();
+
+        assert_eq!(doc.doc_type, "docx");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Gateway Spec");
+        assert_eq!(doc.sections[0].section_type.as_deref(), Some("h1"));
+        assert_eq!(doc.sections[0].content, "Routes requests & retries.");
+        assert_eq!(doc.sections[1].header, "Limits");
+        assert_eq!(doc.sections[1].section_type.as_deref(), Some("h2"));
+        assert_eq!(
+            doc.sections[1].content,
+            "- 100 rps\n\n| Name | Value |\n| timeout | 30s |"
+        );
+        assert!(!doc.content.contains("removed") && !doc.content.contains("PAGE"));
+        assert_eq!(doc.metadata.unwrap()["author"], "Ops");
+    }
+
+    #[test]
+    fn test_docx_without_text_is_skipped() {
+        let bytes = build_package(&[("word/document.xml", "<w:document xmlns:w=\"w\"><w:body/></w:document>")]);
+        assert!(matches!(DocxParser.parse_bytes(&bytes, "empty.docx"), Err(RagmcpError::Skipped(_))));
+    }
+}
@@ -0,0 +1,344 @@
+use super::html::HtmlParser;
+use super::section_builder::document;
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+use mail_parser::mailbox::mbox::MessageIterator;
+use mail_parser::{Address, HeaderValue, Message, MessageParser, MimeHeaders};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// E-mail parser for single messages (`.eml`) and mailboxes (`.mbox`)
+///
+/// Each message becomes one document: a section headed by the subject holding the
+/// `From`/`To`/`Cc`/`Date` lines and the body. The body prefers `text/plain` parts and
+/// falls back to the HTML parts with markup stripped; quoted reply lines (`> ...`) are
+/// left out since the quoted message is indexed on its own. Headers are returned as
+/// metadata (`message_id`, `in_reply_to`, `references` and `thread_id` are used for
+/// the thread relations of the knowledge graph).
+///
+/// Mailboxes yield one document per message with the doc path `inbox.mbox#<part>`,
+/// where the part is a short hash of the Message-ID so a message keeps its path (and
+/// chunk ids) when others are added to or removed from the mailbox. Messages without
+/// a Message-ID, or repeating one already seen, fall back to their 1-based position.
+pub struct EmailParser;
+
+impl Parser for EmailParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        matches!(extension, "eml" | "mbox")
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        self.parse_bytes(content.as_bytes(), path)
+    }
+
+    /// Messages may use any charset, so they are always decoded from bytes
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let message = MessageParser::default()
+            .parse(bytes)
+            .ok_or_else(|| RagmcpError::Parse(format!("{} is not an e-mail message", path)))?;
+        parse_message(&message, path)
+    }
+
+    fn is_multi_document(&self) -> bool {
+        true
+    }
+
+    fn parse_documents(&self, bytes: &[u8], path: &str) -> Result<Vec<(String, ParsedDocument)>> {
+        if !path.to_lowercase().ends_with(".mbox") {
+            return Ok(vec![(path.to_string(), self.parse_bytes(bytes, path)?)]);
+        }
+
+        let mut documents = Vec::new();
+        let mut parts = HashSet::new();
+        for (idx, entry) in MessageIterator::new(bytes).enumerate() {
+            // One broken or empty message must not drop the rest of the mailbox
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(_) => {
+                    log::warn!("{}#{}: malformed mbox entry skipped", path, idx + 1);
+                    continue;
+                }
+            };
+            let message = MessageParser::default().parse(entry.contents());
+            let part = message
+                .as_ref()
+                .and_then(|m| m.message_id())
+                .map(message_part_id)
+                .filter(|part| parts.insert(part.clone()))
+                .unwrap_or_else(|| (idx + 1).to_string());
+            let doc_path = format!("{}#{}", path, part);
+            let parsed = message
+                .ok_or_else(|| RagmcpError::Parse(format!("{} is not an e-mail message", doc_path)))
+                .and_then(|message| parse_message(&message, &doc_path));
+            match parsed {
+                Ok(doc) => documents.push((doc_path, doc)),
+                Err(e) => log::warn!("{}: message skipped ({})", doc_path, e),
+            }
+        }
+        if documents.is_empty() {
+            return Err(RagmcpError::Skipped(format!("{} contains no messages", path)));
+        }
+        Ok(documents)
+    }
+}
+
+/// Part id of a mailbox message: the first 12 hex digits of the Message-ID's SHA-256
+fn message_part_id(message_id: &str) -> String {
+    format!("{:x}", Sha256::digest(message_id.as_bytes()))[..12].to_string()
+}
+
+fn parse_message(message: &Message, path: &str) -> Result<ParsedDocument> {
+    let subject = message.subject().map(str::trim).filter(|s| !s.is_empty()).unwrap_or("(no subject)");
+    let from = addresses(message.from());
+    let to = addresses(message.to());
+    let cc = addresses(message.cc());
+    let date = message.date().map(|d| d.to_rfc3339());
+
+    let mut lines = Vec::new();
+    for (label, value) in [("From", &from), ("To", &to), ("Cc", &cc)] {
+        if !value.is_empty() {
+            lines.push(format!("{}: {}", label, value.join(", ")));
+        }
+    }
+    if let Some(date) = &date {
+        lines.push(format!("Date: {}", date));
+    }
+    let body = message_body(message, path);
+    let content = format!("{}\n\n{}", lines.join("\n"), body).trim().to_string();
+
+    let message_id = message.message_id().map(str::to_string);
+    let in_reply_to = message_ids(message.in_reply_to()).into_iter().next();
+    let references = message_ids(message.references());
+    // The first reference is the thread root; a message without references starts its own thread
+    let thread_id = references.first().cloned().or_else(|| in_reply_to.clone()).or_else(|| message_id.clone());
+    let attachments: Vec<&str> = message.attachments().filter_map(|a| a.attachment_name()).collect();
+
+    let metadata = json!({
+        "title": subject,
+        "subject": subject,
+        "from": from.first(),
+        "from_address": message.from().and_then(Address::first).and_then(|a| a.address()).map(str::to_lowercase),
+        "to": to,
+        "cc": cc,
+        "date": date,
+        "message_id": message_id,
+        "in_reply_to": in_reply_to,
+        "references": references,
+        "thread_id": thread_id,
+        "attachments": attachments,
+    });
+    let metadata = match metadata {
+        Value::Object(map) => Value::Object(map.into_iter().filter(|(_, v)| !is_empty_value(v)).collect()),
+        other => other,
+    };
+
+    if body.is_empty() {
+        return Err(RagmcpError::Skipped(format!("{} has no body text", path)));
+    }
+    let section = Section {
+        header: subject.to_string(),
+        content,
+        section_type: Some("email".to_string()),
+        page: None,
+    };
+    document(vec![section], path, "email", Some(metadata))
+}
+
+/// Plain text body parts, or the HTML parts rendered as text when there are none
+fn message_body(message: &Message, path: &str) -> String {
+    let plain: Vec<&str> = message
+        .text_bodies()
+        .filter(|part| !part.is_text_html())
+        .filter_map(|part| part.text_contents())
+        .collect();
+    let body = if plain.is_empty() {
+        message
+            .html_bodies()
+            .filter(|part| part.is_text_html())
+            .filter_map(|part| part.text_contents())
+            .filter_map(|html| HtmlParser.parse(html, path).ok())
+            .map(|doc| doc.content)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    } else {
+        plain.join("\n\n")
+    };
+    strip_quoted(&body)
+}
+
+/// Drop quoted reply lines and the `On ... wrote:` line introducing them
+fn strip_quoted(body: &str) -> String {
+    let lines: Vec<&str> = body.lines().map(|l| l.trim_end()).collect();
+    let mut kept: Vec<&str> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if line.starts_with('>') {
+            continue;
+        }
+        let quote_follows = lines[idx + 1..]
+            .iter()
+            .find(|l| !l.is_empty())
+            .is_some_and(|l| l.starts_with('>'));
+        if line.ends_with("wrote:") && quote_follows {
+            continue;
+        }
+        if line.is_empty() && kept.last().map_or(true, |l| l.is_empty()) {
+            continue;
+        }
+        kept.push(line);
+    }
+    kept.join("\n").trim().to_string()
+}
+
+/// `Name <address>` (or the bare address) of every mailbox in an address header
+fn addresses(address: Option<&Address>) -> Vec<String> {
+    let Some(address) = address else { return Vec::new() };
+    address
+        .iter()
+        .filter_map(|a| match (a.name(), a.address()) {
+            (Some(name), Some(addr)) if !name.trim().is_empty() => Some(format!("{} <{}>", name.trim(), addr)),
+            (_, Some(addr)) => Some(addr.to_string()),
+            (Some(name), None) => Some(name.trim().to_string()),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+/// Message ids of `In-Reply-To` / `References` (angle brackets already removed)
+fn message_ids(value: &HeaderValue) -> Vec<String> {
+    match value {
+        HeaderValue::Text(id) => vec![id.to_string()],
+        HeaderValue::TextList(ids) => ids.iter().map(|id| id.to_string()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "From: Bo Berg <bo@example.com>\r\n\
+To: ops@example.com, Ana <ana@example.com>\r\n\
+Subject: Re: Gateway timeouts\r\n\
+Date: Tue, 3 Mar 2026 10:15:00 +0000\r\n\
+Message-ID: <c3@example.com>\r\n\
+In-Reply-To: <b2@example.com>\r\n\
+References: <a1@example.com> <b2@example.com>\r\n\
+\r\n\
+Raised the upstream timeout to 30s.\r\n\
+\r\n\
+On Mon, Ana wrote:\r\n\
+> Requests time out after 10s.\r\n";
+
+    #[test]
+    fn test_eml_headers_and_body() {
+        let doc = EmailParser.parse_bytes(REPLY.as_bytes(), "mail/timeouts.eml").unwrap();
+
+        assert_eq!(doc.doc_type, "email");
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].header, "Re: Gateway timeouts");
+        assert_eq!(
+            doc.sections[0].content,
+            "From: Bo Berg <bo@example.com>\nTo: ops@example.com, Ana <ana@example.com>\n\
+Date: 2026-03-03T10:15:00Z\n\nRaised the upstream timeout to 30s."
+        );
+
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata["from_address"], "bo@example.com");
+        assert_eq!(metadata["to"][1], "Ana <ana@example.com>");
+        assert_eq!(metadata["message_id"], "c3@example.com");
+        assert_eq!(metadata["in_reply_to"], "b2@example.com");
+        assert_eq!(metadata["thread_id"], "a1@example.com");
+        assert!(metadata.get("cc").is_none());
+    }
+
+    #[test]
+    fn test_html_only_body_is_stripped() {
+        let raw = "From: ana@example.com\r\nSubject: Status\r\nMessage-ID: <h1@example.com>\r\n\
+Content-Type: text/html; charset=utf-8\r\n\r\n\
+<html><head><style>p{}</style></head><body><p>All <b>green</b>.</p></body></html>\r\n";
+        let doc = EmailParser.parse_bytes(raw.as_bytes(), "status.eml").unwrap();
+
+        assert!(doc.sections[0].content.ends_with("\n\nAll green."));
+        assert!(!doc.content.contains('<'));
+        assert_eq!(doc.metadata.unwrap()["thread_id"], "h1@example.com");
+    }
+
+    #[test]
+    fn test_mbox_one_document_per_message() {
+        let mbox = format!(
+            "From ana@example.com Mon Mar  2 09:00:00 2026\n\
+From: ana@example.com\nSubject: Gateway timeouts\nMessage-ID: <a1@example.com>\n\n\
+Requests time out after 10s.\n\n\
+From bo@example.com Tue Mar  3 10:15:00 2026\n{}",
+            REPLY.replace("\r\n", "\n")
+        );
+        let documents = EmailParser.parse_documents(mbox.as_bytes(), "mail/ops.mbox").unwrap();
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].0, format!("mail/ops.mbox#{}", message_part_id("a1@example.com")));
+        assert_eq!(documents[0].1.sections[0].header, "Gateway timeouts");
+        assert_eq!(documents[1].0, format!("mail/ops.mbox#{}", message_part_id("c3@example.com")));
+        assert_eq!(documents[1].1.metadata.as_ref().unwrap()["in_reply_to"], "b2@example.com");
+    }
+
+    #[test]
+    fn test_mbox_corrupt_entry_is_skipped() {
+        let mbox = format!(
+            "From ana@example.com Mon Mar  2 09:00:00 2026\n\
+From: ana@example.com\nSubject: First\nMessage-ID: <a1@example.com>\n\nFirst body.\n\n\
+From broken Mon Mar  2 09:30:00 2026\n\0\0\x01\n\n\
+From bo@example.com Tue Mar  3 10:15:00 2026\n{}",
+            REPLY.replace("\r\n", "\n")
+        );
+        let documents = EmailParser.parse_documents(mbox.as_bytes(), "mail/ops.mbox").unwrap();
+
+        let subjects: Vec<&str> = documents.iter().map(|(_, doc)| doc.sections[0].header.as_str()).collect();
+        assert_eq!(subjects, vec!["First", "Re: Gateway timeouts"]);
+    }
+
+    #[test]
+    fn test_mbox_paths_survive_removed_messages() {
+        let message = |id: &str, subject: &str| {
+            let header = if id.is_empty() { String::new() } else { format!("Message-ID: <{}>\n", id) };
+            format!(
+                "From ana@example.com Mon Mar  2 09:00:00 2026\n\
+From: ana@example.com\nSubject: {}\n{}\nBody of {}.\n\n",
+                subject, header, subject
+            )
+        };
+        let paths = |mbox: &str| -> Vec<String> {
+            EmailParser
+                .parse_documents(mbox.as_bytes(), "ops.mbox")
+                .unwrap()
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect()
+        };
+        let first = message("a1@example.com", "First");
+        let rest = [message("b2@example.com", "Second"), message("c3@example.com", "Third")].concat();
+
+        let before = paths(&format!("{}{}", first, rest));
+        let after = paths(&rest);
+        assert_eq!(before.len(), 3);
+        assert_eq!(after, before[1..]);
+
+        // Without a Message-ID (or with a repeated one) the position is used
+        let fallback = paths(&[message("", "Anonymous"), message("b2@example.com", "Second"), message("b2@example.com", "Again")].concat());
+        assert_eq!(fallback[0], "ops.mbox#1");
+        assert_eq!(fallback[1], before[1]);
+        assert_eq!(fallback[2], "ops.mbox#3");
+    }
+}
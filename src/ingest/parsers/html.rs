@@ -0,0 +1,429 @@
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+/// Elements that never carry document content
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "iframe", "svg", "canvas",
+    "nav", "footer", "aside", "form", "button", "select",
+];
+
+/// ARIA landmark roles used for site chrome rather than content
+const SKIPPED_ROLES: &[&str] = &["navigation", "contentinfo", "banner", "search", "complementary"];
+
+/// Elements rendered as separate paragraphs
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "section", "article", "main", "header", "blockquote", "ul", "ol", "dl",
+    "figure", "figcaption", "details", "summary", "address", "center",
+];
+
+/// HTML parser for exported wiki pages and generated API docs
+///
+/// Drops scripts, styles, navigation, footers and other page chrome, then renders
+/// the remaining body as Markdown-like text: `h1`–`h6` start sections whose header
+/// is the heading breadcrumb ("Install > Linux"), tables become `| a | b |` rows and
+/// `<pre>` blocks become fenced code. `<title>`, `<meta>` tags and outbound links
+/// are returned as document metadata.
+pub struct HtmlParser;
+
+impl Parser for HtmlParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        matches!(extension, "html" | "htm")
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        let html = Html::parse_document(content);
+
+        let mut renderer = Renderer::default();
+        let body = Selector::parse("body").expect("valid selector");
+        let root = html.select(&body).next().unwrap_or_else(|| html.root_element());
+        renderer.walk(root);
+        renderer.flush_section();
+
+        if renderer.sections.is_empty() {
+            return Err(RagmcpError::Parse(format!("{} has no text content", path)));
+        }
+
+        let metadata = document_metadata(&html, renderer.links);
+        Ok(ParsedDocument {
+            content: renderer.content.trim_end().to_string(),
+            sections: renderer.sections,
+            doc_type: "html".to_string(),
+            metadata,
+        })
+    }
+}
+
+/// Renders the body into sections while walking the DOM
+#[derive(Default)]
+struct Renderer {
+    /// Full document text with Markdown-style heading lines
+    content: String,
+    sections: Vec<Section>,
+    /// Open headings as (level, text)
+    headings: Vec<(usize, String)>,
+    /// Body of the section being built
+    current: String,
+    links: Vec<Value>,
+    seen_links: HashSet<String>,
+}
+
+impl Renderer {
+    fn walk(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        let value = element.value();
+        if SKIPPED_ELEMENTS.contains(&name)
+            || value.attr("role").is_some_and(|r| SKIPPED_ROLES.contains(&r))
+            || value.attr("hidden").is_some()
+            || value.attr("aria-hidden") == Some("true")
+        {
+            return;
+        }
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(1);
+                let text = collapse_whitespace(&element.text().collect::<String>());
+                if !text.is_empty() {
+                    self.start_section(level, text);
+                }
+            }
+            "pre" => {
+                let code = element.text().collect::<String>();
+                self.paragraph_break();
+                self.current.push_str(&format!("```{}\n", code_language(element)));
+                self.current.push_str(code.trim_matches('\n'));
+                self.current.push_str("\n```");
+                self.paragraph_break();
+            }
+            "code" | "kbd" | "samp" => {
+                let code = element.text().collect::<String>();
+                self.push_text(&format!("`{}`", code.trim()));
+            }
+            "table" => {
+                self.paragraph_break();
+                self.table(element);
+                self.paragraph_break();
+            }
+            "br" => self.newline(),
+            "hr" => self.paragraph_break(),
+            "li" => {
+                self.newline();
+                self.current.push_str("- ");
+                self.walk(element);
+                self.newline();
+            }
+            "dt" | "dd" | "tr" => {
+                self.newline();
+                self.walk(element);
+                self.newline();
+            }
+            "img" => {
+                if let Some(alt) = value.attr("alt").map(str::trim).filter(|a| !a.is_empty()) {
+                    self.push_text(&format!("[image: {}]", alt));
+                }
+            }
+            "a" => {
+                if let Some(href) = value.attr("href") {
+                    self.record_link(href, &element.text().collect::<String>());
+                }
+                self.walk(element);
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => {
+                self.paragraph_break();
+                self.walk(element);
+                self.paragraph_break();
+            }
+            _ => self.walk(element),
+        }
+    }
+
+    /// Render rows as `| a | b |`, with a separator after a header row
+    fn table(&mut self, table: ElementRef) {
+        let rows = Selector::parse("tr").expect("valid selector");
+        let cells = Selector::parse("th, td").expect("valid selector");
+        for (idx, row) in table.select(&rows).enumerate() {
+            let values: Vec<String> = row
+                .select(&cells)
+                .map(|c| collapse_whitespace(&c.text().collect::<String>()).replace('|', "\\|"))
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            self.current.push_str(&format!("| {} |\n", values.join(" | ")));
+            let is_header = row.children().filter_map(ElementRef::wrap).all(|c| c.value().name() == "th");
+            if idx == 0 && is_header {
+                self.current.push_str(&format!("|{}\n", " --- |".repeat(values.len())));
+            }
+        }
+    }
+
+    fn start_section(&mut self, level: usize, text: String) {
+        self.flush_section();
+        self.headings.retain(|(l, _)| *l < level);
+        self.headings.push((level, text.clone()));
+        self.content.push_str(&format!("{} {}\n\n", "#".repeat(level), text));
+    }
+
+    fn flush_section(&mut self) {
+        let body = normalize_body(&std::mem::take(&mut self.current));
+        if body.is_empty() {
+            return;
+        }
+        let (header, section_type) = match self.headings.last() {
+            Some((level, _)) => (
+                self.headings.iter().map(|(_, h)| h.as_str()).collect::<Vec<_>>().join(" > "),
+                Some(format!("h{}", level)),
+            ),
+            None => ("content".to_string(), None),
+        };
+        self.content.push_str(&body);
+        self.content.push_str("\n\n");
+        self.sections.push(Section {
+            header,
+            content: body,
+            section_type,
+            page: None,
+        });
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let collapsed = collapse_whitespace(text);
+        if collapsed.is_empty() {
+            if text.chars().next().is_some_and(char::is_whitespace) {
+                self.space();
+            }
+            return;
+        }
+        if text.starts_with(char::is_whitespace) {
+            self.space();
+        }
+        self.current.push_str(&collapsed);
+        if text.ends_with(char::is_whitespace) {
+            self.space();
+        }
+    }
+
+    fn space(&mut self) {
+        if !self.current.is_empty() && !self.current.ends_with([' ', '\n']) {
+            self.current.push(' ');
+        }
+    }
+
+    fn newline(&mut self) {
+        let trimmed = self.current.trim_end_matches(' ').len();
+        self.current.truncate(trimmed);
+        if !self.current.is_empty() && !self.current.ends_with('\n') {
+            self.current.push('\n');
+        }
+    }
+
+    fn paragraph_break(&mut self) {
+        self.newline();
+        if !self.current.is_empty() && !self.current.ends_with("\n\n") {
+            self.current.push('\n');
+        }
+    }
+
+    fn record_link(&mut self, href: &str, text: &str) {
+        let href = href.trim();
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return;
+        }
+        if self.seen_links.insert(href.to_string()) {
+            self.links.push(json!({ "href": href, "text": collapse_whitespace(text) }));
+        }
+    }
+}
+
+/// `<title>`, named `<meta>` tags, `<html lang>` and outbound links; `None` if all are absent
+fn document_metadata(html: &Html, links: Vec<Value>) -> Option<Value> {
+    let mut metadata = Map::new();
+
+    let title = Selector::parse("title").expect("valid selector");
+    if let Some(title) = html
+        .select(&title)
+        .map(|t| collapse_whitespace(&t.text().collect::<String>()))
+        .find(|t| !t.is_empty())
+    {
+        metadata.insert("title".to_string(), Value::String(title));
+    }
+
+    if let Some(lang) = html.root_element().value().attr("lang") {
+        metadata.insert("lang".to_string(), Value::String(lang.to_string()));
+    }
+
+    let meta = Selector::parse("meta[content]").expect("valid selector");
+    let mut tags = Map::new();
+    for element in html.select(&meta) {
+        let value = element.value();
+        let Some(key) = value.attr("name").or_else(|| value.attr("property")) else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let content = value.attr("content").unwrap_or("").trim();
+        if key.is_empty() || key == "viewport" || content.is_empty() {
+            continue;
+        }
+        tags.entry(key).or_insert_with(|| Value::String(content.to_string()));
+    }
+    if !tags.is_empty() {
+        metadata.insert("meta".to_string(), Value::Object(tags));
+    }
+
+    if !links.is_empty() {
+        metadata.insert("links".to_string(), Value::Array(links));
+    }
+
+    (!metadata.is_empty()).then_some(Value::Object(metadata))
+}
+
+/// Language of a code block from `class="language-x"` / `lang-x` on `<pre>` or its `<code>`
+fn code_language(pre: ElementRef) -> String {
+    let code = pre.children().filter_map(ElementRef::wrap).find(|c| c.value().name() == "code");
+    [Some(pre), code]
+        .into_iter()
+        .flatten()
+        .flat_map(|e| e.value().classes())
+        .find_map(|c| c.strip_prefix("language-").or_else(|| c.strip_prefix("lang-")))
+        .unwrap_or("")
+        .to_string()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Trim trailing spaces and collapse blank-line runs outside code fences
+fn normalize_body(body: &str) -> String {
+    let mut out = Vec::new();
+    let mut in_fence = false;
+    let mut blank = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let line = if in_fence { line.trim_end() } else { line.trim() };
+        if line.is_empty() && !in_fence {
+            blank = true;
+            continue;
+        }
+        if blank && !out.is_empty() {
+            out.push("");
+        }
+        blank = false;
+        out.push(line);
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <title>Gateway Guide</title>
+  <meta name="description" content="How to run the gateway">
+  <meta property="og:type" content="article">
+  <script>var tracking = 1;</script>
+</head>
+<body>
+  <nav><a href="/home">Home</a> | <a href="/docs">Docs</a></nav>
+  <h1>Gateway</h1>
+  <p>The gateway routes   requests to
+     <a href="https://example.com/backends">backends</a>.</p>
+  <h2>Configuration</h2>
+  <table>
+    <tr><th>Key</th><th>Default</th></tr>
+    <tr><td>timeout</td><td>30s</td></tr>
+  </table>
+  <pre><code class="language-toml">[gateway]
+timeout = "30s"</code></pre>
+  <h3>Retries</h3>
+  <ul><li>Use <code>retry_max</code></li><li>Back off exponentially</li></ul>
+  <h2>Monitoring</h2>
+  <p>See <a href="metrics.html">metrics</a>.</p>
+  <footer>Copyright 2024</footer>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_sections_follow_heading_hierarchy() {
+        let doc = HtmlParser.parse(PAGE, "docs/gateway.html").unwrap();
+        let headers: Vec<&str> = doc.sections.iter().map(|s| s.header.as_str()).collect();
+
+        assert_eq!(doc.doc_type, "html");
+        assert_eq!(
+            headers,
+            vec![
+                "Gateway",
+                "Gateway > Configuration",
+                "Gateway > Configuration > Retries",
+                "Gateway > Monitoring",
+            ]
+        );
+        assert_eq!(doc.sections[1].section_type.as_deref(), Some("h2"));
+        assert_eq!(doc.sections[0].content, "The gateway routes requests to backends.");
+        assert!(doc.sections[2].content.contains("- Use `retry_max`\n- Back off exponentially"));
+        assert!(doc.content.starts_with("# Gateway\n"));
+    }
+
+    #[test]
+    fn test_boilerplate_removed_and_blocks_readable() {
+        let doc = HtmlParser.parse(PAGE, "docs/gateway.html").unwrap();
+
+        assert!(!doc.content.contains("tracking"));
+        assert!(!doc.content.contains("Home"));
+        assert!(!doc.content.contains("Copyright"));
+
+        let config = &doc.sections[1].content;
+        assert!(config.contains("| Key | Default |\n| --- | --- |\n| timeout | 30s |"));
+        assert!(config.contains("```toml\n[gateway]\ntimeout = \"30s\"\n```"));
+    }
+
+    #[test]
+    fn test_metadata_and_links() {
+        let doc = HtmlParser.parse(PAGE, "docs/gateway.html").unwrap();
+        let metadata = doc.metadata.unwrap();
+
+        assert_eq!(metadata["title"], "Gateway Guide");
+        assert_eq!(metadata["lang"], "en");
+        assert_eq!(metadata["meta"]["description"], "How to run the gateway");
+        assert_eq!(metadata["meta"]["og:type"], "article");
+
+        let links: Vec<&str> = metadata["links"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["href"].as_str().unwrap())
+            .collect();
+        assert_eq!(links, vec!["https://example.com/backends", "metrics.html"]);
+    }
+
+    #[test]
+    fn test_text_without_headings() {
+        let doc = HtmlParser.parse("<p>Just a note.</p>", "note.html").unwrap();
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].header, "content");
+        assert!(doc.metadata.is_none());
+        assert!(HtmlParser.parse("<script>x()</script>", "empty.html").is_err());
+    }
+}
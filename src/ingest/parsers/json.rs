@@ -26,6 +26,7 @@ impl Parser for JsonParser {
                         header: format!("Definition: {}", name),
                         content,
                         section_type: Some("definition".to_string()),
+                        page: None,
                     });
                 }
             }
@@ -38,6 +39,7 @@ impl Parser for JsonParser {
                         header: format!("Property: {}", name),
                         content,
                         section_type: Some("property".to_string()),
+                        page: None,
                     });
                 }
             }
@@ -49,6 +51,7 @@ impl Parser for JsonParser {
                         header: key.clone(),
                         content: json_value_to_text(value),
                         section_type: Some(key.clone()),
+                        page: None,
                     });
                 }
             }
@@ -58,6 +61,7 @@ impl Parser for JsonParser {
                 header: "content".to_string(),
                 content: json_value_to_text(&json_value),
                 section_type: None,
+                page: None,
             });
         }
         
@@ -67,6 +71,7 @@ impl Parser for JsonParser {
                 header: "root".to_string(),
                 content: content.to_string(),
                 section_type: None,
+                page: None,
             });
         }
         
@@ -74,6 +79,7 @@ impl Parser for JsonParser {
             content: content.to_string(),
             sections,
             doc_type: "json_schema".to_string(),
+            metadata: None,
         })
     }
 }
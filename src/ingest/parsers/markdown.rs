@@ -1,182 +1,149 @@
 use super::{Parser, ParsedDocument, Section};
 use crate::error::Result;
-use pulldown_cmark::{Parser as CmarkParser, Event, Tag, CodeBlockKind, TagEnd};
+use pulldown_cmark::{Event, MetadataBlockKind, Options, Parser as CmarkParser, Tag, TagEnd};
+use serde_json::Value;
 
 /// Markdown parser for README files and documentation
+///
+/// Sections start at every heading and are headed by the heading breadcrumb
+/// (`Guide > Install > Linux`). Section content is the Markdown source between two
+/// headings, so lists, tables, line breaks and code fences keep their structure in
+/// chunk text. YAML frontmatter is not indexed as text but returned as document
+/// metadata (`title`, `tags`, `owner`, `status`, ...), which search can filter on.
 pub struct MarkdownParser;
 
 impl Parser for MarkdownParser {
     fn can_parse(&self, extension: &str) -> bool {
         extension == "md"
     }
-    
-    fn parse(&self, content: &str, _path: &str) -> Result<ParsedDocument> {
-        let parser = CmarkParser::new(content);
-        let mut sections = Vec::new();
-        let mut current_section: Option<(String, String, usize)> = None; // (header, content, level)
-        let mut current_content = String::new();
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+
         let mut frontmatter: Option<String> = None;
-        let mut in_frontmatter = false;
-        
-        // Check for YAML frontmatter
-        if content.starts_with("---\n") {
-            if let Some(end) = content[4..].find("---\n") {
-                frontmatter = Some(content[4..end + 4].to_string());
-                in_frontmatter = true;
-            }
-        }
-        
-        let events = parser.into_iter();
-        let mut skip_until_header = in_frontmatter;
-        
-        for event in events {
+        let mut body_start = 0;
+        // (level, text, start of heading, end of heading) in source order
+        let mut headings: Vec<(usize, String, usize, usize)> = Vec::new();
+        let mut in_metadata = false;
+        let mut heading: Option<(usize, String, usize)> = None;
+
+        for (event, range) in CmarkParser::new_ext(content, options).into_offset_iter() {
             match event {
-                Event::Start(Tag::Heading { level, .. }) => {
-                    // Save previous section if exists
-                    if let Some((header, content, _)) = current_section.take() {
-                        if !content.trim().is_empty() {
-                            sections.push(Section {
-                                header: header.clone(),
-                                content: content.trim().to_string(),
-                                section_type: Some(format!("h{}", level as u32)),
-                            });
-                        }
-                    }
-                    
-                    // Start new section
-                    current_content.clear();
-                    current_section = Some((String::new(), String::new(), level as usize));
-                    skip_until_header = false;
-                }
-                Event::Text(text) => {
-                    if skip_until_header {
-                        continue;
-                    }
-                    
-                    if let Some((ref mut header, ref mut content, _)) = current_section {
-                        if header.is_empty() {
-                            // This text is the header
-                            *header = text.to_string();
-                        } else {
-                            // This text is content
-                            content.push_str(&text);
-                            content.push(' ');
-                        }
-                    } else {
-                        // No current section, add to content
-                        current_content.push_str(&text);
-                        current_content.push(' ');
-                    }
+                Event::Start(Tag::MetadataBlock(MetadataBlockKind::YamlStyle)) => in_metadata = true,
+                Event::End(TagEnd::MetadataBlock(_)) => {
+                    in_metadata = false;
+                    body_start = range.end;
                 }
-                Event::Code(code) => {
-                    if skip_until_header {
-                        continue;
-                    }
-                    
-                    if let Some((_, ref mut content, _)) = current_section {
-                        content.push_str("```");
-                        content.push_str(&code);
-                        content.push_str("``` ");
-                    } else {
-                        current_content.push_str("```");
-                        current_content.push_str(&code);
-                        current_content.push_str("``` ");
-                    }
+                Event::Text(text) if in_metadata => {
+                    frontmatter.get_or_insert_with(String::new).push_str(&text);
                 }
-                Event::Start(Tag::CodeBlock(kind)) => {
-                    if skip_until_header {
-                        continue;
-                    }
-                    
-                    let lang = match kind {
-                        CodeBlockKind::Fenced(lang) => lang.to_string(),
-                        CodeBlockKind::Indented => String::new(),
-                    };
-                    
-                    if let Some((_, ref mut content, _)) = current_section {
-                        content.push_str(&format!("```{}\n", lang));
-                    } else {
-                        current_content.push_str(&format!("```{}\n", lang));
-                    }
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((level as usize, String::new(), range.start));
                 }
-                Event::End(TagEnd::CodeBlock) => {
-                    if skip_until_header {
-                        continue;
-                    }
-                    
-                    if let Some((_, ref mut content, _)) = current_section {
-                        content.push_str("```\n");
-                    } else {
-                        current_content.push_str("```\n");
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some((_, ref mut title, _)) = heading {
+                        title.push_str(&text);
                     }
                 }
                 Event::SoftBreak | Event::HardBreak => {
-                    if skip_until_header {
-                        continue;
+                    if let Some((_, ref mut title, _)) = heading {
+                        title.push(' ');
                     }
-                    
-                    if let Some((_, ref mut content, _)) = current_section {
-                        content.push('\n');
-                    } else {
-                        current_content.push('\n');
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, title, start)) = heading.take() {
+                        headings.push((level, title.trim().to_string(), start, range.end));
                     }
                 }
                 _ => {}
             }
         }
-        
-        // Save final section
-        if let Some((header, content, _)) = current_section.take() {
-            if !content.trim().is_empty() {
-                sections.push(Section {
-                    header: header.clone(),
-                    content: content.trim().to_string(),
-                    section_type: Some("markdown".to_string()),
-                });
+
+        let mut sections = Vec::new();
+        let preamble_end = headings.first().map_or(content.len(), |h| h.2);
+        let preamble = content[body_start.min(preamble_end)..preamble_end].trim();
+        if !preamble.is_empty() {
+            sections.push(Section {
+                header: "content".to_string(),
+                content: preamble.to_string(),
+                section_type: None,
+                page: None,
+            });
+        }
+
+        let mut breadcrumb: Vec<(usize, String)> = Vec::new();
+        for (idx, (level, title, _, end)) in headings.iter().enumerate() {
+            breadcrumb.retain(|(l, _)| l < level);
+            breadcrumb.push((*level, title.clone()));
+
+            let next_start = headings.get(idx + 1).map_or(content.len(), |h| h.2);
+            let body = content[*end..next_start].trim();
+            if body.is_empty() {
+                continue;
             }
+            sections.push(Section {
+                header: breadcrumb.iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>().join(" > "),
+                content: body.to_string(),
+                section_type: Some(format!("h{}", level)),
+                page: None,
+            });
         }
-        
+
         // If no sections were created, create one with full content
         if sections.is_empty() {
             sections.push(Section {
                 header: "content".to_string(),
-                content: if !current_content.is_empty() {
-                    current_content.trim().to_string()
-                } else {
-                    content.to_string()
-                },
+                content: content.to_string(),
                 section_type: None,
+                page: None,
             });
         }
-        
-        // Add frontmatter as first section if present
-        if let Some(fm) = frontmatter {
-            sections.insert(0, Section {
-                header: "frontmatter".to_string(),
-                content: fm,
-                section_type: Some("frontmatter".to_string()),
-            });
-        }
-        
+
         Ok(ParsedDocument {
             content: content.to_string(),
             sections,
             doc_type: "markdown".to_string(),
+            metadata: frontmatter.and_then(|yaml| frontmatter_metadata(&yaml, path)),
         })
     }
 }
 
+/// Frontmatter mapping as JSON metadata; a comma-separated `tags` string becomes a list
+fn frontmatter_metadata(yaml: &str, path: &str) -> Option<Value> {
+    let mut metadata = match serde_yaml_ng::from_str::<Value>(yaml) {
+        Ok(Value::Object(map)) if !map.is_empty() => map,
+        Ok(_) => return None,
+        Err(e) => {
+            log::warn!("{}: frontmatter ignored ({})", path, e);
+            return None;
+        }
+    };
+    if let Some(Value::String(tags)) = metadata.get("tags") {
+        let tags: Vec<Value> = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| Value::String(t.to_string()))
+            .collect();
+        metadata.insert("tags".to_string(), Value::Array(tags));
+    }
+    Some(Value::Object(metadata))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_markdown_parser_can_parse() {
         let parser = MarkdownParser;
         assert!(parser.can_parse("md"));
         assert!(!parser.can_parse("txt"));
     }
-    
+
     #[test]
     fn test_markdown_parser_simple() {
         let parser = MarkdownParser;
@@ -189,14 +156,48 @@ This is content.
 
 More content.
 "#;
-        
+
         let result = parser.parse(content, "test.md").unwrap();
         assert_eq!(result.doc_type, "markdown");
         assert!(result.sections.len() >= 2);
-        
+
         let title_section = result.sections.iter()
             .find(|s| s.header == "Title")
             .unwrap();
         assert!(title_section.content.contains("This is content"));
     }
+
+    #[test]
+    fn test_markdown_breadcrumbs_and_structure() {
+        let content = "# Guide\n\n## Install\n\nSteps:\n\n- download\n- unpack\n\n\
+### Linux\n\n| distro | package |\n| --- | --- |\n| debian | deb |\n\n\
+```sh\n# not a heading\napt install ragmcp\n```\n\n## Usage\n\nRun it.\n";
+        let doc = MarkdownParser.parse(content, "guide.md").unwrap();
+
+        let headers: Vec<&str> = doc.sections.iter().map(|s| s.header.as_str()).collect();
+        assert_eq!(headers, vec!["Guide > Install", "Guide > Install > Linux", "Guide > Usage"]);
+        assert_eq!(doc.sections[0].content, "Steps:\n\n- download\n- unpack");
+        assert_eq!(doc.sections[1].section_type.as_deref(), Some("h3"));
+        assert!(doc.sections[1].content.starts_with("| distro | package |\n| --- | --- |\n"));
+        assert!(doc.sections[1].content.ends_with("```sh\n# not a heading\napt install ragmcp\n```"));
+        assert!(doc.metadata.is_none());
+    }
+
+    #[test]
+    fn test_markdown_frontmatter_metadata() {
+        let content = "---\ntitle: Failover Runbook\ntags: networking, dr\nowner: sre\nstatus: published\n---\n\
+Intro paragraph.\n\n# Failover\n\nSwitch traffic.\n";
+        let doc = MarkdownParser.parse(content, "runbooks/failover.md").unwrap();
+
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "content");
+        assert_eq!(doc.sections[0].content, "Intro paragraph.");
+        assert!(doc.sections.iter().all(|s| !s.content.contains("owner:")));
+
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata["title"], "Failover Runbook");
+        assert_eq!(metadata["tags"], serde_json::json!(["networking", "dr"]));
+        assert_eq!(metadata["owner"], "sre");
+        assert_eq!(metadata["status"], "published");
+    }
 }
@@ -3,6 +3,21 @@ pub mod yaml;
 pub mod json;
 pub mod markdown;
 pub mod plaintext;
+pub mod pdf;
+pub mod html;
+pub mod code;
+pub mod toml;
+pub mod rst;
+pub mod asciidoc;
+pub mod docx;
+pub mod odt;
+pub mod pptx;
+pub mod xlsx;
+pub mod notebook;
+pub mod csv;
+pub mod email;
+mod section_builder;
+mod office;
 
 use crate::error::Result;
 
@@ -12,6 +27,8 @@ pub struct ParsedDocument {
     pub content: String,
     pub sections: Vec<Section>,
     pub doc_type: String,
+    /// Document-level metadata (e.g. HTML title, meta tags, links), stored as `metadata_json`
+    pub metadata: Option<serde_json::Value>,
 }
 
 /// A section within a document
@@ -20,6 +37,8 @@ pub struct Section {
     pub header: String,
     pub content: String,
     pub section_type: Option<String>,
+    /// 1-based page number for paginated formats (PDF), kept on chunks for citations
+    pub page: Option<u32>,
 }
 
 /// Trait for document parsers
@@ -29,6 +48,31 @@ pub trait Parser {
     
     /// Parse document content into structured sections
     fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument>;
+    
+    /// Whether the format is binary and must be parsed with `parse_bytes`
+    fn is_binary(&self) -> bool {
+        false
+    }
+    
+    /// Parse raw file bytes; text formats decode UTF-8 and delegate to `parse`
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let content = std::str::from_utf8(bytes).map_err(|e| {
+            crate::error::RagmcpError::Parse(format!("{} is not valid UTF-8: {}", path, e))
+        })?;
+        self.parse(content, path)
+    }
+    
+    /// Whether one file can hold several documents (e.g. an mbox) and must be parsed
+    /// with `parse_documents`
+    fn is_multi_document(&self) -> bool {
+        false
+    }
+    
+    /// Parse raw file bytes into `(doc_path, document)` pairs; single-document formats
+    /// return the file itself under `path`
+    fn parse_documents(&self, bytes: &[u8], path: &str) -> Result<Vec<(String, ParsedDocument)>> {
+        Ok(vec![(path.to_string(), self.parse_bytes(bytes, path)?)])
+    }
 }
 
 /// Parser registry that selects appropriate parser by extension
@@ -37,8 +81,13 @@ pub struct ParserRegistry {
 }
 
 impl ParserRegistry {
-    /// Create a new parser registry with all built-in parsers
+    /// Create a new parser registry with all built-in parsers and default settings
     pub fn new() -> Self {
+        Self::with_config(&crate::config::IngestConfig::default())
+    }
+
+    /// Create a parser registry with all built-in parsers configured from `[ingest]`
+    pub fn with_config(config: &crate::config::IngestConfig) -> Self {
         let mut registry = Self {
             parsers: Vec::new(),
         };
@@ -47,6 +96,20 @@ impl ParserRegistry {
         registry.register(Box::new(yaml::YamlParser));
         registry.register(Box::new(json::JsonParser));
         registry.register(Box::new(markdown::MarkdownParser));
+        registry.register(Box::new(pdf::PdfParser));
+        registry.register(Box::new(html::HtmlParser));
+        registry.register(Box::new(code::CodeParser));
+        registry.register(Box::new(toml::TomlParser));
+        registry.register(Box::new(rst::RstParser));
+        registry.register(Box::new(asciidoc::AsciiDocParser));
+        registry.register(Box::new(docx::DocxParser));
+        registry.register(Box::new(odt::OdtParser));
+        registry.register(Box::new(xlsx::XlsxParser));
+        registry.register(Box::new(pptx::PptxParser));
+        registry.register(Box::new(notebook::NotebookParser));
+        registry.register(Box::new(csv::CsvParser::new(&config.csv)));
+        registry.register(Box::new(email::EmailParser));
+        registry.register(Box::new(plaintext::TextParser));
         
         registry
     }
@@ -92,6 +155,37 @@ impl ParserRegistry {
             }
         }
     }
+    
+    /// Parse raw file bytes using the appropriate parser for the extension
+    /// 
+    /// Binary formats are parsed from the bytes directly and have no plain text
+    /// fallback; text formats are decoded as UTF-8 and go through `parse`.
+    pub fn parse_file(&self, bytes: &[u8], path: &str, extension: &str) -> Result<ParsedDocument> {
+        let parser = self.find_parser(extension)
+            .ok_or_else(|| crate::error::RagmcpError::Parse(
+                format!("No parser found for extension: {}", extension)
+            ))?;
+        
+        if parser.is_binary() {
+            return parser.parse_bytes(bytes, path);
+        }
+        
+        let content = std::str::from_utf8(bytes).map_err(|e| {
+            crate::error::RagmcpError::Parse(format!("{} is not valid UTF-8: {}", path, e))
+        })?;
+        self.parse(content, path, extension)
+    }
+    
+    /// Parse raw file bytes into the documents the file holds as `(doc_path, document)`
+    /// 
+    /// Mailboxes yield one document per message (`inbox.mbox#1`, ...); every other
+    /// format yields a single document under `path` parsed with `parse_file`.
+    pub fn parse_documents(&self, bytes: &[u8], path: &str, extension: &str) -> Result<Vec<(String, ParsedDocument)>> {
+        match self.find_parser(extension) {
+            Some(parser) if parser.is_multi_document() => parser.parse_documents(bytes, path),
+            _ => Ok(vec![(path.to_string(), self.parse_file(bytes, path, extension)?)]),
+        }
+    }
 }
 
 impl Default for ParserRegistry {
@@ -113,7 +207,26 @@ mod tests {
         assert!(registry.find_parser("yml").is_some());
         assert!(registry.find_parser("json").is_some());
         assert!(registry.find_parser("md").is_some());
-        assert!(registry.find_parser("txt").is_none());
+        for ext in ["pdf", "docx", "odt", "xlsx", "pptx"] {
+            assert!(registry.find_parser(ext).is_some_and(|p| p.is_binary()), "no binary parser for {}", ext);
+        }
+        assert!(registry.find_parser("html").is_some());
+        assert!(registry.find_parser("htm").is_some());
+        for ext in ["rs", "py", "ts", "js", "go"] {
+            assert!(registry.find_parser(ext).is_some(), "no parser for {}", ext);
+        }
+        assert!(registry.find_parser("toml").is_some());
+        assert!(registry.find_parser("rst").is_some());
+        assert!(registry.find_parser("adoc").is_some());
+        assert!(registry.find_parser("asciidoc").is_some());
+        assert!(registry.find_parser("ipynb").is_some_and(|p| !p.is_binary()));
+        assert!(registry.find_parser("csv").is_some());
+        assert!(registry.find_parser("tsv").is_some());
+        for ext in ["eml", "mbox"] {
+            assert!(registry.find_parser(ext).is_some_and(|p| p.is_multi_document()), "no mail parser for {}", ext);
+        }
+        assert!(registry.find_parser("txt").is_some());
+        assert!(registry.find_parser("png").is_none());
     }
     
     #[test]
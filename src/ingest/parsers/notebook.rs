@@ -0,0 +1,205 @@
+use super::section_builder::{document, SectionBuilder};
+use super::{Parser, ParsedDocument};
+use crate::error::{Result, RagmcpError};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+/// Text outputs of a code cell longer than this are left out
+const MAX_OUTPUT_CHARS: usize = 1000;
+
+/// Jupyter notebook (`.ipynb`) parser
+///
+/// Markdown cells are split into sections at their `#` headings; code cells are kept
+/// as fenced code in the notebook's kernel language. Small text outputs (stream
+/// output, `text/plain` results, error names) follow their cell as an `Output:` block;
+/// larger outputs and rich media such as base64 images and HTML are dropped.
+pub struct NotebookParser;
+
+impl Parser for NotebookParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "ipynb"
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        let notebook: Value = serde_json::from_str(content)
+            .map_err(|e| RagmcpError::Parse(format!("Invalid notebook {}: {}", path, e)))?;
+        let cells = notebook
+            .get("cells")
+            .and_then(Value::as_array)
+            .ok_or_else(|| RagmcpError::Parse(format!("{} has no cells (nbformat 4 required)", path)))?;
+
+        let metadata = &notebook["metadata"];
+        let language = metadata["language_info"]["name"]
+            .as_str()
+            .or_else(|| metadata["kernelspec"]["language"].as_str())
+            .unwrap_or("")
+            .to_string();
+
+        let mut builder = SectionBuilder::default();
+        for cell in cells {
+            let source = text_of(&cell["source"]);
+            match cell["cell_type"].as_str() {
+                Some("markdown") => push_markdown(&mut builder, &source),
+                Some("code") if !source.trim().is_empty() => {
+                    let lines: Vec<&str> = source.lines().collect();
+                    builder.push_code(&language, &lines);
+                    if let Some(output) = cell_output(cell) {
+                        builder.push_paragraph(&format!("Output:\n{}", output));
+                    }
+                }
+                // Raw cells and empty code cells carry no searchable text
+                _ => {}
+            }
+        }
+
+        let mut properties = serde_json::Map::new();
+        if let Some(title) = builder.first_heading.clone() {
+            properties.insert("title".to_string(), json!(title));
+        }
+        if let Some(kernel) = metadata["kernelspec"]["name"].as_str() {
+            properties.insert("kernel".to_string(), json!(kernel));
+        }
+        if !language.is_empty() {
+            properties.insert("language".to_string(), json!(language));
+        }
+        let properties = (!properties.is_empty()).then_some(Value::Object(properties));
+
+        document(builder.finish(""), path, "ipynb", properties)
+    }
+}
+
+/// Notebook text fields are either a string or a list of lines
+fn text_of(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Push markdown source, starting a section at every ATX heading outside code fences
+fn push_markdown(builder: &mut SectionBuilder, source: &str) {
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    let heading_re = HEADING.get_or_init(|| Regex::new(r"^(#{1,6})\s+(.+?)\s*#*\s*$").expect("Invalid regex pattern"));
+
+    let mut in_fence = false;
+    builder.push_line("");
+    for line in source.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        match heading_re.captures(line).filter(|_| !in_fence) {
+            Some(c) => builder.start_section(c[2].to_string(), c[1].len()),
+            None => builder.push_line(line),
+        }
+    }
+    builder.push_line("");
+}
+
+/// Text outputs of a code cell, `None` if there are none or they are too large
+fn cell_output(cell: &Value) -> Option<String> {
+    let outputs = cell["outputs"].as_array()?;
+    let mut text = String::new();
+    for output in outputs {
+        let part = match output["output_type"].as_str() {
+            Some("stream") => text_of(&output["text"]),
+            Some("execute_result" | "display_data") => text_of(&output["data"]["text/plain"]),
+            Some("error") => format!(
+                "{}: {}",
+                output["ename"].as_str().unwrap_or("Error"),
+                output["evalue"].as_str().unwrap_or("")
+            ),
+            _ => String::new(),
+        };
+        text.push_str(&strip_ansi(&part));
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    let text = text.trim_end();
+    (!text.is_empty() && text.chars().count() <= MAX_OUTPUT_CHARS).then(|| text.to_string())
+}
+
+/// Remove terminal color codes from tracebacks and logs
+fn strip_ansi(text: &str) -> String {
+    static ANSI: OnceLock<Regex> = OnceLock::new();
+    let ansi_re = ANSI.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("Invalid regex pattern"));
+    ansi_re.replace_all(text, "").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notebook() -> String {
+        json!({
+            "nbformat": 4,
+            "metadata": {
+                "kernelspec": { "name": "python3", "language": "python" },
+                "language_info": { "name": "python" }
+            },
+            "cells": [
+                { "cell_type": "markdown", "source": ["# Latency Runbook\n", "Check p95 first.\n"] },
+                {
+                    "cell_type": "code",
+                    "source": "df = load()\ndf.p95.max()",
+                    "outputs": [
+                        { "output_type": "stream", "name": "stdout", "text": ["loaded 42 rows\n"] },
+                        { "output_type": "execute_result", "data": { "text/plain": "812.5" } }
+                    ]
+                },
+                { "cell_type": "markdown", "source": "## Plot\n```\n# not a heading\n```" },
+                {
+                    "cell_type": "code",
+                    "source": "df.plot()",
+                    "outputs": [
+                        { "output_type": "display_data", "data": { "image/png": "iVBORw0KGgoAAAANSUhEUg==" } },
+                        { "output_type": "error", "ename": "\u{1b}[31mKeyError", "evalue": "'p99'" }
+                    ]
+                },
+                { "cell_type": "code", "source": "print(big)", "outputs": [
+                    { "output_type": "stream", "text": "x".repeat(MAX_OUTPUT_CHARS + 1) }
+                ] }
+            ]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_notebook_sections_and_code() {
+        let doc = NotebookParser.parse(&notebook(), "runbooks/latency.ipynb").unwrap();
+
+        assert_eq!(doc.doc_type, "ipynb");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Latency Runbook");
+        assert_eq!(doc.sections[0].section_type.as_deref(), Some("h1"));
+        assert_eq!(
+            doc.sections[0].content,
+            "Check p95 first.\n\n```python\ndf = load()\ndf.p95.max()\n```\n\nOutput:\nloaded 42 rows\n812.5"
+        );
+        assert_eq!(doc.sections[1].header, "Plot");
+        assert!(doc.sections[1].content.contains("# not a heading"));
+
+        let metadata = doc.metadata.unwrap();
+        assert_eq!(metadata["title"], "Latency Runbook");
+        assert_eq!(metadata["kernel"], "python3");
+    }
+
+    #[test]
+    fn test_notebook_drops_images_and_large_outputs() {
+        let doc = NotebookParser.parse(&notebook(), "runbooks/latency.ipynb").unwrap();
+        let plot = &doc.sections[1].content;
+
+        assert!(plot.contains("Output:\nKeyError: 'p99'"));
+        assert!(!doc.content.contains("iVBORw0KGgo"));
+        assert!(!doc.content.contains("xxxx"));
+        assert!(plot.ends_with("```python\nprint(big)\n```"));
+    }
+
+    #[test]
+    fn test_invalid_notebook_is_error() {
+        assert!(NotebookParser.parse("{\"cells\": 3}", "bad.ipynb").is_err());
+        assert!(NotebookParser.parse("not json", "bad.ipynb").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use super::office::{attr, document_properties, for_each_event, Package, TextFlow, XmlEvent};
+use super::section_builder::document;
+use super::{Parser, ParsedDocument};
+use crate::error::{Result, RagmcpError};
+
+/// Elements whose text is not part of the document body
+const SKIPPED_ELEMENTS: &[&str] = &["annotation", "tracked-changes", "note-citation", "sequence-decls"];
+
+/// Most spaces one `text:s` expands to; longer runs only matter for layout, and the
+/// count comes from the file
+const MAX_SPACE_RUN: usize = 64;
+
+/// OpenDocument text (ODT) parser
+///
+/// Reads `content.xml`: `text:h` headings become sections at their outline level,
+/// paragraphs, list items (`- item`) and tables (`| a | b |` rows) form the section
+/// content. Title and author from `meta.xml` are kept as metadata.
+pub struct OdtParser;
+
+impl Parser for OdtParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "odt"
+    }
+
+    fn parse(&self, _content: &str, path: &str) -> Result<ParsedDocument> {
+        Err(RagmcpError::Parse(format!("{} is an OpenDocument file and must be parsed from bytes", path)))
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let mut package = Package::open(bytes, path)?;
+        let xml = package.read_required("content.xml")?;
+
+        let mut flow = TextFlow::default();
+        // Depth inside paragraphs/headings; text outside them is formatting whitespace
+        let mut in_paragraph = 0usize;
+        let mut skipped = 0usize;
+        let mut list_depth = 0usize;
+        for_each_event(&xml, |event| match event {
+            XmlEvent::Start(name, _) if skipped > 0 || SKIPPED_ELEMENTS.contains(&name.as_str()) => {
+                skipped += 1;
+            }
+            XmlEvent::End(_) if skipped > 0 => skipped -= 1,
+            XmlEvent::Text(_) if skipped > 0 => {}
+            XmlEvent::Start(name, attrs) => match name.as_str() {
+                "h" => {
+                    in_paragraph += 1;
+                    let level = attr(&attrs, "text:outline-level").and_then(|l| l.parse().ok());
+                    flow.set_heading(level.unwrap_or(1));
+                }
+                "p" => {
+                    in_paragraph += 1;
+                    if list_depth > 0 {
+                        flow.set_list_item();
+                    }
+                }
+                "s" => {
+                    let count: usize = attr(&attrs, "text:c").and_then(|c| c.parse().ok()).unwrap_or(1);
+                    flow.text(&" ".repeat(count.min(MAX_SPACE_RUN)));
+                }
+                "tab" => flow.text("\t"),
+                "line-break" => flow.text("\n"),
+                "list-item" => list_depth += 1,
+                "table" => flow.start_table(),
+                _ => {}
+            },
+            XmlEvent::Text(text) if in_paragraph > 0 => flow.text(&text),
+            XmlEvent::End(name) => match name.as_str() {
+                "h" | "p" => {
+                    in_paragraph = in_paragraph.saturating_sub(1);
+                    flow.end_paragraph();
+                }
+                "list-item" => list_depth = list_depth.saturating_sub(1),
+                "table-cell" => flow.end_cell(),
+                "table-row" => flow.end_row(),
+                "table" => flow.end_table(),
+                _ => {}
+            },
+            _ => {}
+        })?;
+
+        let metadata = document_properties(&mut package, "meta.xml")?;
+        document(flow.builder.finish(""), path, "odt", metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::parsers::office::build_package;
+
+    const CONTENT: &str = r#"<office:document-content xmlns:office="o" xmlns:text="t" xmlns:table="tb"><office:body><office:text>
+<text:h text:outline-level="1">Meeting Notes</text:h>
+<text:p>Attendees:<text:s text:c="2"/>Ana, Bo<office:annotation><text:p>draft</text:p></office:annotation></text:p>
+<text:list><text:list-item><text:p>Ship <text:span>v2</text:span></text:p></text:list-item></text:list>
+<text:h text:outline-level="2">Decisions</text:h>
+<table:table><table:table-row><table:table-cell><text:p>Owner</text:p></table:table-cell><table:table-cell><text:p>Bo</text:p></table:table-cell></table:table-row></table:table>
+</office:text></office:body></office:document-content>"#;
+
+    #[test]
+    fn test_odt_headings_lists_and_tables() {
+        let bytes = build_package(&[
+            ("content.xml", CONTENT),
+            ("meta.xml", r#"<office:document-meta xmlns:office="o" xmlns:dc="d"><office:meta><dc:title>Weekly</dc:title></office:meta></office:document-meta>"#),
+        ]);
+        let doc = OdtParser.parse_bytes(&bytes, "notes.odt").unwrap();
+
+        assert_eq!(doc.doc_type, "odt");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Meeting Notes");
+        assert_eq!(doc.sections[0].content, "Attendees:  Ana, Bo\n\n- Ship v2");
+        assert_eq!(doc.sections[1].header, "Decisions");
+        assert_eq!(doc.sections[1].section_type.as_deref(), Some("h2"));
+        assert_eq!(doc.sections[1].content, "| Owner | Bo |");
+        assert_eq!(doc.metadata.unwrap()["title"], "Weekly");
+    }
+
+    #[test]
+    fn test_odt_space_runs_are_clamped() {
+        let content = CONTENT.replace(r#"text:c="2""#, r#"text:c="4000000000""#);
+        let bytes = build_package(&[("content.xml", content.as_str())]);
+        let doc = OdtParser.parse_bytes(&bytes, "notes.odt").unwrap();
+
+        assert!(doc.sections[0].content.starts_with(&format!("Attendees:{}Ana", " ".repeat(MAX_SPACE_RUN))));
+    }
+}
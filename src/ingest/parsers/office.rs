@@ -0,0 +1,341 @@
+//! Shared helpers for zipped XML document formats (DOCX, ODT, XLSX, PPTX)
+
+use super::section_builder::SectionBuilder;
+use crate::error::{Result, RagmcpError};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+/// Largest uncompressed part we read from a package (guards against zip bombs)
+const MAX_PART_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Signature of OLE compound files: legacy .doc/.xls/.ppt and password-protected OOXML
+const CFB_MAGIC: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// An opened zip package
+pub(super) struct Package<'a> {
+    archive: ZipArchive<Cursor<&'a [u8]>>,
+    path: String,
+}
+
+impl<'a> Package<'a> {
+    pub(super) fn open(bytes: &'a [u8], path: &str) -> Result<Self> {
+        if bytes.starts_with(CFB_MAGIC) {
+            return Err(RagmcpError::Skipped(format!(
+                "{} is encrypted or a legacy binary Office file",
+                path
+            )));
+        }
+        let archive = ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| RagmcpError::Parse(format!("Invalid document package {}: {}", path, e)))?;
+        Ok(Self { archive, path: path.to_string() })
+    }
+
+    /// Raw bytes of a part, `None` if the package does not contain it
+    pub(super) fn read(&mut self, name: &str) -> Result<Option<Vec<u8>>> {
+        let file = match self.archive.by_name(name) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(e) => {
+                return Err(RagmcpError::Parse(format!("Cannot read {} in {}: {}", name, self.path, e)))
+            }
+        };
+        let mut bytes = Vec::new();
+        file.take(MAX_PART_BYTES + 1).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 > MAX_PART_BYTES {
+            return Err(RagmcpError::Skipped(format!("{} in {} is too large", name, self.path)));
+        }
+        Ok(Some(bytes))
+    }
+
+    /// Like `read`, but a missing part is an error
+    pub(super) fn read_required(&mut self, name: &str) -> Result<Vec<u8>> {
+        self.read(name)?.ok_or_else(|| {
+            RagmcpError::Parse(format!("{} is missing {}", self.path, name))
+        })
+    }
+
+    pub(super) fn file_names(&self) -> Vec<String> {
+        self.archive.file_names().map(str::to_string).collect()
+    }
+
+    /// Relationships of an OOXML part: relationship id -> (type, resolved part name)
+    pub(super) fn relationships(&mut self, part: &str) -> Result<HashMap<String, (String, String)>> {
+        let (dir, file) = part.rsplit_once('/').unwrap_or(("", part));
+        let rels_part = if dir.is_empty() {
+            format!("_rels/{}.rels", file)
+        } else {
+            format!("{}/_rels/{}.rels", dir, file)
+        };
+        let mut rels = HashMap::new();
+        let Some(xml) = self.read(&rels_part)? else {
+            return Ok(rels);
+        };
+        for_each_event(&xml, |event| {
+            if let XmlEvent::Start(name, attrs) = event {
+                if name == "Relationship" {
+                    if let (Some(id), Some(target)) = (attr(&attrs, "Id"), attr(&attrs, "Target")) {
+                        let kind = attr(&attrs, "Type").unwrap_or_default();
+                        rels.insert(id.to_string(), (kind.to_string(), resolve_target(dir, target)));
+                    }
+                }
+            }
+        })?;
+        Ok(rels)
+    }
+}
+
+/// Resolve a relationship target relative to the directory of its source part
+fn resolve_target(base_dir: &str, target: &str) -> String {
+    let mut parts: Vec<&str> = match target.strip_prefix('/') {
+        Some(_) => Vec::new(),
+        None => base_dir.split('/').filter(|p| !p.is_empty()).collect(),
+    };
+    for segment in target.trim_start_matches('/').split('/') {
+        match segment {
+            ".." => {
+                parts.pop();
+            }
+            "." | "" => {}
+            s => parts.push(s),
+        }
+    }
+    parts.join("/")
+}
+
+/// Simplified XML event: element names without namespace prefix, attributes as written
+pub(super) enum XmlEvent {
+    Start(String, Vec<(String, String)>),
+    End(String),
+    Text(String),
+}
+
+/// Walk the events of an XML part; empty elements produce a `Start` followed by an `End`
+pub(super) fn for_each_event(xml: &[u8], mut f: impl FnMut(XmlEvent)) -> Result<()> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| RagmcpError::Parse(format!("XML error at {}: {}", reader.buffer_position(), e)))?;
+        let is_empty = matches!(event, Event::Empty(_));
+        match event {
+            Event::Start(e) | Event::Empty(e) => {
+                let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+                let attrs = e
+                    .attributes()
+                    .flatten()
+                    .map(|a| {
+                        let key = String::from_utf8_lossy(a.key.as_ref()).to_string();
+                        let value = a
+                            .unescape_value()
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|_| String::from_utf8_lossy(&a.value).to_string());
+                        (key, value)
+                    })
+                    .collect();
+                f(XmlEvent::Start(name.clone(), attrs));
+                if is_empty {
+                    f(XmlEvent::End(name));
+                }
+            }
+            Event::End(e) => f(XmlEvent::End(String::from_utf8_lossy(e.local_name().as_ref()).to_string())),
+            Event::Text(e) => f(XmlEvent::Text(String::from_utf8_lossy(e.as_ref()).to_string())),
+            Event::CData(e) => f(XmlEvent::Text(String::from_utf8_lossy(e.as_ref()).to_string())),
+            Event::GeneralRef(e) => {
+                let resolved = match e.resolve_char_ref() {
+                    Ok(Some(c)) => Some(c.to_string()),
+                    _ => resolve_predefined_entity(&String::from_utf8_lossy(e.as_ref())).map(str::to_string),
+                };
+                if let Some(text) = resolved {
+                    f(XmlEvent::Text(text));
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+/// Attribute value by its qualified name (`w:val`, `r:id`)
+pub(super) fn attr<'v>(attrs: &'v [(String, String)], name: &str) -> Option<&'v str> {
+    attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+}
+
+/// Document properties (title, author, subject, keywords) from OOXML `docProps/core.xml`
+/// or ODF `meta.xml`
+pub(super) fn document_properties(package: &mut Package, part: &str) -> Result<Option<serde_json::Value>> {
+    let Some(xml) = package.read(part)? else {
+        return Ok(None);
+    };
+    let mut properties = serde_json::Map::new();
+    let mut current: Option<&str> = None;
+    let mut text = String::new();
+    for_each_event(&xml, |event| match event {
+        XmlEvent::Start(name, _) => {
+            current = match name.as_str() {
+                "title" => Some("title"),
+                "creator" | "initial-creator" => Some("author"),
+                "subject" => Some("subject"),
+                "keywords" | "keyword" => Some("keywords"),
+                _ => None,
+            };
+            text.clear();
+        }
+        XmlEvent::Text(t) if current.is_some() => text.push_str(&t),
+        XmlEvent::End(_) => {
+            if let Some(key) = current.take() {
+                let value = text.trim();
+                if !value.is_empty() && !properties.contains_key(key) {
+                    properties.insert(key.to_string(), serde_json::Value::String(value.to_string()));
+                }
+            }
+        }
+        _ => {}
+    })?;
+    Ok((!properties.is_empty()).then_some(serde_json::Value::Object(properties)))
+}
+
+/// Render table rows as `| a | b |` lines
+pub(super) fn table_rows(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .filter(|row| row.iter().any(|c| !c.is_empty()))
+        .map(|row| format!("| {} |", row.join(" | ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Paragraph/table text flow of word processing documents (DOCX, ODT)
+///
+/// Paragraphs become heading sections or body paragraphs; paragraphs inside table
+/// cells are collected into `| a | b |` rows, nested tables into their parent cell.
+#[derive(Default)]
+pub(super) struct TextFlow {
+    pub(super) builder: SectionBuilder,
+    paragraph: String,
+    heading: Option<usize>,
+    list_item: bool,
+    tables: Vec<Table>,
+}
+
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<String>>,
+    row: Vec<String>,
+    cell: Vec<String>,
+}
+
+impl TextFlow {
+    pub(super) fn text(&mut self, text: &str) {
+        self.paragraph.push_str(text);
+    }
+
+    pub(super) fn set_heading(&mut self, level: usize) {
+        self.heading = Some(level.clamp(1, 9));
+    }
+
+    pub(super) fn set_list_item(&mut self) {
+        self.list_item = true;
+    }
+
+    pub(super) fn end_paragraph(&mut self) {
+        let text = self.paragraph.trim().to_string();
+        self.paragraph.clear();
+        let heading = self.heading.take();
+        let list_item = std::mem::take(&mut self.list_item);
+        if text.is_empty() {
+            return;
+        }
+        if let Some(table) = self.tables.last_mut() {
+            table.cell.push(text);
+        } else if let Some(level) = heading {
+            self.builder.start_section(text, level);
+        } else if list_item {
+            self.builder.push_line(&format!("- {}", text));
+        } else {
+            self.builder.push_paragraph(&text);
+        }
+    }
+
+    pub(super) fn start_table(&mut self) {
+        self.tables.push(Table::default());
+    }
+
+    pub(super) fn end_cell(&mut self) {
+        if let Some(table) = self.tables.last_mut() {
+            let cell = std::mem::take(&mut table.cell).join(" ");
+            table.row.push(cell.replace('|', "/"));
+        }
+    }
+
+    pub(super) fn end_row(&mut self) {
+        if let Some(table) = self.tables.last_mut() {
+            let row = std::mem::take(&mut table.row);
+            table.rows.push(row);
+        }
+    }
+
+    pub(super) fn end_table(&mut self) {
+        let Some(table) = self.tables.pop() else { return };
+        let text = table_rows(&table.rows);
+        match self.tables.last_mut() {
+            Some(parent) => parent.cell.push(text.replace('\n', " ")),
+            None => self.builder.push_paragraph(&text),
+        }
+    }
+}
+
+/// Build an in-memory zip package from (part name, content) pairs
+#[cfg(test)]
+pub(super) fn build_package(parts: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in parts {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relationships_and_properties() {
+        let bytes = build_package(&[
+            (
+                "ppt/slides/_rels/slide1.xml.rels",
+                r#"<Relationships><Relationship Id="rId2" Type="http://x/notesSlide" Target="../notesSlides/notesSlide1.xml"/></Relationships>"#,
+            ),
+            (
+                "docProps/core.xml",
+                r#"<cp:coreProperties xmlns:dc="d" xmlns:cp="c"><dc:title>Q3 Plan &amp; Goals</dc:title><dc:creator>Ops</dc:creator></cp:coreProperties>"#,
+            ),
+        ]);
+        let mut package = Package::open(&bytes, "deck.pptx").unwrap();
+
+        let rels = package.relationships("ppt/slides/slide1.xml").unwrap();
+        assert_eq!(rels["rId2"].1, "ppt/notesSlides/notesSlide1.xml");
+
+        let properties = document_properties(&mut package, "docProps/core.xml").unwrap().unwrap();
+        assert_eq!(properties["title"], "Q3 Plan & Goals");
+        assert_eq!(properties["author"], "Ops");
+        assert!(package.read("missing.xml").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_encrypted_and_invalid_packages() {
+        let mut encrypted = CFB_MAGIC.to_vec();
+        encrypted.extend_from_slice(&[0; 64]);
+        assert!(matches!(Package::open(&encrypted, "secret.docx"), Err(RagmcpError::Skipped(_))));
+        assert!(matches!(Package::open(b"not a zip", "broken.docx"), Err(RagmcpError::Parse(_))));
+    }
+}
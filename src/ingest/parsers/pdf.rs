@@ -0,0 +1,302 @@
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+use lopdf::Document;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// PDF parser
+///
+/// Extracts the text layer of each page with `lopdf` and produces one section per
+/// page. When the document has an outline (bookmarks), the section header is the
+/// breadcrumb of the outline entry covering the page ("Setup > Network"), otherwise
+/// "Page N". The page number is kept on the section so chunks can cite it.
+///
+/// Encrypted files that do not open with the empty user password and files without
+/// any text layer (scanned images) are rejected with `RagmcpError::Skipped`.
+pub struct PdfParser;
+
+impl Parser for PdfParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "pdf"
+    }
+
+    fn parse(&self, _content: &str, path: &str) -> Result<ParsedDocument> {
+        Err(RagmcpError::Parse(format!("{} is a PDF and must be parsed from bytes", path)))
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let mut doc = Document::load_mem(bytes)
+            .map_err(|e| RagmcpError::Parse(format!("Invalid PDF {}: {}", path, e)))?;
+
+        if doc.is_encrypted() && doc.decrypt("").is_err() {
+            return Err(RagmcpError::Skipped(format!("{} is encrypted", path)));
+        }
+
+        let outline = outline_entries(&doc);
+        let mut sections = Vec::new();
+
+        for page_num in doc.get_pages().into_keys() {
+            // Pages with unsupported fonts or broken content streams are skipped
+            // rather than failing the whole document
+            let text = match guarded(|| doc.extract_text(&[page_num])) {
+                Ok(text) => normalize_text(&text),
+                Err(e) => {
+                    log::debug!("No text extracted from {} page {}: {}", path, page_num, e);
+                    continue;
+                }
+            };
+            if text.is_empty() {
+                continue;
+            }
+
+            let header = page_header(&outline, page_num)
+                .unwrap_or_else(|| format!("Page {}", page_num));
+            sections.push(Section {
+                header,
+                content: text,
+                section_type: Some("page".to_string()),
+                page: Some(page_num),
+            });
+        }
+
+        if sections.is_empty() {
+            return Err(RagmcpError::Skipped(format!(
+                "{} has no text layer (image-only or scanned PDF)",
+                path
+            )));
+        }
+
+        let content = sections
+            .iter()
+            .map(|s| s.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        Ok(ParsedDocument {
+            content,
+            sections,
+            doc_type: "pdf".to_string(),
+            metadata: None,
+        })
+    }
+}
+
+/// Run a lopdf call on the untrusted document, turning a panic into an error
+///
+/// lopdf 0.34 panics on some malformed files instead of returning an error: the outline
+/// walker behind `get_toc` unwraps each outline item's `/Title` as a string, expects its
+/// destination page to be an object reference and indexes into destination arrays. Text extraction walks fonts and
+/// content streams from the same file, so both calls go through here.
+fn guarded<T>(call: impl FnOnce() -> lopdf::Result<T>) -> std::result::Result<T, String> {
+    match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err("lopdf panicked on malformed input".to_string()),
+    }
+}
+
+/// Outline entry with the breadcrumb of its ancestors
+#[derive(Debug, Clone, PartialEq)]
+struct OutlineEntry {
+    page: u32,
+    breadcrumb: String,
+}
+
+/// Flatten the document outline into breadcrumbs in outline order
+fn outline_entries(doc: &Document) -> Vec<OutlineEntry> {
+    // A broken outline only costs us the nicer headers
+    let toc = match guarded(|| doc.get_toc()) {
+        Ok(toc) => toc.toc,
+        Err(e) => {
+            log::debug!("No outline used: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut stack: Vec<String> = Vec::new();
+    let mut entries = Vec::new();
+    for item in toc {
+        let title = item.title.trim().to_string();
+        if title.is_empty() {
+            continue;
+        }
+        stack.truncate(item.level.saturating_sub(1));
+        stack.push(title);
+        entries.push(OutlineEntry {
+            page: item.page as u32,
+            breadcrumb: stack.join(" > "),
+        });
+    }
+    entries
+}
+
+/// Breadcrumb of the outline entry starting closest before (or on) `page`
+fn page_header(outline: &[OutlineEntry], page: u32) -> Option<String> {
+    outline
+        .iter()
+        .filter(|e| e.page <= page)
+        .fold(None, |best: Option<&OutlineEntry>, e| match best {
+            Some(b) if b.page > e.page => Some(b),
+            _ => Some(e),
+        })
+        .map(|e| e.breadcrumb.clone())
+}
+
+/// Trim lines and collapse runs of blank lines left by text positioning
+fn normalize_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank { "\n\n" } else { "\n" });
+        }
+        out.push_str(line);
+        blank = false;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Bookmark, Object, Stream};
+
+    /// Build a PDF with one page per text (empty text = page without text layer)
+    fn build_pdf(pages: &[&str], bookmarks: &[(&str, usize)]) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut page_ids = Vec::new();
+        for text in pages {
+            let mut operations = Vec::new();
+            if !text.is_empty() {
+                operations = vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                    Operation::new("Td", vec![72.into(), 720.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(*text)]),
+                    Operation::new("ET", vec![]),
+                ];
+            }
+            let content = Content { operations };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            page_ids.push(doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            }));
+        }
+
+        doc.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => page_ids.iter().map(|id| Object::Reference(*id)).collect::<Vec<_>>(),
+            "Count" => page_ids.len() as i64,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+        }));
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut parent = None;
+        for (title, page_idx) in bookmarks {
+            let id = doc.add_bookmark(
+                Bookmark::new(title.to_string(), [0.0, 0.0, 0.0], 0, page_ids[*page_idx]),
+                parent,
+            );
+            parent.get_or_insert(id);
+        }
+        if let Some(outline_id) = doc.build_outline() {
+            if let Ok(Object::Dictionary(catalog)) = doc.get_object_mut(catalog_id) {
+                catalog.set("Outlines", Object::Reference(outline_id));
+            }
+        }
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_parse_pages_with_page_numbers() {
+        let bytes = build_pdf(&["Gateway timeout settings", "Retry policy details"], &[]);
+        let doc = PdfParser.parse_bytes(&bytes, "manual.pdf").unwrap();
+
+        assert_eq!(doc.doc_type, "pdf");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Page 1");
+        assert_eq!(doc.sections[0].page, Some(1));
+        assert!(doc.sections[0].content.contains("Gateway timeout settings"));
+        assert_eq!(doc.sections[1].page, Some(2));
+        assert!(doc.content.contains("Retry policy details"));
+    }
+
+    #[test]
+    fn test_outline_breadcrumb_headers() {
+        let bytes = build_pdf(
+            &["Intro text", "Network text", "More network text"],
+            &[("Setup", 0), ("Network", 1)],
+        );
+        let doc = PdfParser.parse_bytes(&bytes, "manual.pdf").unwrap();
+
+        assert_eq!(doc.sections[0].header, "Setup");
+        assert_eq!(doc.sections[1].header, "Setup > Network");
+        assert_eq!(doc.sections[2].header, "Setup > Network");
+        assert_eq!(doc.sections[2].page, Some(3));
+    }
+
+    #[test]
+    fn test_malformed_outline_keeps_text() {
+        let bytes = build_pdf(&["Intro text"], &[("Setup", 0)]);
+        let mut doc = Document::load_mem(&bytes).unwrap();
+        // Outline title that is not a string: lopdf's outline walker unwraps it
+        for object in doc.objects.values_mut() {
+            if let Object::Dictionary(dict) = object {
+                if dict.has(b"Title") {
+                    dict.set("Title", Object::Integer(7));
+                }
+            }
+        }
+        let mut broken = Vec::new();
+        doc.save_to(&mut broken).unwrap();
+
+        let parsed = PdfParser.parse_bytes(&broken, "manual.pdf").unwrap();
+        assert_eq!(parsed.sections[0].header, "Page 1");
+        assert!(parsed.content.contains("Intro text"));
+    }
+
+    #[test]
+    fn test_image_only_and_invalid_pdfs() {
+        let bytes = build_pdf(&["", ""], &[]);
+        assert!(matches!(
+            PdfParser.parse_bytes(&bytes, "scan.pdf"),
+            Err(RagmcpError::Skipped(_))
+        ));
+        assert!(matches!(
+            PdfParser.parse_bytes(b"not a pdf", "broken.pdf"),
+            Err(RagmcpError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(normalize_text("  a \n\n\n b\nc  \n"), "a\n\nb\nc");
+    }
+}
@@ -30,12 +30,193 @@ impl Parser for PlainTextParser {
             header: "content".to_string(),
             content: content.to_string(),
             section_type: None,
+            page: None,
         }];
         
         Ok(ParsedDocument {
             content: content.to_string(),
             sections,
             doc_type: doc_type.to_string(),
+            metadata: None,
         })
     }
 }
+
+/// Paragraph groups are merged up to this size (~300 tokens, the default chunk size)
+const MAX_GROUP_CHARS: usize = 1200;
+
+/// Plain text parser for `.txt` files
+/// 
+/// Detects heading-like lines (ALL CAPS lines and lines underlined with `===`,
+/// `---`, `~~~`) and starts a section at each; underlined `===` and ALL CAPS
+/// headings are top level, others nest under them ("INSTALL > Linux"). Within a
+/// section, consecutive blank-line separated paragraphs are grouped into sections
+/// of at most ~300 tokens so chunks never start mid-paragraph unless a single
+/// paragraph is larger than that.
+pub struct TextParser;
+
+impl Parser for TextParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "txt"
+    }
+    
+    fn parse(&self, content: &str, _path: &str) -> Result<ParsedDocument> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut sections = Vec::new();
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut paragraphs: Vec<String> = Vec::new();
+        let mut paragraph: Vec<&str> = Vec::new();
+        
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+            let next = lines.get(i + 1).map(|l| l.trim());
+            let heading = match next.and_then(underline_level) {
+                Some(level) if paragraph.is_empty() && is_heading_text(line) => Some((level, 2)),
+                _ if paragraph.is_empty() && is_all_caps_heading(line, next) => Some((1, 1)),
+                _ => None,
+            };
+            
+            if let Some((level, consumed)) = heading {
+                push_paragraph_groups(&mut sections, &headings, &mut paragraphs);
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, line.trim().to_string()));
+                i += consumed;
+                continue;
+            }
+            
+            if line.trim().is_empty() {
+                if !paragraph.is_empty() {
+                    paragraphs.push(paragraph.join("\n"));
+                    paragraph.clear();
+                }
+            } else {
+                paragraph.push(line);
+            }
+            i += 1;
+        }
+        if !paragraph.is_empty() {
+            paragraphs.push(paragraph.join("\n"));
+        }
+        push_paragraph_groups(&mut sections, &headings, &mut paragraphs);
+        
+        if sections.is_empty() {
+            sections.push(Section {
+                header: "content".to_string(),
+                content: content.to_string(),
+                section_type: None,
+                page: None,
+            });
+        }
+        
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: "text".to_string(),
+            metadata: None,
+        })
+    }
+}
+
+/// Heading level of an underline (`===` → 1, `---`/`~~~`/`***` → 2)
+fn underline_level(line: &str) -> Option<usize> {
+    let first = line.chars().next()?;
+    if line.chars().count() < 3 || !line.chars().all(|c| c == first) {
+        return None;
+    }
+    match first {
+        '=' => Some(1),
+        '-' | '~' | '*' => Some(2),
+        _ => None,
+    }
+}
+
+fn is_heading_text(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && line.chars().count() <= 100 && underline_level(line).is_none()
+}
+
+/// A short standalone line whose letters are all upper case, e.g. "1. INSTALLATION"
+fn is_all_caps_heading(line: &str, next: Option<&str>) -> bool {
+    let line = line.trim();
+    let letters: Vec<char> = line.chars().filter(|c| c.is_alphabetic()).collect();
+    letters.len() >= 3
+        && line.chars().count() <= 80
+        && letters.iter().all(|c| c.is_uppercase())
+        && !line.ends_with(['.', ',', ';'])
+        && next.map_or(true, |n| n.is_empty() || !is_all_caps_line(n))
+}
+
+/// Whether the following line continues an all-caps block (a shouted paragraph, not a heading)
+fn is_all_caps_line(line: &str) -> bool {
+    let mut letters = line.chars().filter(|c| c.is_alphabetic()).peekable();
+    letters.peek().is_some() && letters.all(|c| c.is_uppercase())
+}
+
+/// Emit collected paragraphs under the current heading, grouped up to `MAX_GROUP_CHARS`
+fn push_paragraph_groups(sections: &mut Vec<Section>, headings: &[(usize, String)], paragraphs: &mut Vec<String>) {
+    let (header, section_type) = match headings.last() {
+        Some((level, _)) => (
+            headings.iter().map(|(_, h)| h.as_str()).collect::<Vec<_>>().join(" > "),
+            Some(format!("h{}", level)),
+        ),
+        None => ("content".to_string(), None),
+    };
+    
+    let mut group = String::new();
+    for paragraph in paragraphs.drain(..) {
+        if !group.is_empty() && group.len() + paragraph.len() + 2 > MAX_GROUP_CHARS {
+            sections.push(Section {
+                header: header.clone(),
+                content: std::mem::take(&mut group),
+                section_type: section_type.clone(),
+                page: None,
+            });
+        }
+        if !group.is_empty() {
+            group.push_str("\n\n");
+        }
+        group.push_str(&paragraph);
+    }
+    if !group.is_empty() {
+        sections.push(Section {
+            header,
+            content: group,
+            section_type,
+            page: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_text_headings_and_paragraphs() {
+        let content = "Release notes for the gateway.\n\nINSTALLATION\n\nDownload the binary.\n\nLinux\n-----\nUse the tarball.\nExtract it.\n\nUSAGE\n\nRun it.\n";
+        let doc = TextParser.parse(content, "notes.txt").unwrap();
+        let headers: Vec<&str> = doc.sections.iter().map(|s| s.header.as_str()).collect();
+        
+        assert_eq!(doc.doc_type, "text");
+        assert_eq!(headers, vec!["content", "INSTALLATION", "INSTALLATION > Linux", "USAGE"]);
+        assert_eq!(doc.sections[2].content, "Use the tarball.\nExtract it.");
+        assert_eq!(doc.sections[2].section_type.as_deref(), Some("h2"));
+    }
+    
+    #[test]
+    fn test_text_paragraph_grouping() {
+        let paragraph = "word ".repeat(100);
+        let content = [paragraph.trim(); 5].join("\n\n");
+        let doc = TextParser.parse(&content, "long.txt").unwrap();
+        
+        // 5 × ~500 chars split into groups of at most two paragraphs, never mid-paragraph
+        assert_eq!(doc.sections.len(), 3);
+        assert!(doc.sections.iter().all(|s| s.content.starts_with("word") && s.content.ends_with("word")));
+        
+        // Shouted paragraphs are not headings
+        let doc = TextParser.parse("WARNING DO NOT\nUNPLUG THE DEVICE\n\nok", "w.txt").unwrap();
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].header, "content");
+    }
+}
@@ -0,0 +1,217 @@
+use super::office::{attr, document_properties, for_each_event, Package, XmlEvent};
+use super::section_builder::document;
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+
+/// PowerPoint (PPTX) parser
+///
+/// Produces one section per slide in presentation order, headed `Slide N: <title>`
+/// (the text of the title placeholder). The section holds the slide text followed by
+/// the speaker notes, and `page` is the slide number so search results can cite it.
+pub struct PptxParser;
+
+impl Parser for PptxParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "pptx"
+    }
+
+    fn parse(&self, _content: &str, path: &str) -> Result<ParsedDocument> {
+        Err(RagmcpError::Parse(format!("{} is a PowerPoint file and must be parsed from bytes", path)))
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let mut package = Package::open(bytes, path)?;
+        let mut sections = Vec::new();
+
+        for (idx, slide_part) in slide_parts(&mut package)?.into_iter().enumerate() {
+            let Some(xml) = package.read(&slide_part)? else { continue };
+            let slide = shape_text(&xml)?;
+
+            // Speaker notes live in a separate part linked from the slide
+            let notes_part = package
+                .relationships(&slide_part)?
+                .into_values()
+                .find(|(kind, _)| kind.ends_with("/notesSlide"))
+                .map(|(_, target)| target);
+            let notes = match notes_part {
+                Some(part) => match package.read(&part)? {
+                    Some(xml) => shape_text(&xml)?.body,
+                    None => Vec::new(),
+                },
+                None => Vec::new(),
+            };
+
+            let number = idx + 1;
+            let header = match &slide.title {
+                Some(title) => format!("Slide {}: {}", number, title),
+                None => format!("Slide {}", number),
+            };
+            let mut content = slide.body.join("\n");
+            if !notes.is_empty() {
+                content.push_str(&format!("\n\nSpeaker notes:\n{}", notes.join("\n")));
+            }
+            sections.push(Section {
+                header,
+                content: content.trim().to_string(),
+                section_type: Some("slide".to_string()),
+                page: Some(number as u32),
+            });
+        }
+
+        let metadata = document_properties(&mut package, "docProps/core.xml")?;
+        document(sections, path, "pptx", metadata)
+    }
+}
+
+/// Slide part names in presentation order (`p:sldIdLst`), falling back to file name order
+fn slide_parts(package: &mut Package) -> Result<Vec<String>> {
+    let rels = package.relationships("ppt/presentation.xml")?;
+    let mut ordered = Vec::new();
+    if let Some(xml) = package.read("ppt/presentation.xml")? {
+        for_each_event(&xml, |event| {
+            if let XmlEvent::Start(name, attrs) = event {
+                if name == "sldId" {
+                    if let Some((_, target)) = attr(&attrs, "r:id").and_then(|id| rels.get(id)) {
+                        ordered.push(target.clone());
+                    }
+                }
+            }
+        })?;
+    }
+    if !ordered.is_empty() {
+        return Ok(ordered);
+    }
+
+    let mut numbered: Vec<(u32, String)> = package
+        .file_names()
+        .into_iter()
+        .filter_map(|name| {
+            let number = name.strip_prefix("ppt/slides/slide")?.strip_suffix(".xml")?.parse().ok()?;
+            Some((number, name))
+        })
+        .collect();
+    numbered.sort();
+    Ok(numbered.into_iter().map(|(_, name)| name).collect())
+}
+
+/// Text of a slide or notes page: the title placeholder and the other text paragraphs
+#[derive(Default)]
+struct ShapeText {
+    title: Option<String>,
+    body: Vec<String>,
+}
+
+/// Placeholders without readable content (slide number, date, slide image on notes pages)
+const SKIPPED_PLACEHOLDERS: &[&str] = &["sldNum", "dt", "ftr", "hdr", "sldImg"];
+
+fn shape_text(xml: &[u8]) -> Result<ShapeText> {
+    let mut result = ShapeText::default();
+    let mut placeholder: Option<String> = None;
+    let mut paragraph = String::new();
+    let mut shape_paragraphs: Vec<String> = Vec::new();
+    let mut cell: Option<Vec<String>> = None;
+    let mut row: Vec<String> = Vec::new();
+    let mut in_text = false;
+    for_each_event(xml, |event| match event {
+        XmlEvent::Start(name, attrs) => match name.as_str() {
+            "sp" | "graphicFrame" => {
+                placeholder = None;
+                shape_paragraphs.clear();
+            }
+            "ph" => placeholder = Some(attr(&attrs, "type").unwrap_or("body").to_string()),
+            "t" => in_text = true,
+            "br" => paragraph.push('\n'),
+            "tc" => cell = Some(Vec::new()),
+            _ => {}
+        },
+        XmlEvent::Text(text) if in_text => paragraph.push_str(&text),
+        XmlEvent::End(name) => match name.as_str() {
+            "t" => in_text = false,
+            "p" => {
+                let text = paragraph.trim().to_string();
+                paragraph.clear();
+                if !text.is_empty() {
+                    cell.as_mut().unwrap_or(&mut shape_paragraphs).push(text);
+                }
+            }
+            // Table rows read left to right on one line
+            "tc" => row.push(cell.take().unwrap_or_default().join(" ")),
+            "tr" => {
+                let cells = std::mem::take(&mut row);
+                if cells.iter().any(|c| !c.is_empty()) {
+                    shape_paragraphs.push(format!("| {} |", cells.join(" | ")));
+                }
+            }
+            "sp" | "graphicFrame" => {
+                let paragraphs = std::mem::take(&mut shape_paragraphs);
+                match placeholder.as_deref() {
+                    Some("title" | "ctrTitle") if result.title.is_none() => {
+                        result.title = Some(paragraphs.join(" "));
+                    }
+                    Some(kind) if SKIPPED_PLACEHOLDERS.contains(&kind) => {}
+                    _ => result.body.extend(paragraphs),
+                }
+                placeholder = None;
+            }
+            _ => {}
+        },
+        _ => {}
+    })?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::parsers::office::build_package;
+
+    fn slide(title: &str, body: &str) -> String {
+        format!(
+            r#"<p:sld xmlns:p="p" xmlns:a="a"><p:cSld><p:spTree>
+<p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>{}</a:t></a:r></a:p></p:txBody></p:sp>
+<p:sp><p:txBody><a:p><a:r><a:t>{}</a:t></a:r></a:p></p:txBody></p:sp>
+<p:sp><p:nvSpPr><p:nvPr><p:ph type="sldNum"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>7</a:t></a:r></a:p></p:txBody></p:sp>
+</p:spTree></p:cSld></p:sld>"#,
+            title, body
+        )
+    }
+
+    #[test]
+    fn test_pptx_slides_in_presentation_order_with_notes() {
+        let first = slide("Roadmap", "Q3 goals");
+        let second = slide("Risks", "Vendor delays");
+        let bytes = build_package(&[
+            (
+                "ppt/presentation.xml",
+                r#"<p:presentation xmlns:p="p" xmlns:r="r"><p:sldIdLst><p:sldId id="256" r:id="rId3"/><p:sldId id="257" r:id="rId2"/></p:sldIdLst></p:presentation>"#,
+            ),
+            (
+                "ppt/_rels/presentation.xml.rels",
+                r#"<Relationships><Relationship Id="rId2" Type="x/slide" Target="slides/slide1.xml"/><Relationship Id="rId3" Type="x/slide" Target="slides/slide2.xml"/></Relationships>"#,
+            ),
+            ("ppt/slides/slide1.xml", &second),
+            ("ppt/slides/slide2.xml", &first),
+            (
+                "ppt/slides/_rels/slide2.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Type="x/notesSlide" Target="../notesSlides/notesSlide1.xml"/></Relationships>"#,
+            ),
+            (
+                "ppt/notesSlides/notesSlide1.xml",
+                r#"<p:notes xmlns:p="p" xmlns:a="a"><p:sp><p:nvSpPr><p:nvPr><p:ph type="body"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>Mention hiring</a:t></a:r></a:p></p:txBody></p:sp></p:notes>"#,
+            ),
+        ]);
+        let doc = PptxParser.parse_bytes(&bytes, "deck.pptx").unwrap();
+
+        assert_eq!(doc.doc_type, "pptx");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Slide 1: Roadmap");
+        assert_eq!(doc.sections[0].page, Some(1));
+        assert_eq!(doc.sections[0].content, "Q3 goals\n\nSpeaker notes:\nMention hiring");
+        assert_eq!(doc.sections[1].header, "Slide 2: Risks");
+        assert_eq!(doc.sections[1].content, "Vendor delays");
+    }
+}
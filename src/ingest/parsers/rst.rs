@@ -0,0 +1,295 @@
+use super::section_builder::SectionBuilder;
+use super::{Parser, ParsedDocument};
+use crate::error::Result;
+use regex::Regex;
+use serde_json::json;
+use std::sync::OnceLock;
+
+/// Characters RST accepts for section adornments
+const ADORNMENT_CHARS: &str = "=-~^\"'`#*+:._<>!$%&,/;?@\\|";
+
+/// Admonition directives rendered as `NOTE: ...` paragraphs
+const ADMONITIONS: &[&str] = &[
+    "note", "warning", "tip", "important", "caution", "danger", "attention", "hint", "error", "seealso",
+];
+
+/// Directives without readable text content
+const SKIPPED_DIRECTIVES: &[&str] = &[
+    "image", "toctree", "contents", "raw", "meta", "index", "highlight", "sectnum", "role",
+    "default-role", "tabularcolumns", "only",
+];
+
+/// reStructuredText parser for Sphinx and docutils documentation
+///
+/// Sections follow RST heading adornments: a title underlined (optionally also
+/// overlined) with punctuation. Levels are assigned in order of first appearance of
+/// each adornment style, as docutils does. `.. code-block::`/`.. code::` directives
+/// and `::` literal blocks become fenced code, admonitions (`.. note::`,
+/// `.. warning::`, ...) become `NOTE: ...` paragraphs, and `.. include::` /
+/// `.. literalinclude::` targets are listed in the `includes` metadata.
+pub struct RstParser;
+
+impl Parser for RstParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "rst"
+    }
+
+    fn parse(&self, content: &str, _path: &str) -> Result<ParsedDocument> {
+        static DIRECTIVE: OnceLock<Regex> = OnceLock::new();
+        let directive_re = DIRECTIVE
+            .get_or_init(|| Regex::new(r"^\.\.\s+([\w:-]+)::\s*(.*)$").expect("Invalid regex pattern"));
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut builder = SectionBuilder::default();
+        let mut styles: Vec<(char, bool)> = Vec::new();
+        let mut includes: Vec<String> = Vec::new();
+
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+
+            if let Some((text, style, consumed)) = heading_at(&lines, i) {
+                let level = match styles.iter().position(|s| *s == style) {
+                    Some(pos) => pos + 1,
+                    None => {
+                        styles.push(style);
+                        styles.len()
+                    }
+                };
+                builder.start_section(text, level);
+                i += consumed;
+                continue;
+            }
+
+            if line.starts_with("..") {
+                let (body, next) = indented_block(&lines, i + 1);
+                if let Some(c) = directive_re.captures(line) {
+                    let name = c[1].to_lowercase();
+                    let argument = c[2].trim();
+                    let body: Vec<&str> = body
+                        .into_iter()
+                        .skip_while(|l| is_option_line(l))
+                        .skip_while(|l| l.is_empty())
+                        .collect();
+                    match name.as_str() {
+                        "code-block" | "code" | "sourcecode" => builder.push_code(argument, &body),
+                        "include" | "literalinclude" => {
+                            includes.push(argument.to_string());
+                            builder.push_line(&format!("[include: {}]", argument));
+                        }
+                        _ if ADMONITIONS.contains(&name.as_str()) => {
+                            let text = join_block(argument, &body);
+                            builder.push_paragraph(&format!("{}: {}", name.to_uppercase(), text));
+                        }
+                        "admonition" => {
+                            let text = join_block("", &body);
+                            builder.push_paragraph(&format!("{}: {}", argument, text));
+                        }
+                        _ if SKIPPED_DIRECTIVES.contains(&name.as_str()) => {}
+                        _ => {
+                            let text = join_block(argument, &body);
+                            if !text.is_empty() {
+                                builder.push_paragraph(&text);
+                            }
+                        }
+                    }
+                }
+                // Anything else starting with `..` is a comment, target or substitution
+                i = next;
+                continue;
+            }
+
+            if line.ends_with("::") && !line.trim().is_empty() {
+                // Paragraph introducing a literal block: "Example::" renders as "Example:"
+                let intro = line.trim_end_matches(':').trim_end();
+                if !intro.is_empty() {
+                    builder.push_line(&format!("{}:", clean_inline(intro)));
+                }
+                let (body, next) = indented_block(&lines, i + 1);
+                builder.push_code("", &body);
+                i = next;
+                continue;
+            }
+
+            builder.push_line(&clean_inline(line));
+            i += 1;
+        }
+
+        let title = builder.first_heading.clone();
+        let sections = builder.finish(content);
+        let mut metadata = serde_json::Map::new();
+        if let Some(title) = title {
+            metadata.insert("title".to_string(), json!(title));
+        }
+        if !includes.is_empty() {
+            metadata.insert("includes".to_string(), json!(includes));
+        }
+
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: "rst".to_string(),
+            metadata: (!metadata.is_empty()).then_some(serde_json::Value::Object(metadata)),
+        })
+    }
+}
+
+/// A heading starting at line `i`: (title, adornment style, lines consumed)
+fn heading_at(lines: &[&str], i: usize) -> Option<(String, (char, bool), usize)> {
+    let line = lines[i].trim_end();
+    let next = lines.get(i + 1).map(|l| l.trim_end());
+
+    // Overline + title + underline
+    if let (Some(c), Some(title), Some(under)) = (adornment(line), next, lines.get(i + 2)) {
+        let title = title.trim();
+        if !title.is_empty() && adornment(title).is_none() && adornment(under.trim_end()) == Some(c) {
+            return Some((title.to_string(), (c, true), 3));
+        }
+    }
+
+    // Title + underline, title not indented and preceded by a blank line
+    let under = next?;
+    let c = adornment(under)?;
+    let starts_block = i == 0 || lines[i - 1].trim().is_empty();
+    let title_len = line.chars().count();
+    if starts_block && !line.trim().is_empty() && !line.starts_with(char::is_whitespace)
+        && adornment(line).is_none()
+        && (under.chars().count() >= title_len || under.chars().count() >= 3)
+    {
+        return Some((line.trim().to_string(), (c, false), 2));
+    }
+    None
+}
+
+/// The adornment character if `line` is a run of one punctuation character
+fn adornment(line: &str) -> Option<char> {
+    let first = line.chars().next()?;
+    (ADORNMENT_CHARS.contains(first) && line.chars().count() >= 2 && line.chars().all(|c| c == first))
+        .then_some(first)
+}
+
+/// Indented lines following a directive or `::` (blank lines included), and the next line index
+fn indented_block<'a>(lines: &[&'a str], start: usize) -> (Vec<&'a str>, usize) {
+    let mut end = start;
+    while end < lines.len() && (lines[end].trim().is_empty() || lines[end].starts_with(char::is_whitespace)) {
+        end += 1;
+    }
+    while end > start && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    (dedent(&lines[start..end]), end)
+}
+
+fn dedent<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut lines: Vec<&str> = lines.iter().map(|l| l.get(indent..).unwrap_or("").trim_end()).collect();
+    while lines.first().is_some_and(|l| l.is_empty()) {
+        lines.remove(0);
+    }
+    lines
+}
+
+/// Directive option lines such as `:linenos:` or `:caption: Example`
+fn is_option_line(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with(':') && line[1..].contains(':')
+}
+
+/// Directive argument and body as one paragraph of text
+fn join_block(argument: &str, body: &[&str]) -> String {
+    std::iter::once(argument)
+        .chain(body.iter().copied())
+        .map(|l| clean_inline(l.trim()))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Simplify inline markup: ``literal`` → `literal`, :role:`target` → `target`
+fn clean_inline(line: &str) -> String {
+    static ROLE: OnceLock<Regex> = OnceLock::new();
+    let role_re = ROLE.get_or_init(|| Regex::new(r":(?:[\w-]+:)+`([^`]+)`").expect("Invalid regex pattern"));
+    role_re.replace_all(&line.replace("``", "`"), "`$1`").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"=========
+ Gateway
+=========
+
+The gateway routes requests.
+
+Installation
+============
+
+Install with pip:
+
+.. code-block:: bash
+   :linenos:
+
+   pip install gateway
+   gateway --version
+
+.. note::
+   Requires Python 3.10
+   or newer.
+
+.. include:: ../shared/license.rst
+
+Configuration
+-------------
+
+Set :conf:`timeout` in ``gateway.toml``. Example::
+
+    timeout = 30
+
+.. _usage:
+
+Usage
+=====
+
+Run it.
+"#;
+
+    #[test]
+    fn test_rst_heading_levels() {
+        let doc = RstParser.parse(DOC, "docs/gateway.rst").unwrap();
+        let headings: Vec<(&str, &str)> = doc
+            .sections
+            .iter()
+            .map(|s| (s.header.as_str(), s.section_type.as_deref().unwrap_or("")))
+            .collect();
+
+        assert_eq!(doc.doc_type, "rst");
+        assert_eq!(
+            headings,
+            vec![("Gateway", "h1"), ("Installation", "h2"), ("Configuration", "h3"), ("Usage", "h2")]
+        );
+        assert_eq!(doc.metadata.as_ref().unwrap()["title"], "Gateway");
+    }
+
+    #[test]
+    fn test_rst_directives() {
+        let doc = RstParser.parse(DOC, "docs/gateway.rst").unwrap();
+        let install = &doc.sections[1].content;
+
+        assert!(install.contains("```bash\npip install gateway\ngateway --version\n```"));
+        assert!(install.contains("NOTE: Requires Python 3.10 or newer."));
+        assert!(install.contains("[include: ../shared/license.rst]"));
+        assert_eq!(doc.metadata.as_ref().unwrap()["includes"][0], "../shared/license.rst");
+
+        let config = &doc.sections[2].content;
+        assert!(config.starts_with("Set `timeout` in `gateway.toml`. Example:"));
+        assert!(config.contains("```\ntimeout = 30\n```"));
+        assert!(!config.contains("_usage"));
+        assert_eq!(doc.sections[3].content, "Run it.");
+    }
+}
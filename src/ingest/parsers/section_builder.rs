@@ -0,0 +1,127 @@
+use super::{ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+
+/// Collects rendered lines into heading sections for the markup and office parsers
+#[derive(Default)]
+pub(super) struct SectionBuilder {
+    sections: Vec<Section>,
+    current: Option<(String, usize)>,
+    body: Vec<String>,
+    /// Text before the first heading
+    preamble: Vec<String>,
+    pub(super) first_heading: Option<String>,
+}
+
+impl SectionBuilder {
+    pub(super) fn start_section(&mut self, header: String, level: usize) {
+        self.flush();
+        self.first_heading.get_or_insert_with(|| header.clone());
+        self.current = Some((header, level));
+    }
+
+    pub(super) fn push_line(&mut self, line: &str) {
+        let target = if self.current.is_some() { &mut self.body } else { &mut self.preamble };
+        target.push(line.trim_end().to_string());
+    }
+
+    /// Push text as its own paragraph
+    pub(super) fn push_paragraph(&mut self, text: &str) {
+        self.push_line("");
+        self.push_line(text);
+        self.push_line("");
+    }
+
+    pub(super) fn push_code(&mut self, language: &str, code: &[&str]) {
+        self.push_line("");
+        self.push_line(&format!("```{}", language));
+        for line in code {
+            self.push_line(line);
+        }
+        self.push_line("```");
+        self.push_line("");
+    }
+
+    fn flush(&mut self) {
+        let text = collapse_blank_lines(&std::mem::take(&mut self.body));
+        if let Some((header, level)) = self.current.take() {
+            if !text.is_empty() {
+                self.sections.push(Section {
+                    header,
+                    content: text,
+                    section_type: Some(format!("h{}", level)),
+                    page: None,
+                });
+            }
+        }
+    }
+
+    /// Final sections; a document without headings becomes one `content` section
+    pub(super) fn finish(mut self, raw: &str) -> Vec<Section> {
+        self.flush();
+        let preamble = collapse_blank_lines(&self.preamble);
+        if !preamble.is_empty() {
+            self.sections.insert(0, Section {
+                header: "content".to_string(),
+                content: preamble,
+                section_type: None,
+                page: None,
+            });
+        }
+        if self.sections.is_empty() {
+            self.sections.push(Section {
+                header: "content".to_string(),
+                content: raw.to_string(),
+                section_type: None,
+                page: None,
+            });
+        }
+        self.sections
+    }
+}
+
+/// Join lines, dropping leading/trailing blanks and runs of blank lines outside code fences
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in lines {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if line.is_empty() && !in_fence && out.last().map_or(true, |l| l.is_empty()) {
+            continue;
+        }
+        out.push(line);
+    }
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// Finish a document whose source is not plain text (packages, notebooks): documents
+/// without any text are skipped, and the content is the text of all sections with their headers
+pub(super) fn document(
+    sections: Vec<Section>,
+    path: &str,
+    doc_type: &str,
+    metadata: Option<serde_json::Value>,
+) -> Result<ParsedDocument> {
+    let sections: Vec<Section> = sections.into_iter().filter(|s| !s.content.trim().is_empty()).collect();
+    if sections.is_empty() {
+        return Err(RagmcpError::Skipped(format!("{} contains no text", path)));
+    }
+    let content = sections
+        .iter()
+        .map(|s| match s.section_type {
+            Some(_) => format!("{}\n\n{}", s.header, s.content),
+            None => s.content.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(ParsedDocument {
+        content,
+        sections,
+        doc_type: doc_type.to_string(),
+        metadata,
+    })
+}
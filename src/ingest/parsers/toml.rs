@@ -0,0 +1,261 @@
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+use std::collections::HashMap;
+
+/// TOML parser for configuration files
+///
+/// Emits one section per table, keeping the original lines (comments included).
+/// The header is the dotted key path of the table (`server.tls`); entries of an
+/// array of tables are numbered (`sources[0]`, `sources[1]`). Keys before the first
+/// table header form the `root` section. Comment lines directly above a table header
+/// belong to that table.
+pub struct TomlParser;
+
+impl Parser for TomlParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "toml"
+    }
+
+    fn parse(&self, content: &str, path: &str) -> Result<ParsedDocument> {
+        // Validate first so syntax errors fall back to plain text
+        content
+            .parse::<::toml::Table>()
+            .map_err(|e| RagmcpError::Parse(format!("TOML parse error in {}: {}", path, e)))?;
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut sections = Vec::new();
+        let mut array_counts: HashMap<String, usize> = HashMap::new();
+        let mut header = "root".to_string();
+        let mut start = 0;
+        let mut scanner = ValueScanner::default();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let table = if scanner.in_value() { None } else { table_header(line) };
+            scanner.scan(line);
+            let Some((key_path, is_array)) = table else {
+                continue;
+            };
+
+            // Leading comments belong to the new table
+            let mut split = idx;
+            while split > start && lines[split - 1].trim_start().starts_with('#') {
+                split -= 1;
+            }
+            push_section(&mut sections, &header, &lines[start..split]);
+
+            header = if is_array {
+                let count = array_counts.entry(key_path.clone()).or_insert(0);
+                *count += 1;
+                format!("{}[{}]", key_path, *count - 1)
+            } else {
+                key_path
+            };
+            start = split;
+        }
+        push_section(&mut sections, &header, &lines[start..]);
+
+        if sections.is_empty() {
+            sections.push(Section {
+                header: "content".to_string(),
+                content: content.to_string(),
+                section_type: None,
+                page: None,
+            });
+        }
+
+        Ok(ParsedDocument {
+            content: content.to_string(),
+            sections,
+            doc_type: "toml".to_string(),
+            metadata: None,
+        })
+    }
+}
+
+/// Add a section unless it has no key/value lines
+fn push_section(sections: &mut Vec<Section>, header: &str, lines: &[&str]) {
+    let has_values = lines.iter().map(|l| l.trim()).any(|l| {
+        !l.is_empty() && !l.starts_with('#') && table_header(l).is_none()
+    });
+    if !has_values {
+        return;
+    }
+    sections.push(Section {
+        header: header.to_string(),
+        content: lines.join("\n").trim_matches('\n').to_string(),
+        section_type: Some(header.to_string()),
+        page: None,
+    });
+}
+
+/// Parse `[a.b]` / `[[a.b]]` into the normalized dotted key path and whether it is an array of tables
+fn table_header(line: &str) -> Option<(String, bool)> {
+    let line = strip_comment(line).trim();
+    let (inner, is_array) = if let Some(inner) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+        (inner, true)
+    } else {
+        (line.strip_prefix('[')?.strip_suffix(']')?, false)
+    };
+    let keys: Vec<String> = split_dotted(inner)?
+        .into_iter()
+        .map(|k| k.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .collect();
+    if keys.iter().any(|k| k.is_empty()) {
+        return None;
+    }
+    Some((keys.join("."), is_array))
+}
+
+/// Split a dotted key on dots outside quotes; `None` if it contains characters a key cannot
+fn split_dotted(key: &str) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in key.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '.') => {
+                parts.push(&key[start..i]);
+                start = i + 1;
+            }
+            (None, c) if c.is_alphanumeric() || matches!(c, '_' | '-' | ' ' | '\t') => {}
+            (None, _) => return None,
+        }
+    }
+    parts.push(&key[start..]);
+    Some(parts)
+}
+
+/// Remove a trailing `#` comment outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Tracks multi-line arrays, inline tables and strings so their lines are not
+/// mistaken for table headers
+#[derive(Default)]
+struct ValueScanner {
+    depth: usize,
+    multiline_string: Option<&'static str>,
+}
+
+impl ValueScanner {
+    fn in_value(&self) -> bool {
+        self.depth > 0 || self.multiline_string.is_some()
+    }
+
+    fn scan(&mut self, line: &str) {
+        let is_header = !self.in_value() && table_header(line).is_some();
+        if is_header {
+            return;
+        }
+        let mut rest = line;
+        while !rest.is_empty() {
+            if let Some(delimiter) = self.multiline_string {
+                match rest.find(delimiter) {
+                    Some(end) => {
+                        self.multiline_string = None;
+                        rest = &rest[end + 3..];
+                    }
+                    None => return,
+                }
+                continue;
+            }
+            let Some(c) = rest.chars().next() else { break };
+            if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+                self.multiline_string = Some(if c == '"' { "\"\"\"" } else { "'''" });
+                rest = &rest[3..];
+                continue;
+            }
+            match c {
+                '#' => return,
+                '"' | '\'' => {
+                    // Single-line string: skip to the closing quote
+                    let body = &rest[1..];
+                    let mut escaped = false;
+                    let end = body.char_indices().find(|&(_, ch)| {
+                        let close = ch == c && !escaped;
+                        escaped = c == '"' && ch == '\\' && !escaped;
+                        close
+                    });
+                    rest = match end {
+                        Some((i, _)) => &body[i + 1..],
+                        None => "",
+                    };
+                    continue;
+                }
+                '[' | '{' => self.depth += 1,
+                ']' | '}' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Service configuration
+name = "gateway"
+
+[server]
+port = 8080
+hosts = [
+  "a.example.com",
+  "[b]",
+]
+
+# TLS settings
+[server.tls]
+cert = "cert.pem"   # path
+
+[empty]
+
+[[sources]]
+path = "docs"
+
+[[sources]]
+path = "wiki"
+note = """
+[not.a.table]
+"""
+
+[ "quoted.key" . inner ]
+x = 1
+"#;
+
+    #[test]
+    fn test_toml_sections_per_table() {
+        let doc = TomlParser.parse(CONFIG, "config.toml").unwrap();
+        let headers: Vec<&str> = doc.sections.iter().map(|s| s.header.as_str()).collect();
+
+        assert_eq!(doc.doc_type, "toml");
+        assert_eq!(
+            headers,
+            vec!["root", "server", "server.tls", "sources[0]", "sources[1]", "quoted.key.inner"]
+        );
+        assert!(doc.sections[0].content.starts_with("# Service configuration\nname = \"gateway\""));
+        assert!(doc.sections[1].content.contains("\"[b]\","));
+        assert!(!doc.sections[1].content.contains("TLS settings"));
+        assert!(doc.sections[2].content.starts_with("# TLS settings\n[server.tls]"));
+        assert!(doc.sections[4].content.contains("[not.a.table]"));
+    }
+
+    #[test]
+    fn test_invalid_toml_is_error() {
+        assert!(TomlParser.parse("[server\nport = ", "bad.toml").is_err());
+    }
+}
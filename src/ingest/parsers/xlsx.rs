@@ -0,0 +1,256 @@
+use super::office::{attr, document_properties, for_each_event, table_rows, Package, XmlEvent};
+use super::section_builder::document;
+use super::{Parser, ParsedDocument, Section};
+use crate::error::{Result, RagmcpError};
+
+/// Data rows per section; the header row is repeated in every section
+const ROWS_PER_SECTION: usize = 50;
+
+/// Columns in a worksheet (`A` to `XFD`); references past it come from a corrupt file
+const MAX_COLUMNS: usize = 16384;
+
+/// Excel (XLSX) parser
+///
+/// Produces sections per sheet in groups of up to 50 rows, headed
+/// `Sheet (rows 2-51)`. The first non-empty row of a sheet is treated as the column
+/// header and repeated at the top of every group so each chunk stays readable on its
+/// own. Cells are rendered as `| a | b |` rows with shared strings resolved; formulas
+/// contribute their cached values. Hidden sheets are skipped.
+pub struct XlsxParser;
+
+impl Parser for XlsxParser {
+    fn can_parse(&self, extension: &str) -> bool {
+        extension == "xlsx"
+    }
+
+    fn parse(&self, _content: &str, path: &str) -> Result<ParsedDocument> {
+        Err(RagmcpError::Parse(format!("{} is an Excel workbook and must be parsed from bytes", path)))
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+
+    fn parse_bytes(&self, bytes: &[u8], path: &str) -> Result<ParsedDocument> {
+        let mut package = Package::open(bytes, path)?;
+        let shared_strings = match package.read("xl/sharedStrings.xml")? {
+            Some(xml) => shared_strings(&xml)?,
+            None => Vec::new(),
+        };
+
+        let mut sections = Vec::new();
+        for (sheet_name, part) in sheets(&mut package)? {
+            let Some(xml) = package.read(&part)? else { continue };
+            let rows = sheet_rows(&xml, &shared_strings)?;
+            let Some(((_, header), data)) = rows.split_first() else { continue };
+
+            if data.is_empty() {
+                sections.push(sheet_section(&sheet_name, "", header, &[]));
+            }
+            for group in data.chunks(ROWS_PER_SECTION) {
+                let first = group.first().map_or(0, |(n, _)| *n);
+                let last = group.last().map_or(0, |(n, _)| *n);
+                let range = format!(" (rows {}-{})", first, last);
+                let cells: Vec<Vec<String>> = group.iter().map(|(_, cells)| cells.clone()).collect();
+                sections.push(sheet_section(&sheet_name, &range, header, &cells));
+            }
+        }
+
+        let metadata = document_properties(&mut package, "docProps/core.xml")?;
+        document(sections, path, "xlsx", metadata)
+    }
+}
+
+fn sheet_section(sheet_name: &str, range: &str, header: &[String], rows: &[Vec<String>]) -> Section {
+    let mut all_rows = vec![header.to_vec()];
+    all_rows.extend_from_slice(rows);
+    Section {
+        header: format!("{}{}", sheet_name, range),
+        content: table_rows(&all_rows),
+        section_type: Some("sheet".to_string()),
+        page: None,
+    }
+}
+
+/// Visible sheets as (name, part name) in workbook order
+fn sheets(package: &mut Package) -> Result<Vec<(String, String)>> {
+    let rels = package.relationships("xl/workbook.xml")?;
+    let xml = package.read_required("xl/workbook.xml")?;
+    let mut sheets = Vec::new();
+    for_each_event(&xml, |event| {
+        if let XmlEvent::Start(name, attrs) = event {
+            if name != "sheet" || attr(&attrs, "state").is_some_and(|s| s != "visible") {
+                return;
+            }
+            if let (Some(sheet_name), Some((_, target))) =
+                (attr(&attrs, "name"), attr(&attrs, "r:id").and_then(|id| rels.get(id)))
+            {
+                sheets.push((sheet_name.to_string(), target.clone()));
+            }
+        }
+    })?;
+    Ok(sheets)
+}
+
+/// Shared string table; phonetic runs (`rPh`) are not part of the value
+fn shared_strings(xml: &[u8]) -> Result<Vec<String>> {
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    let mut in_phonetic = false;
+    for_each_event(xml, |event| match event {
+        XmlEvent::Start(name, _) => match name.as_str() {
+            "si" => current.clear(),
+            "rPh" => in_phonetic = true,
+            "t" => in_text = !in_phonetic,
+            _ => {}
+        },
+        XmlEvent::Text(text) if in_text => current.push_str(&text),
+        XmlEvent::End(name) => match name.as_str() {
+            "t" => in_text = false,
+            "rPh" => in_phonetic = false,
+            "si" => strings.push(std::mem::take(&mut current)),
+            _ => {}
+        },
+        _ => {}
+    })?;
+    Ok(strings)
+}
+
+/// Non-empty rows of a worksheet as (row number, cells), trailing empty cells removed
+fn sheet_rows(xml: &[u8], shared_strings: &[String]) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut row_number = 0usize;
+    let mut cell_column = None;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+    for_each_event(xml, |event| match event {
+        XmlEvent::Start(name, attrs) => match name.as_str() {
+            "row" => {
+                row.clear();
+                row_number = attr(&attrs, "r").and_then(|r| r.parse().ok()).unwrap_or(row_number + 1);
+            }
+            "c" => {
+                // A reference past the last column is skipped rather than allocated
+                cell_column = match attr(&attrs, "r") {
+                    Some(reference) => column_index(reference),
+                    None => Some(row.len()),
+                };
+                cell_type = attr(&attrs, "t").unwrap_or("n").to_string();
+                value.clear();
+            }
+            "v" | "t" => in_value = true,
+            _ => {}
+        },
+        XmlEvent::Text(text) if in_value => value.push_str(&text),
+        XmlEvent::End(name) => match name.as_str() {
+            "v" | "t" => in_value = false,
+            "c" => {
+                let text = match cell_type.as_str() {
+                    "s" => value.trim().parse::<usize>().ok().and_then(|i| shared_strings.get(i)).cloned(),
+                    "b" => Some(if value.trim() == "1" { "TRUE" } else { "FALSE" }.to_string()),
+                    _ => Some(value.clone()),
+                };
+                if let Some(column) = cell_column {
+                    if row.len() <= column {
+                        row.resize(column + 1, String::new());
+                    }
+                    row[column] = text.unwrap_or_default().replace('|', "/").replace('\n', " ").trim().to_string();
+                }
+            }
+            "row" => {
+                while row.last().is_some_and(|c| c.is_empty()) {
+                    row.pop();
+                }
+                if !row.is_empty() {
+                    rows.push((row_number, std::mem::take(&mut row)));
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    })?;
+    Ok(rows)
+}
+
+/// Zero-based column of a cell reference (`C7` -> 2), `None` without letters or past `XFD`
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: Vec<u8> = reference.bytes().take_while(u8::is_ascii_alphabetic).collect();
+    if letters.is_empty() {
+        return None;
+    }
+    let number = letters.iter().try_fold(0usize, |acc, b| {
+        acc.checked_mul(26)?
+            .checked_add((b.to_ascii_uppercase() - b'A' + 1) as usize)
+            .filter(|&n| n <= MAX_COLUMNS)
+    })?;
+    Some(number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingest::parsers::office::build_package;
+
+    #[test]
+    fn test_xlsx_sheets_and_row_groups() {
+        let mut sheet = String::from(r#"<worksheet><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row>"#);
+        for r in 2..=56 {
+            sheet.push_str(&format!(r#"<row r="{r}"><c r="A{r}" t="inlineStr"><is><t>svc{r}</t></is></c><c r="C{r}"><v>{r}</v></c></row>"#));
+        }
+        sheet.push_str("</sheetData></worksheet>");
+
+        let bytes = build_package(&[
+            (
+                "xl/workbook.xml",
+                r#"<workbook xmlns:r="r"><sheets><sheet name="Services" sheetId="1" r:id="rId1"/><sheet name="Old" sheetId="2" state="hidden" r:id="rId2"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Type="x/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="x/worksheet" Target="worksheets/sheet2.xml"/></Relationships>"#,
+            ),
+            ("xl/sharedStrings.xml", r#"<sst><si><t>Name</t></si><si><r><t>Own</t></r><r><t>er</t></r><rPh><t>x</t></rPh></si></sst>"#),
+            ("xl/worksheets/sheet1.xml", &sheet),
+            ("xl/worksheets/sheet2.xml", r#"<worksheet><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>hidden</t></is></c></row></sheetData></worksheet>"#),
+        ]);
+        let doc = XlsxParser.parse_bytes(&bytes, "services.xlsx").unwrap();
+
+        assert_eq!(doc.doc_type, "xlsx");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].header, "Services (rows 2-51)");
+        assert!(doc.sections[0].content.starts_with("| Name | Owner |\n| svc2 |  | 2 |"));
+        assert_eq!(doc.sections[1].header, "Services (rows 52-56)");
+        assert!(doc.sections[1].content.starts_with("| Name | Owner |\n| svc52 |"));
+        assert!(!doc.content.contains("hidden"));
+    }
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("C7"), Some(2));
+        assert_eq!(column_index("AA10"), Some(26));
+        assert_eq!(column_index("12"), None);
+        assert_eq!(column_index("XFD1"), Some(MAX_COLUMNS - 1));
+        assert_eq!(column_index("XFE1"), None);
+        assert_eq!(column_index(&format!("{}1", "Z".repeat(40))), None);
+    }
+
+    #[test]
+    fn test_oversized_cell_reference_is_skipped() {
+        let sheet = r#"<worksheet><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>Name</t></is></c><c r="ZZZZZZZ1" t="inlineStr"><is><t>bogus</t></is></c></row><row r="2"><c r="A2" t="inlineStr"><is><t>svc</t></is></c></row></sheetData></worksheet>"#;
+        let bytes = build_package(&[
+            ("xl/workbook.xml", r#"<workbook xmlns:r="r"><sheets><sheet name="S" sheetId="1" r:id="rId1"/></sheets></workbook>"#),
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Type="x/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#,
+            ),
+            ("xl/worksheets/sheet1.xml", sheet),
+        ]);
+        let doc = XlsxParser.parse_bytes(&bytes, "big.xlsx").unwrap();
+
+        assert!(doc.sections[0].content.starts_with("| Name |\n| svc |"));
+        assert!(!doc.content.contains("bogus"));
+    }
+}
@@ -37,6 +37,7 @@ impl Parser for XmlParser {
                                 header: header.clone(),
                                 content,
                                 section_type: Some(section_type.unwrap_or_else(|| header.clone())),
+                                page: None,
                             });
                         }
                     }
@@ -72,6 +73,7 @@ impl Parser for XmlParser {
                                 header: header.clone(),
                                 content: content.trim().to_string(),
                                 section_type: Some(section_type.unwrap_or_else(|| header.clone())),
+                                page: None,
                             });
                         }
                     }
@@ -94,6 +96,7 @@ impl Parser for XmlParser {
                 header: root_tag.unwrap_or_else(|| "root".to_string()),
                 content: content.to_string(),
                 section_type: None,
+                page: None,
             });
         } else {
             // If we have sections but one is still open, finalize it
@@ -103,6 +106,7 @@ impl Parser for XmlParser {
                         header: header.clone(),
                         content: content.trim().to_string(),
                         section_type: Some(section_type.unwrap_or_else(|| header.clone())),
+                        page: None,
                     });
                 }
             }
@@ -118,6 +122,7 @@ impl Parser for XmlParser {
             content: content.to_string(),
             sections,
             doc_type,
+            metadata: None,
         })
     }
 }
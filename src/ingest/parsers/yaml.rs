@@ -33,6 +33,7 @@ impl Parser for YamlParser {
                         header: header.clone(),
                         content,
                         section_type: Some(header),
+                        page: None,
                     });
                 }
             }
@@ -42,6 +43,7 @@ impl Parser for YamlParser {
                     header: "root".to_string(),
                     content: yaml_value_to_text(&yaml_value),
                     section_type: None,
+                    page: None,
                 });
             }
         }
@@ -52,6 +54,7 @@ impl Parser for YamlParser {
                 header: "content".to_string(),
                 content: content.to_string(),
                 section_type: None,
+                page: None,
             });
         }
         
@@ -59,6 +62,7 @@ impl Parser for YamlParser {
             content: content.to_string(),
             sections,
             doc_type: "yaml".to_string(),
+            metadata: None,
         })
     }
 }
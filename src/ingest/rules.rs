@@ -0,0 +1,269 @@
+//! Per-path ingestion overrides (`[[ingest.rules]]`).
+//!
+//! Rules are matched against the document path (relative to rag_folder, or under the mount
+//! prefix for `[[sources]]` files) and the namespace derived from it, and override chunking, parser choice, doc_type, namespace/agent mapping and
+//! embedding for the files they match. `ingest`, `watch` and the document write tools all
+//! resolve a file's settings through `FileSettings::resolve`.
+
+use crate::config::{Config, IngestRule};
+use crate::error::{Result, RagmcpError};
+use crate::ingest::chunker::ChunkStrategy;
+use crate::ingest::metadata::{extract_agent_name, extract_namespace};
+use globset::{GlobBuilder, GlobMatcher};
+
+/// Effective ingestion settings for one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSettings {
+    /// Extension whose parser reads the file
+    pub parser_extension: String,
+    /// doc_type stored instead of the parser's
+    pub doc_type: Option<String>,
+    pub namespace: String,
+    pub agent_name: Option<String>,
+    pub chunk_size_tokens: usize,
+    pub chunk_overlap_tokens: usize,
+    pub chunk_strategy: ChunkStrategy,
+    /// Whether `embed` and the watcher embed the file's chunks
+    pub embed: bool,
+}
+
+impl FileSettings {
+    /// Settings for the file at `relative_path` after applying `[[ingest.rules]]`
+    pub fn resolve(relative_path: &str, extension: &str, config: &Config) -> Result<Self> {
+        IngestRules::new(&config.ingest.rules)?.settings_for(relative_path, extension, config)
+    }
+}
+
+/// `[[ingest.rules]]` with their globs compiled
+pub struct IngestRules<'a> {
+    rules: Vec<(&'a IngestRule, Option<GlobMatcher>)>,
+}
+
+impl<'a> IngestRules<'a> {
+    /// Compile and validate the rules
+    pub fn new(rules: &'a [IngestRule]) -> Result<Self> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for (idx, rule) in rules.iter().enumerate() {
+            let invalid = |msg: String| RagmcpError::Config(format!("ingest.rules[{}]: {}", idx, msg));
+            let glob = match &rule.glob {
+                Some(pattern) => Some(
+                    GlobBuilder::new(pattern)
+                        .literal_separator(true)
+                        .build()
+                        .map_err(|e| invalid(format!("invalid glob '{}': {}", pattern, e)))?
+                        .compile_matcher(),
+                ),
+                None => None,
+            };
+            if let Some(name) = &rule.chunk_strategy {
+                if ChunkStrategy::from_name(name).is_none() {
+                    return Err(invalid(format!("unknown chunk_strategy '{}'", name)));
+                }
+            }
+            if rule.chunk_size_tokens == Some(0) {
+                return Err(invalid("chunk_size_tokens must be greater than 0".to_string()));
+            }
+            if let (Some(size), Some(overlap)) = (rule.chunk_size_tokens, rule.chunk_overlap_tokens) {
+                if overlap >= size {
+                    return Err(invalid("chunk_overlap_tokens must be less than chunk_size_tokens".to_string()));
+                }
+            }
+            compiled.push((rule, glob));
+        }
+        Ok(Self { rules: compiled })
+    }
+
+    /// Settings for `relative_path`: the global settings overridden by every matching rule
+    pub fn settings_for(&self, relative_path: &str, extension: &str, config: &Config) -> Result<FileSettings> {
+        let path = relative_path.replace('\\', "/");
+        let path_namespace = extract_namespace(&path);
+        let mut settings = FileSettings {
+            parser_extension: extension.to_lowercase(),
+            doc_type: None,
+            namespace: path_namespace.clone(),
+            agent_name: extract_agent_name(&path),
+            chunk_size_tokens: config.performance.chunk_size_tokens,
+            chunk_overlap_tokens: config.performance.chunk_overlap_tokens,
+            chunk_strategy: ChunkStrategy::Fixed,
+            embed: true,
+        };
+        let mut strategy = None;
+
+        for (rule, glob) in &self.rules {
+            if glob.as_ref().is_some_and(|g| !g.is_match(&path)) {
+                continue;
+            }
+            if rule.match_namespace.as_ref().is_some_and(|ns| !ns.eq_ignore_ascii_case(&path_namespace)) {
+                continue;
+            }
+            if let Some(size) = rule.chunk_size_tokens {
+                settings.chunk_size_tokens = size;
+            }
+            if let Some(overlap) = rule.chunk_overlap_tokens {
+                settings.chunk_overlap_tokens = overlap;
+            }
+            if let Some(name) = &rule.chunk_strategy {
+                strategy = ChunkStrategy::from_name(name);
+            }
+            if let Some(parser) = &rule.parser {
+                settings.parser_extension = parser.trim_start_matches('.').to_lowercase();
+            }
+            if let Some(doc_type) = &rule.doc_type {
+                settings.doc_type = Some(doc_type.clone());
+            }
+            if let Some(namespace) = &rule.namespace {
+                settings.namespace = namespace.clone();
+            }
+            if let Some(agent_name) = &rule.agent_name {
+                settings.agent_name = Some(agent_name.clone()).filter(|a| !a.is_empty());
+            }
+            if let Some(embed) = rule.embed {
+                settings.embed = embed;
+            }
+        }
+
+        // A rule may shrink the chunk size below the global overlap
+        if settings.chunk_overlap_tokens >= settings.chunk_size_tokens {
+            settings.chunk_overlap_tokens = settings.chunk_size_tokens / 2;
+        }
+        let strategy_name = config.performance.chunk_strategy_for(&settings.parser_extension);
+        settings.chunk_strategy = strategy
+            .or_else(|| ChunkStrategy::from_name(strategy_name))
+            .ok_or_else(|| RagmcpError::Config(format!("unknown chunk strategy '{}'", strategy_name)))?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rules: &str) -> Config {
+        toml::from_str(&format!(
+            r#"
+[ragmcp]
+rag_folder = "."
+db_path = "./test.db"
+
+[embeddings]
+provider = "openai"
+model = "text-embedding-3-small"
+api_key_env = "OPENAI_API_KEY"
+batch_size = 100
+dimensions = 1536
+
+[search]
+default_k = 5
+min_score = 0.65
+hybrid_bm25_weight = 0.5
+hybrid_vector_weight = 0.5
+
+[performance]
+max_latency_ms = 1000
+chunk_size_tokens = 300
+chunk_overlap_tokens = 50
+
+{}
+"#,
+            rules
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_override_in_order() {
+        let config = config(
+            r#"
+[[ingest.rules]]
+glob = "configs/**/*.yaml"
+chunk_size_tokens = 40
+chunk_strategy = "structured"
+doc_type = "config"
+embed = false
+
+[[ingest.rules]]
+glob = "configs/gateway/*.yaml"
+namespace = "platform"
+agent_name = "gateway"
+chunk_overlap_tokens = 10
+
+[[ingest.rules]]
+match_namespace = "manuals"
+chunk_size_tokens = 800
+parser = ".md"
+"#,
+        );
+
+        let yaml = FileSettings::resolve("configs/gateway/routes.yaml", "yaml", &config).unwrap();
+        assert_eq!(yaml.chunk_size_tokens, 40);
+        assert_eq!(yaml.chunk_overlap_tokens, 10);
+        assert_eq!(yaml.chunk_strategy, ChunkStrategy::Structured);
+        assert_eq!(yaml.doc_type.as_deref(), Some("config"));
+        assert_eq!((yaml.namespace.as_str(), yaml.agent_name.as_deref()), ("platform", Some("gateway")));
+        assert!(!yaml.embed);
+
+        // `*` does not cross directories; the overlap is clamped below the smaller size
+        let nested = FileSettings::resolve("configs/gateway/old/routes.yaml", "yaml", &config).unwrap();
+        assert_eq!((nested.namespace.as_str(), nested.chunk_overlap_tokens), ("configs", 20));
+
+        let manual = FileSettings::resolve("Manuals/install.txt", "txt", &config).unwrap();
+        assert_eq!((manual.chunk_size_tokens, manual.parser_extension.as_str()), (800, "md"));
+        assert!(manual.embed);
+
+        let other = FileSettings::resolve("readme.md", "md", &config).unwrap();
+        assert_eq!(other.chunk_size_tokens, 300);
+        assert_eq!(other.chunk_strategy, ChunkStrategy::Fixed);
+        assert_eq!(other.namespace, "all");
+    }
+
+    #[test]
+    fn test_rules_match_prefixed_source_paths() {
+        use crate::ingest::sources::Source;
+        use std::path::PathBuf;
+
+        let config = config(
+            r#"
+[[ingest.rules]]
+glob = "configs/**/*.yaml"
+doc_type = "config"
+
+[[ingest.rules]]
+glob = "wiki/configs/**/*.yaml"
+chunk_size_tokens = 80
+
+[[ingest.rules]]
+match_namespace = "wiki"
+embed = false
+"#,
+        );
+        let wiki = Source {
+            prefix: "wiki".to_string(),
+            root: PathBuf::from("/srv/wiki"),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            read_only: false,
+            mounted: Vec::new(),
+        };
+
+        // Rules see the document path, so the prefix is part of what globs match
+        let settings = FileSettings::resolve(&wiki.doc_path("configs/app.yaml"), "yaml", &config).unwrap();
+        assert_eq!(settings.doc_type, None);
+        assert_eq!(settings.chunk_size_tokens, 80);
+        assert_eq!(settings.namespace, "wiki");
+        assert!(!settings.embed);
+
+        let local = FileSettings::resolve("configs/app.yaml", "yaml", &config).unwrap();
+        assert_eq!((local.doc_type.as_deref(), local.chunk_size_tokens), (Some("config"), 300));
+        assert!(local.embed);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let bad_glob = config("[[ingest.rules]]\nglob = \"configs/[\"");
+        assert!(IngestRules::new(&bad_glob.ingest.rules).is_err());
+        let bad_strategy = config("[[ingest.rules]]\nchunk_strategy = \"paragraph\"");
+        assert!(IngestRules::new(&bad_strategy.ingest.rules).is_err());
+        let bad_overlap = config("[[ingest.rules]]\nchunk_size_tokens = 50\nchunk_overlap_tokens = 50");
+        assert!(IngestRules::new(&bad_overlap.ingest.rules).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Document source roots (`rag_folder` and `[[sources]]`).
+//!
+//! Every source is a directory mounted under a prefix: a file `guide.md` in a source
+//! with prefix `wiki` is stored as document `wiki/guide.md`, so the prefix is also its
+//! namespace. `rag_folder` is the source with the empty prefix; its top-level
+//! directories named like a mount prefix are left to the mounted source.
+
+use crate::config::IngestConfig;
+use crate::error::{Result, RagmcpError};
+use crate::ingest::walker::{discover_files, Discovery};
+use std::path::{Path, PathBuf};
+
+/// One document root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    /// Mount prefix of its document paths ("" for rag_folder)
+    pub prefix: String,
+    pub root: PathBuf,
+    /// Globs relative to `root`, in addition to `[ingest] include`/`exclude`
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Reject writes from ragmcp_create_doc / ragmcp_update_doc
+    pub read_only: bool,
+    /// Mount prefixes of the other sources (rag_folder only), skipped during discovery
+    pub(crate) mounted: Vec<String>,
+}
+
+impl Source {
+    /// Name for logs: the prefix, or "rag_folder"
+    pub fn name(&self) -> &str {
+        if self.prefix.is_empty() {
+            "rag_folder"
+        } else {
+            &self.prefix
+        }
+    }
+
+    /// Whether the root is currently reachable (shared drives may be unmounted)
+    pub fn is_available(&self) -> bool {
+        self.root.is_dir()
+    }
+
+    /// Document path of the file at `relative_path` below the root
+    pub fn doc_path(&self, relative_path: &str) -> String {
+        if self.prefix.is_empty() {
+            relative_path.to_string()
+        } else {
+            format!("{}/{}", self.prefix, relative_path)
+        }
+    }
+
+    /// Path below the root of a document path under this source's prefix
+    ///
+    /// For rag_folder, paths under another source's prefix are not its own.
+    pub fn relative_path<'a>(&self, doc_path: &'a str) -> Option<&'a str> {
+        if self.prefix.is_empty() {
+            let top = doc_path.split('/').next().unwrap_or(doc_path);
+            return (!self.mounted.iter().any(|prefix| prefix == top)).then_some(doc_path);
+        }
+        doc_path
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_prefix('/'))
+    }
+
+    /// `[ingest]` discovery settings for this source: its include globs replace the
+    /// global ones, its exclude globs are added to them
+    pub fn ingest_config(&self, base: &IngestConfig) -> IngestConfig {
+        let mut config = base.clone();
+        if !self.include.is_empty() {
+            config.include = self.include.clone();
+        }
+        config.exclude.extend(self.exclude.iter().cloned());
+        config.exclude.extend(self.mounted.iter().map(|prefix| format!("{}/**", prefix)));
+        config
+    }
+
+    /// Discover the files of this source, with document paths under its prefix
+    pub fn discover(&self, base: &IngestConfig) -> Result<Discovery> {
+        let mut discovery = discover_files(&self.root, &self.ingest_config(base))?;
+        for file in &mut discovery.files {
+            file.relative_path = self.doc_path(&file.relative_path);
+        }
+        for skipped in &mut discovery.skipped {
+            skipped.relative_path = self.doc_path(&skipped.relative_path);
+        }
+        Ok(discovery)
+    }
+}
+
+/// Source owning `doc_path`: the one with the longest matching prefix
+pub fn source_for_doc_path<'a>(sources: &'a [Source], doc_path: &str) -> Option<&'a Source> {
+    sources
+        .iter()
+        .filter(|source| source.relative_path(doc_path).is_some())
+        .max_by_key(|source| source.prefix.len())
+}
+
+/// Source whose root contains `absolute_path`
+pub fn source_for_file<'a>(sources: &'a [Source], absolute_path: &Path) -> Option<&'a Source> {
+    let path = absolute_path.canonicalize().ok()?;
+    sources.iter().find(|source| {
+        source
+            .root
+            .canonicalize()
+            .is_ok_and(|root| path.starts_with(root))
+    })
+}
+
+/// Documents among `deleted` that may be removed: those of sources that are
+/// unavailable (e.g. an unmounted share) are kept, documents of sources removed
+/// from the config are not
+pub fn deletable_documents(sources: &[Source], deleted: Vec<String>) -> Vec<String> {
+    deleted
+        .into_iter()
+        .filter(|doc_path| source_for_doc_path(sources, doc_path).map_or(true, Source::is_available))
+        .collect()
+}
+
+/// Validate a mount prefix: one non-empty path segment
+pub(crate) fn validate_prefix(prefix: &str) -> Result<()> {
+    let invalid = prefix.is_empty()
+        || prefix.contains(['/', '\\', '#'])
+        || prefix == "."
+        || prefix == ".."
+        || prefix.trim() != prefix;
+    if invalid {
+        return Err(RagmcpError::Config(format!(
+            "sources: prefix '{}' must be a single directory name (no '/', '\\\\' or '#')",
+            prefix
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn source(prefix: &str, root: &Path) -> Source {
+        Source {
+            prefix: prefix.to_string(),
+            root: root.to_path_buf(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            read_only: false,
+            mounted: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_doc_paths_and_ownership() {
+        let main = TempDir::new().unwrap();
+        let wiki = TempDir::new().unwrap();
+        let sources = vec![
+            Source { mounted: vec!["wiki".to_string()], ..source("", main.path()) },
+            source("wiki", wiki.path()),
+            source("wiki-old", Path::new("/nonexistent/ragmcp-share")),
+        ];
+
+        assert_eq!(sources[1].doc_path("team/guide.md"), "wiki/team/guide.md");
+        assert_eq!(sources[1].relative_path("wiki/team/guide.md"), Some("team/guide.md"));
+        assert_eq!(sources[1].relative_path("wiki-old/guide.md"), None);
+        assert_eq!(source_for_doc_path(&sources, "wiki/guide.md").unwrap().name(), "wiki");
+        assert_eq!(source_for_doc_path(&sources, "wiki-old/guide.md").unwrap().name(), "wiki-old");
+        assert_eq!(source_for_doc_path(&sources, "guides/setup.md").unwrap().name(), "rag_folder");
+
+        fs::write(wiki.path().join("page.md"), "# Page").unwrap();
+        let owner = source_for_file(&sources, &wiki.path().join("page.md")).unwrap();
+        assert_eq!(owner.name(), "wiki");
+
+        // The unmounted share keeps its documents
+        let deleted = vec!["wiki/gone.md".to_string(), "wiki-old/guide.md".to_string(), "old.md".to_string()];
+        assert_eq!(deletable_documents(&sources, deleted), vec!["wiki/gone.md", "old.md"]);
+    }
+
+    #[test]
+    fn test_discover_mounted_sources() {
+        let main = TempDir::new().unwrap();
+        let wiki = TempDir::new().unwrap();
+        fs::create_dir_all(main.path().join("wiki")).unwrap();
+        fs::write(main.path().join("readme.md"), "# Readme").unwrap();
+        fs::write(main.path().join("wiki/shadowed.md"), "# Shadowed").unwrap();
+        fs::create_dir_all(wiki.path().join("team/drafts")).unwrap();
+        fs::write(wiki.path().join("team/guide.md"), "# Guide").unwrap();
+        fs::write(wiki.path().join("team/drafts/wip.md"), "# WIP").unwrap();
+
+        let main_source = Source { mounted: vec!["wiki".to_string()], ..source("", main.path()) };
+        let wiki_source = Source { exclude: vec!["**/drafts/**".to_string()], ..source("wiki", wiki.path()) };
+        let config = IngestConfig::default();
+
+        let main_files: Vec<String> =
+            main_source.discover(&config).unwrap().files.into_iter().map(|f| f.relative_path).collect();
+        assert_eq!(main_files, vec!["readme.md"]);
+
+        let discovery = wiki_source.discover(&config).unwrap();
+        let wiki_files: Vec<&str> = discovery.files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(wiki_files, vec!["wiki/team/guide.md"]);
+        assert!(discovery.skipped.iter().any(|s| s.relative_path == "wiki/team/drafts/"));
+    }
+
+    #[test]
+    fn test_validate_prefix() {
+        assert!(validate_prefix("wiki").is_ok());
+        assert!(validate_prefix("team-docs").is_ok());
+        for bad in ["", "a/b", "..", "wiki#1", " wiki"] {
+            assert!(validate_prefix(bad).is_err(), "{:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::config::EmbeddingsConfig;
+use crate::error::{Result, RagmcpError};
+use tiktoken_rs::CoreBPE;
+
+/// Token counter used for chunk boundaries and `content_tokens`/`chunk_tokens`
+///
+/// BPE vocabularies (cl100k, o200k) are bundled with `tiktoken-rs` and loaded once per
+/// process. `Chars` keeps the old ~4 characters per token approximation.
+#[derive(Clone, Copy)]
+pub enum Tokenizer {
+    Bpe(&'static str, &'static CoreBPE),
+    Chars,
+}
+
+impl std::fmt::Debug for Tokenizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Tokenizer {
+    /// Tokenizer by name: `cl100k_base`, `o200k_base` or `chars`
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "cl100k_base" => Ok(Self::Bpe("cl100k_base", tiktoken_rs::cl100k_base_singleton())),
+            "o200k_base" => Ok(Self::Bpe("o200k_base", tiktoken_rs::o200k_base_singleton())),
+            "chars" => Ok(Self::Chars),
+            other => Err(RagmcpError::Config(format!(
+                "unknown tokenizer '{}' (expected auto, cl100k_base, o200k_base or chars)",
+                other
+            ))),
+        }
+    }
+
+    /// Tokenizer of an OpenAI model; other models use cl100k, which is far closer than
+    /// a character estimate for code and non-English text
+    pub fn for_model(model: &str) -> Self {
+        use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer as Encoding};
+        match get_tokenizer(model) {
+            Some(Encoding::O200kBase) => Self::Bpe("o200k_base", tiktoken_rs::o200k_base_singleton()),
+            Some(Encoding::Cl100kBase) => Self::Bpe("cl100k_base", tiktoken_rs::cl100k_base_singleton()),
+            _ => {
+                log::debug!("No tokenizer known for model '{}', using cl100k_base", model);
+                Self::Bpe("cl100k_base", tiktoken_rs::cl100k_base_singleton())
+            }
+        }
+    }
+
+    /// Tokenizer selected by `[embeddings] tokenizer` ("auto" follows `model`)
+    pub fn from_config(config: &EmbeddingsConfig) -> Result<Self> {
+        match config.tokenizer.as_str() {
+            "auto" | "" => Ok(Self::for_model(&config.model)),
+            name => Self::from_name(name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bpe(name, _) => name,
+            Self::Chars => "chars",
+        }
+    }
+
+    /// Number of tokens in `text`
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            Self::Bpe(_, bpe) => bpe.encode_ordinary(text).len(),
+            Self::Chars => super::chunker::estimate_tokens(text),
+        }
+    }
+
+    /// Positions where `text` can be cut: `(byte offset, tokens before it)`, starting at
+    /// `(0, 0)` and ending at `(text.len(), count)`
+    ///
+    /// A BPE token may end inside a multi-byte character (CJK, emoji); such positions are
+    /// skipped so every offset is a character boundary.
+    pub fn boundaries(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut boundaries = vec![(0, 0)];
+        match self {
+            Self::Bpe(_, bpe) => {
+                // Tokens are decoded through the public `decode`, which only accepts whole
+                // characters: a token ending inside a character is decoded together with
+                // the following ones until they complete it
+                let mut offset = 0;
+                let mut pending = Vec::new();
+                for (idx, token) in bpe.encode_ordinary(text).into_iter().enumerate() {
+                    pending.push(token);
+                    if let Ok(decoded) = bpe.decode(pending.clone()) {
+                        offset += decoded.len();
+                        pending.clear();
+                        boundaries.push((offset, idx + 1));
+                    }
+                }
+            }
+            Self::Chars => {
+                for (offset, c) in text.char_indices() {
+                    let end = offset + c.len_utf8();
+                    boundaries.push((end, (end + 3) / 4));
+                }
+            }
+        }
+        // Guard against a tokenizer that does not reproduce the input byte for byte
+        if boundaries.last().map(|b| b.0) != Some(text.len()) {
+            let count = self.count(text);
+            boundaries.retain(|b| b.0 < text.len());
+            boundaries.push((text.len(), count));
+        }
+        boundaries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bpe_counts() {
+        let cl100k = Tokenizer::from_name("cl100k_base").unwrap();
+        assert_eq!(cl100k.count("hello world"), 2);
+        assert_eq!(cl100k.count(""), 0);
+        // A character estimate undercounts CJK text badly
+        let cjk = "検索拡張生成は文書を小さな断片に分割します";
+        assert!(cl100k.count(cjk) > Tokenizer::Chars.count(cjk));
+
+        assert!(Tokenizer::from_name("gpt2").is_err());
+        assert_eq!(Tokenizer::for_model("text-embedding-3-small").name(), "cl100k_base");
+        assert_eq!(Tokenizer::for_model("gpt-4o").name(), "o200k_base");
+        assert_eq!(Tokenizer::for_model("nomic-embed-text").name(), "cl100k_base");
+    }
+
+    #[test]
+    fn test_boundaries_are_char_boundaries() {
+        let text = "naïve 検索 🦀 code::path()";
+        for tokenizer in [Tokenizer::from_name("o200k_base").unwrap(), Tokenizer::Chars] {
+            let boundaries = tokenizer.boundaries(text);
+            assert_eq!(boundaries.first(), Some(&(0, 0)));
+            assert_eq!(boundaries.last(), Some(&(text.len(), tokenizer.count(text))));
+            assert!(boundaries.iter().all(|(offset, _)| text.is_char_boundary(*offset)));
+            assert!(boundaries.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1));
+        }
+    }
+}
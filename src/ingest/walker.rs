@@ -1,6 +1,28 @@
 use walkdir::WalkDir;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
 use std::path::{Path, PathBuf};
-use crate::error::Result;
+use crate::config::IngestConfig;
+use crate::error::{Result, RagmcpError};
+
+/// File extensions the parsers can ingest (lowercase)
+///
+/// - Documentation / markup: `md`, `txt`, `rst`, `adoc`, `asciidoc`, `xml`, `html`, `htm`, `ipynb`
+/// - Binary documents (text extracted by the parser): `pdf`, `docx`, `odt`, `pptx`, `xlsx`
+/// - E-mail (one document per message): `eml`, `mbox`
+/// - Data / configuration: `yaml`, `yml`, `json`, `toml`, `csv`, `tsv`
+/// - Source code (optional — useful for code-knowledge RAG): `rs`, `py`, `ts`, `js`, `go`
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "md", "txt", "rst", "adoc", "asciidoc", "xml", "html", "htm", "ipynb",
+    "pdf", "docx", "odt", "pptx", "xlsx",
+    "eml", "mbox",
+    "yaml", "yml", "json", "toml", "csv", "tsv",
+    "rs", "py", "ts", "js", "go",
+];
+
+/// Ignore files read in every directory, highest precedence first
+const IGNORE_FILES: &[&str] = &[".ragignore", ".gitignore"];
 
 /// Metadata for a discovered file
 #[derive(Debug, Clone)]
@@ -12,72 +34,302 @@ pub struct FileMetadata {
     pub modified: std::time::SystemTime,
 }
 
-/// Discover all relevant files in the configured rag_folder (docs root directory).
+/// Why discovery left out a file or directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Name starts with `.` (or is `.git`)
+    Hidden,
+    /// Matched a pattern in this ignore file (`.gitignore` or `.ragignore`)
+    IgnoreFile(&'static str),
+    /// Matched an `[ingest] exclude` glob
+    Excluded,
+    /// Matched none of the `[ingest] include` globs
+    NotIncluded,
+    /// Larger than `[ingest] max_file_size`
+    TooLarge { size: u64, limit: u64 },
+    /// No parser for the extension
+    UnsupportedExtension,
+}
+
+impl SkipReason {
+    /// Short label for grouping skip counts
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkipReason::Hidden => "hidden",
+            SkipReason::IgnoreFile(_) => "ignored",
+            SkipReason::Excluded => "excluded",
+            SkipReason::NotIncluded => "not included",
+            SkipReason::TooLarge { .. } => "too large",
+            SkipReason::UnsupportedExtension => "unsupported type",
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::Hidden => write!(f, "hidden"),
+            SkipReason::IgnoreFile(name) => write!(f, "matched {}", name),
+            SkipReason::Excluded => write!(f, "matched an exclude glob"),
+            SkipReason::NotIncluded => write!(f, "matched no include glob"),
+            SkipReason::TooLarge { size, limit } => {
+                write!(f, "{} bytes exceeds max_file_size of {} bytes", size, limit)
+            }
+            SkipReason::UnsupportedExtension => write!(f, "unsupported file type"),
+        }
+    }
+}
+
+/// A file or directory (relative path ending in `/`) left out by discovery
+#[derive(Debug, Clone)]
+pub struct SkippedPath {
+    pub relative_path: String,
+    pub reason: SkipReason,
+}
+
+/// Files to ingest plus everything discovery skipped
+#[derive(Debug, Default)]
+pub struct Discovery {
+    pub files: Vec<FileMetadata>,
+    /// Skipped directories are listed once, not per file
+    pub skipped: Vec<SkippedPath>,
+}
+
+/// Include/exclude globs, size limit and hidden/ignore-file handling from `[ingest]`
 ///
-/// Recursively walks the entire directory tree and indexes all common text-based
-/// document formats. Binary files and unknown extensions are automatically skipped.
+/// Shared by `discover_files` and the watcher so both skip the same files.
+#[derive(Debug, Clone)]
+pub struct DiscoveryFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    max_file_size: u64,
+    skip_hidden: bool,
+    use_ignore_files: bool,
+}
+
+/// Ignore files of one directory, `depth` levels below the root
+struct IgnoreLevel {
+    depth: usize,
+    matchers: Vec<(&'static str, Gitignore)>,
+}
+
+impl DiscoveryFilter {
+    pub fn from_config(config: &IngestConfig) -> Result<Self> {
+        let include = if config.include.is_empty() {
+            None
+        } else {
+            Some(build_globset("ingest.include", &config.include)?)
+        };
+        Ok(Self {
+            include,
+            exclude: build_globset("ingest.exclude", &config.exclude)?,
+            max_file_size: config.max_file_size,
+            skip_hidden: config.skip_hidden,
+            use_ignore_files: config.use_ignore_files,
+        })
+    }
+
+    /// Why the entry at `relative_path` (below the root, `/`-separated) is skipped,
+    /// if it is; `size` is only checked for files
+    fn skip_reason(
+        &self,
+        absolute_path: &Path,
+        relative_path: &str,
+        is_dir: bool,
+        size: u64,
+        levels: &[IgnoreLevel],
+    ) -> Option<SkipReason> {
+        let name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+        if name == ".git" || (self.skip_hidden && name.starts_with('.')) {
+            return Some(SkipReason::Hidden);
+        }
+        if let Some(ignore_file) = ignored_by(levels, absolute_path, is_dir) {
+            return Some(SkipReason::IgnoreFile(ignore_file));
+        }
+        if self.exclude.is_match(relative_path) || (is_dir && self.exclude.is_match(format!("{}/", relative_path))) {
+            return Some(SkipReason::Excluded);
+        }
+        if is_dir {
+            return None;
+        }
+        if !SUPPORTED_EXTENSIONS.contains(&extension_of(absolute_path).as_str()) {
+            return Some(SkipReason::UnsupportedExtension);
+        }
+        if self.include.as_ref().is_some_and(|include| !include.is_match(relative_path)) {
+            return Some(SkipReason::NotIncluded);
+        }
+        if self.max_file_size > 0 && size > self.max_file_size {
+            return Some(SkipReason::TooLarge { size, limit: self.max_file_size });
+        }
+        None
+    }
+
+    /// Why the file at `absolute_path` under `root` is skipped, checking every
+    /// directory on the way down (used for single files, e.g. by the watcher)
+    pub fn check_file(&self, root: &Path, absolute_path: &Path, size: u64) -> Option<SkipReason> {
+        let relative = absolute_path.strip_prefix(root).ok()?;
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        let mut levels = Vec::new();
+        self.push_ignore_level(&mut levels, root, 0);
+        let mut dir = root.to_path_buf();
+        for (idx, component) in components.iter().enumerate() {
+            dir.push(component);
+            let relative_path = components[..=idx].join("/");
+            let is_dir = idx + 1 < components.len();
+            if let Some(reason) = self.skip_reason(&dir, &relative_path, is_dir, size, &levels) {
+                return Some(reason);
+            }
+            if is_dir {
+                self.push_ignore_level(&mut levels, &dir, idx + 1);
+            }
+        }
+        None
+    }
+
+    fn push_ignore_level(&self, levels: &mut Vec<IgnoreLevel>, dir: &Path, depth: usize) {
+        if !self.use_ignore_files {
+            return;
+        }
+        let matchers: Vec<(&'static str, Gitignore)> = IGNORE_FILES
+            .iter()
+            .filter_map(|name| {
+                let path = dir.join(name);
+                if !path.is_file() {
+                    return None;
+                }
+                let mut builder = GitignoreBuilder::new(dir);
+                if let Some(e) = builder.add(&path) {
+                    log::warn!("{}: {}", path.display(), e);
+                }
+                match builder.build() {
+                    Ok(gitignore) => Some((*name, gitignore)),
+                    Err(e) => {
+                        log::warn!("{}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect();
+        if !matchers.is_empty() {
+            levels.push(IgnoreLevel { depth, matchers });
+        }
+    }
+}
+
+fn build_globset(key: &str, patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| RagmcpError::Config(format!("{}: invalid glob '{}': {}", key, pattern, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map_err(|e| RagmcpError::Config(format!("{}: {}", key, e)))
+}
+
+/// Ignore file deciding that `path` is ignored; the deepest directory decides, and a
+/// negated (`!pattern`) match re-includes the path
+fn ignored_by(levels: &[IgnoreLevel], path: &Path, is_dir: bool) -> Option<&'static str> {
+    for level in levels.iter().rev() {
+        for (name, gitignore) in &level.matchers {
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return Some(name),
+                Match::Whitelist(_) => return None,
+                Match::None => {}
+            }
+        }
+    }
+    None
+}
+
+fn extension_of(path: &Path) -> String {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// Discover all relevant files in the configured rag_folder (docs root directory).
 ///
-/// **Supported extensions** (case-insensitive):
-/// - Documents: `.md`, `.txt`, `.xml`
-/// - Data: `.yaml`, `.yml`, `.json`, `.toml`
-/// - Code (optional — index your source too): `.rs`, `.py`, `.ts`, `.js`, `.go`
-pub fn discover_files(root: &Path) -> Result<Vec<FileMetadata>> {
-    let mut files = Vec::new();
-    
-    for entry in WalkDir::new(root)
+/// Recursively walks the entire directory tree and collects files with one of the
+/// `SUPPORTED_EXTENSIONS`. Hidden entries, paths matched by `.gitignore`/`.ragignore`
+/// or the `[ingest]` exclude globs, files outside the include globs and files above
+/// `max_file_size` are skipped and reported with their reason.
+pub fn discover_files(root: &Path, config: &IngestConfig) -> Result<Discovery> {
+    let filter = DiscoveryFilter::from_config(config)?;
+    let mut discovery = Discovery::default();
+    let mut levels: Vec<IgnoreLevel> = Vec::new();
+    filter.push_ignore_level(&mut levels, root, 0);
+
+    let mut entries = WalkDir::new(root)
         .follow_links(true)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
+        .min_depth(1)
+        .into_iter();
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else { continue };
         let path = entry.path();
-        
-        if !path.is_file() {
-            continue;
-        }
-        
-        let extension = path
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_lowercase();
-        
-        // Filter for relevant text-based file types; binary files are skipped.
-        // Add more extensions here if you want to index additional file types.
-        if !matches!(
-            extension.as_str(),
-            // Documentation / markup
-            "md" | "txt" | "xml" |
-            // Data / configuration
-            "yaml" | "yml" | "json" | "toml" |
-            // Source code (optional — useful for code-knowledge RAG)
-            "rs" | "py" | "ts" | "js" | "go"
-        ) {
-            continue;
+        let depth = entry.depth();
+        let is_dir = entry.file_type().is_dir();
+
+        // Leave the ignore files of directories the walk has left
+        while levels.last().is_some_and(|level| level.depth >= depth) {
+            levels.pop();
         }
-        
-        let metadata = std::fs::metadata(path)
-            .map_err(crate::error::RagmcpError::Io)?;
-        
+
         let relative_path = path
             .strip_prefix(root)
-            .map_err(|_| crate::error::RagmcpError::Config(
+            .map_err(|_| RagmcpError::Config(
                 format!("Failed to compute relative path for: {}", path.display())
             ))?
             .to_string_lossy()
-            .to_string();
-        
-        files.push(FileMetadata {
+            .replace('\\', "/");
+
+        let size = if is_dir {
+            0
+        } else {
+            std::fs::metadata(path).map_err(RagmcpError::Io)?.len()
+        };
+        if let Some(reason) = filter.skip_reason(path, &relative_path, is_dir, size, &levels) {
+            if is_dir {
+                entries.skip_current_dir();
+                discovery.skipped.push(SkippedPath { relative_path: format!("{}/", relative_path), reason });
+            } else {
+                discovery.skipped.push(SkippedPath { relative_path, reason });
+            }
+            continue;
+        }
+        if is_dir {
+            filter.push_ignore_level(&mut levels, path, depth);
+            continue;
+        }
+
+        let metadata = std::fs::metadata(path)
+            .map_err(RagmcpError::Io)?;
+
+        discovery.files.push(FileMetadata {
             relative_path,
             absolute_path: path.to_path_buf(),
-            extension,
+            extension: extension_of(path),
             file_size: metadata.len(),
             modified: metadata.modified()
-                .map_err(crate::error::RagmcpError::Io)?,
+                .map_err(RagmcpError::Io)?,
         });
     }
-    
-    log::info!("Discovered {} files in {}", files.len(), root.display());
-    Ok(files)
+
+    log::info!(
+        "Discovered {} files in {} ({} paths skipped)",
+        discovery.files.len(),
+        root.display(),
+        discovery.skipped.len()
+    );
+    Ok(discovery)
 }
 
 #[cfg(test)]
@@ -85,12 +337,12 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
     use std::fs;
-    
+
     #[test]
     fn test_discover_files() {
         let temp_dir = TempDir::new().unwrap();
         let root = temp_dir.path();
-        
+
         // Create a generic multi-level directory structure
         fs::create_dir_all(root.join("Guides/api")).unwrap();
         fs::write(root.join("overview.xml"), "<doc></doc>").unwrap();
@@ -100,9 +352,9 @@ mod tests {
         fs::write(root.join("notes.txt"), "plain text note").unwrap();
         fs::write(root.join("Guides/api/endpoints.md"), "# API endpoints").unwrap();
         fs::write(root.join("image.png"), b"\x89PNG\r\n\x1a\n").unwrap(); // Binary PNG — should be skipped
-        
-        let files = discover_files(root).unwrap();
-        
+
+        let files = discover_files(root, &IngestConfig::default()).unwrap().files;
+
         // xml, yaml, json, md, txt, md (nested) = 6 relevant files; .png is skipped
         assert_eq!(files.len(), 6);
         assert!(files.iter().any(|f| f.relative_path.contains("overview.xml")));
@@ -113,11 +365,71 @@ mod tests {
         assert!(files.iter().any(|f| f.relative_path.contains("endpoints.md")));
         assert!(!files.iter().any(|f| f.relative_path.contains("image.png")));
     }
-    
+
     #[test]
     fn test_discover_files_empty() {
         let temp_dir = TempDir::new().unwrap();
-        let files = discover_files(temp_dir.path()).unwrap();
+        let files = discover_files(temp_dir.path(), &IngestConfig::default()).unwrap().files;
         assert_eq!(files.len(), 0);
     }
+
+    #[test]
+    fn test_discover_files_skips_with_reasons() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in [".git", ".obsidian", "node_modules/pkg", "docs/build", "docs/drafts"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "node_modules/\n*.log.md\n").unwrap();
+        fs::write(root.join("docs/.ragignore"), "build/\ndrafts/*\n!drafts/keep.md\n").unwrap();
+        fs::write(root.join(".git/HEAD.md"), "ref").unwrap();
+        fs::write(root.join(".obsidian/app.json"), "{}").unwrap();
+        fs::write(root.join("node_modules/pkg/readme.md"), "# pkg").unwrap();
+        fs::write(root.join("docs/guide.md"), "# Guide").unwrap();
+        fs::write(root.join("docs/debug.log.md"), "log").unwrap();
+        fs::write(root.join("docs/build/out.md"), "out").unwrap();
+        fs::write(root.join("docs/drafts/wip.md"), "wip").unwrap();
+        fs::write(root.join("docs/drafts/keep.md"), "keep").unwrap();
+        fs::write(root.join("docs/data.json"), "[".repeat(200)).unwrap();
+        fs::write(root.join("docs/fixture.yaml"), "a: 1").unwrap();
+        fs::write(root.join("notes.txt"), "not included").unwrap();
+
+        let config = IngestConfig {
+            include: vec!["docs/**".to_string()],
+            exclude: vec!["**/fixture.*".to_string()],
+            max_file_size: 100,
+            ..IngestConfig::default()
+        };
+        let discovery = discover_files(root, &config).unwrap();
+
+        let mut files: Vec<&str> = discovery.files.iter().map(|f| f.relative_path.as_str()).collect();
+        files.sort();
+        assert_eq!(files, vec!["docs/drafts/keep.md", "docs/guide.md"]);
+
+        let reason = |path: &str| {
+            discovery.skipped.iter().find(|s| s.relative_path == path).map(|s| s.reason.clone())
+        };
+        assert_eq!(reason(".git/"), Some(SkipReason::Hidden));
+        assert_eq!(reason(".obsidian/"), Some(SkipReason::Hidden));
+        assert_eq!(reason("node_modules/"), Some(SkipReason::IgnoreFile(".gitignore")));
+        assert_eq!(reason("docs/debug.log.md"), Some(SkipReason::IgnoreFile(".gitignore")));
+        assert_eq!(reason("docs/build/"), Some(SkipReason::IgnoreFile(".ragignore")));
+        assert_eq!(reason("docs/drafts/wip.md"), Some(SkipReason::IgnoreFile(".ragignore")));
+        assert_eq!(reason("docs/data.json"), Some(SkipReason::TooLarge { size: 200, limit: 100 }));
+        assert_eq!(reason("docs/fixture.yaml"), Some(SkipReason::Excluded));
+        assert_eq!(reason("notes.txt"), Some(SkipReason::NotIncluded));
+        assert!(reason("node_modules/pkg/readme.md").is_none(), "skipped directories are listed once");
+
+        // Single files (watcher) get the same verdicts
+        let filter = DiscoveryFilter::from_config(&config).unwrap();
+        assert_eq!(
+            filter.check_file(root, &root.join("node_modules/pkg/readme.md"), 5),
+            Some(SkipReason::IgnoreFile(".gitignore"))
+        );
+        assert_eq!(
+            filter.check_file(root, &root.join("docs/drafts/wip.md"), 3),
+            Some(SkipReason::IgnoreFile(".ragignore"))
+        );
+        assert_eq!(filter.check_file(root, &root.join("docs/drafts/keep.md"), 4), None);
+    }
 }
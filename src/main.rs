@@ -270,7 +270,9 @@ async fn run_schema_verification() -> Result<()> {
     // Load configuration
     let config = Config::load()?;
     log::info!("Configuration loaded successfully");
-    log::info!("Docs root: {}", config.rag_folder().display());
+    for source in config.sources() {
+        log::info!("Docs root ({}): {}", source.name(), source.root.display());
+    }
     log::info!("Database path: {}", config.db_path().display());
     log::info!("Embedding model: {}", config.embeddings.model);
     
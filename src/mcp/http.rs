@@ -160,6 +160,7 @@ impl HttpMcpServer {
             .route("/.well-known/mcp-server", get(handle_discovery))  // Discovery endpoint
             .route("/.well-known/mcp.json", get(handle_discovery))  // Alternative discovery endpoint
             .route("/health", get(handle_health))
+            .route("/feedback", post(handle_feedback))  // Relevance feedback for logged searches
             .layer(
                 ServiceBuilder::new()
                     .layer(TraceLayer::new_for_http())
@@ -415,6 +416,62 @@ async fn handle_health() -> Response {
     ).into_response()
 }
 
+/// Handle POST /feedback
+/// Accepts the same body as the `ragmcp_feedback` tool:
+/// `{"query_id": "...", "chunks": [{"chunk_id": "...", "helpful": true}]}`
+async fn handle_feedback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Response {
+    if !state.authless {
+        if let Err(response) = validate_auth(&headers, &state.api_key) {
+            return response;
+        }
+        if let Err(response) = validate_origin(&headers, &state.allowed_origins) {
+            return response;
+        }
+    }
+
+    let arguments: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid JSON: {}", e)})),
+            )
+                .into_response();
+        }
+    };
+
+    match state.server.record_feedback(&arguments).await {
+        Ok(result) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "status": "ok",
+                "message": result.content.first().map(|c| c.text.clone()).unwrap_or_default()
+            })),
+        )
+            .into_response(),
+        Err(RagmcpError::InvalidInput(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": message})),
+        )
+            .into_response(),
+        Err(e) => {
+            log::error!("Error recording feedback: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error": "Internal server error",
+                    "details": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Handle OAuth discovery endpoint (/.well-known/oauth-authorization-server)
 /// Returns OAuth 2.0 authorization server metadata per RFC 8414.
 ///
@@ -2,6 +2,7 @@ pub mod audit;
 pub mod http;
 pub mod roots;
 pub mod server;
+pub mod structured;
 pub mod tools;
 pub mod types;
 
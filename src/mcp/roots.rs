@@ -1,10 +1,12 @@
 //! Path validation for MCP write operations (Module 14).
 //!
 //! Ensures all write paths stay within the configured rag_folder boundary
-//! and prevents path traversal attacks.
+//! (or the `[[sources]]` root a document is mounted from) and prevents path
+//! traversal attacks.
 
 use std::path::{Path, PathBuf};
 use crate::error::{Result, RagmcpError};
+use crate::ingest::sources::{source_for_doc_path, Source};
 
 /// Validates paths for write operations within allowed boundaries.
 /// Compliant with MCP Roots protocol: only paths under rag_folder are allowed.
@@ -64,6 +66,25 @@ impl PathValidator {
     }
 }
 
+/// Validate a write to the document at `doc_path` and return its absolute path in
+/// the source it is mounted from (`wiki/page.md` is `page.md` below the `wiki` root).
+///
+/// Writes to read-only sources are rejected.
+pub fn validate_source_write_path(sources: &[Source], doc_path: &str) -> Result<PathBuf> {
+    let source = source_for_doc_path(sources, doc_path).ok_or_else(|| {
+        RagmcpError::InvalidInput(format!("No source is mounted for document path: {}", doc_path))
+    })?;
+    if source.read_only {
+        return Err(RagmcpError::InvalidInput(format!(
+            "Source '{}' is read-only: {}",
+            source.name(),
+            doc_path
+        )));
+    }
+    let relative_path = source.relative_path(doc_path).unwrap_or(doc_path);
+    PathValidator::new(&source.root)?.validate_write_path(relative_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +158,28 @@ mod tests {
         assert!(p.is_absolute());
         assert!(p.to_string_lossy().replace('\\', "/").ends_with("Business/2026/Q1/report.md"));
     }
+
+    #[test]
+    fn test_validate_source_write_path() {
+        let main = TempDir::new().unwrap();
+        let wiki = TempDir::new().unwrap();
+        let share = TempDir::new().unwrap();
+        let sources = vec![
+            Source { prefix: String::new(), root: main.path().to_path_buf(), include: vec![], exclude: vec![], read_only: false, mounted: vec!["wiki".into(), "share".into()] },
+            Source { prefix: "wiki".into(), root: wiki.path().to_path_buf(), include: vec![], exclude: vec![], read_only: false, mounted: vec![] },
+            Source { prefix: "share".into(), root: share.path().to_path_buf(), include: vec![], exclude: vec![], read_only: true, mounted: vec![] },
+        ];
+
+        let p = validate_source_write_path(&sources, "wiki/team/page.md").unwrap();
+        assert!(p.starts_with(wiki.path().canonicalize().unwrap()));
+        assert!(p.ends_with("team/page.md"));
+        let p = validate_source_write_path(&sources, "guides/setup.md").unwrap();
+        assert!(p.starts_with(main.path().canonicalize().unwrap()));
+
+        assert!(validate_source_write_path(&sources, "share/notes.md").is_err());
+        assert!(validate_source_write_path(&sources, "wiki/../share/notes.md").is_err());
+        assert!(validate_source_write_path(&sources, "wiki").is_err());
+        // Without rag_folder, paths outside every prefix have no source
+        assert!(validate_source_write_path(&sources[1..], "guides/setup.md").is_err());
+    }
 }
@@ -4,7 +4,7 @@ use crate::embeddings::OpenAIEmbedder;
 use crate::error::{Result, RagmcpError};
 use crate::mcp::tools;
 use crate::mcp::types::*;
-use crate::cache::ChunkEmbeddingCache;
+use crate::cache::{ChunkEmbeddingCache, ResultCache};
 use crate::pageindex::PageIndexManager;
 use serde_json::Value;
 use std::sync::Arc;
@@ -17,6 +17,7 @@ pub struct McpServer {
     config: Config,
     chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
     pageindex: Option<Arc<PageIndexManager>>,
+    result_cache: Option<ResultCache>,
 }
 
 impl McpServer {
@@ -28,15 +29,28 @@ impl McpServer {
         chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
         pageindex: Option<Arc<PageIndexManager>>,
     ) -> Self {
+        let cache_config = &config.search.cache;
+        let result_cache = (cache_config.enabled && cache_config.capacity > 0).then(|| {
+            ResultCache::new(
+                cache_config.capacity,
+                std::time::Duration::from_secs(cache_config.ttl_secs),
+            )
+        });
         Self {
             db,
             embedder,
             config,
             chunk_cache,
             pageindex,
+            result_cache,
         }
     }
 
+    /// Record relevance feedback outside JSON-RPC (HTTP `/feedback` endpoint)
+    pub async fn record_feedback(&self, arguments: &Value) -> Result<ToolsCallResult> {
+        tools::handle_feedback(&self.db, &self.embedder, arguments).await
+    }
+
     /// Process an MCP JSON-RPC request (transport-agnostic)
     /// 
     /// This function handles routing and processing of MCP protocol requests.
@@ -206,13 +220,9 @@ impl McpServer {
         )
         .map_err(|e| RagmcpError::Config(format!("Invalid initialize params: {}", e)))?;
 
-        // Support protocol version 2024-11-05 and 2025-06-18
-        let protocol_version = if params.protocol_version.starts_with("2024") 
-            || params.protocol_version.starts_with("2025") {
-            "2024-11-05".to_string() // Use stable version
-        } else {
-            params.protocol_version.clone()
-        };
+        // Support protocol version 2024-11-05 and 2025-06-18 (adds structuredContent/outputSchema;
+        // older clients ignore those fields)
+        let protocol_version = negotiate_protocol_version(&params.protocol_version);
 
         let result = InitializeResult {
             protocol_version: protocol_version.clone(),
@@ -272,6 +282,16 @@ impl McpServer {
                     &self.config,
                     &params.arguments,
                     self.chunk_cache.clone(),
+                    self.result_cache.as_ref(),
+                )
+                .await?
+            }
+            "ragmcp_similar" => {
+                tools::handle_similar(
+                    &self.db,
+                    &self.config,
+                    &params.arguments,
+                    self.chunk_cache.clone(),
                 )
                 .await?
             }
@@ -285,7 +305,10 @@ impl McpServer {
                 tools::handle_related(&self.db, &params.arguments).await?
             }
             "ragmcp_explain" => {
-                tools::handle_explain(&self.db, &params.arguments).await?
+                tools::handle_explain(&self.db, &self.config, &params.arguments).await?
+            }
+            "ragmcp_feedback" => {
+                tools::handle_feedback(&self.db, &self.embedder, &params.arguments).await?
             }
             "ragmcp_create_doc" => {
                 tools::handle_create_doc(
@@ -424,10 +447,39 @@ fn extract_id_from_line(line: &str) -> Option<Value> {
     None
 }
 
+/// Pick the protocol version to answer an initialize request with
+fn negotiate_protocol_version(requested: &str) -> String {
+    if requested >= "2025-06-18" && requested.starts_with("20") {
+        "2025-06-18".to_string()
+    } else if requested.starts_with("2024") || requested.starts_with("2025") {
+        "2024-11-05".to_string() // Use stable version
+    } else {
+        requested.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_negotiate_protocol_version() {
+        assert_eq!(negotiate_protocol_version("2024-11-05"), "2024-11-05");
+        assert_eq!(negotiate_protocol_version("2025-03-26"), "2024-11-05");
+        assert_eq!(negotiate_protocol_version("2025-06-18"), "2025-06-18");
+        assert_eq!(negotiate_protocol_version("2025-11-25"), "2025-06-18");
+    }
+
+    #[test]
+    fn test_tools_declare_output_schema() {
+        let tools = tools::get_tool_definitions(false);
+        let search = tools.iter().find(|t| t.name == "ragmcp_search").unwrap();
+        let value = serde_json::to_value(search).unwrap();
+        assert_eq!(value["outputSchema"]["type"], "object");
+        let feedback = tools.iter().find(|t| t.name == "ragmcp_feedback").unwrap();
+        assert!(serde_json::to_value(feedback).unwrap().get("outputSchema").is_none());
+    }
+
     #[test]
     fn test_extract_id_from_line() {
         // Test string ID
@@ -0,0 +1,381 @@
+//! Structured tool output.
+//!
+//! Tools with a declared `outputSchema` return the same data as JSON in
+//! `structuredContent` next to the human-readable text item, so programmatic
+//! clients do not have to parse "score: 0.873". Older clients ignore the extra field.
+
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::documents::DocumentResult;
+use crate::search::duplicates::{format_diff, DuplicateReport};
+use crate::search::SearchResult;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Character range of a chunk within its document's parsed text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOffset {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Position of a chunk within its document, parsed from the `{doc_id}::{index}` chunk id
+pub fn chunk_index(chunk_id: &str) -> Option<usize> {
+    chunk_id.rsplit_once("::")?.1.parse().ok()
+}
+
+/// Locate `chunk_text` in `content` and return its character (not byte) range
+///
+/// Chunks are cut from the parsed document text, so an exact match is expected; chunks
+/// whose whitespace was normalized by the chunker are not found and yield `None`.
+pub fn char_offset(content: &str, chunk_text: &str) -> Option<ChunkOffset> {
+    let needle = chunk_text.trim();
+    if needle.is_empty() {
+        return None;
+    }
+    let byte_start = content.find(needle)?;
+    let start = content[..byte_start].chars().count();
+    Some(ChunkOffset {
+        start,
+        end: start + needle.chars().count(),
+    })
+}
+
+/// Where a result chunk sits in its document: character range and source page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkLocation {
+    pub offset: Option<ChunkOffset>,
+    /// 1-based page for paginated formats (PDF)
+    pub page: Option<u32>,
+}
+
+/// Location of each result chunk within its document, keyed by chunk id
+pub async fn chunk_locations(
+    db: &Db,
+    results: &[SearchResult],
+) -> Result<HashMap<String, ChunkLocation>> {
+    let mut doc_paths: Vec<String> = results.iter().map(|r| r.doc_path.clone()).collect();
+    doc_paths.sort();
+    doc_paths.dedup();
+    let chunk_ids: Vec<String> = results.iter().map(|r| r.chunk_id.clone()).collect();
+    let (contents, pages) = db.with_connection(move |conn| {
+        let mut stmt = conn.prepare("SELECT content_text FROM documents WHERE doc_path = ?1")?;
+        let mut contents: HashMap<String, String> = HashMap::new();
+        for path in doc_paths {
+            if let Ok(content) = stmt.query_row([&path], |row| row.get::<_, String>(0)) {
+                contents.insert(path, content);
+            }
+        }
+        let mut stmt = conn.prepare("SELECT page FROM chunks WHERE chunk_id = ?1 AND page IS NOT NULL")?;
+        let mut pages: HashMap<String, u32> = HashMap::new();
+        for id in chunk_ids {
+            if let Ok(page) = stmt.query_row([&id], |row| row.get::<_, u32>(0)) {
+                pages.insert(id, page);
+            }
+        }
+        Ok::<_, RagmcpError>((contents, pages))
+    }).await?;
+
+    Ok(results
+        .iter()
+        .map(|r| {
+            let location = ChunkLocation {
+                offset: contents.get(&r.doc_path).and_then(|c| char_offset(c, &r.chunk_text)),
+                page: pages.get(&r.chunk_id).copied(),
+            };
+            (r.chunk_id.clone(), location)
+        })
+        .collect())
+}
+
+/// JSON object for one chunk result
+pub fn search_result_json(result: &SearchResult, location: ChunkLocation) -> Value {
+    json!({
+        "rank": result.rank,
+        "chunk_id": result.chunk_id,
+        "chunk_index": chunk_index(&result.chunk_id),
+        "doc_path": result.doc_path,
+        "doc_type": result.doc_type,
+        "agent_name": result.agent_name,
+        "section": result.section,
+        "score": result.score,
+        "text": result.chunk_text,
+        "offset": location.offset.map(|o| json!({ "start": o.start, "end": o.end })),
+        "page": location.page,
+        "boosts": result.boosts.iter().map(|b| json!({
+            "kind": b.kind,
+            "factor": b.factor,
+            "detail": b.detail,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// JSON object for one document result with its sections
+pub fn document_result_json(doc: &DocumentResult, locations: &HashMap<String, ChunkLocation>) -> Value {
+    json!({
+        "rank": doc.rank,
+        "doc_path": doc.doc_path,
+        "doc_type": doc.doc_type,
+        "agent_name": doc.agent_name,
+        "title": doc.title,
+        "score": doc.score,
+        "sections": doc.sections.iter()
+            .map(|s| search_result_json(s, locations.get(&s.chunk_id).copied().unwrap_or_default()))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// JSON for a duplicate report; pairs beyond `max_pairs` are dropped, each pair carries a diff
+pub fn duplicate_report_json(report: &DuplicateReport, max_pairs: usize, diff_lines: usize) -> Value {
+    let chunk = |c: &crate::search::duplicates::DuplicateChunk| json!({
+        "chunk_id": c.chunk_id,
+        "doc_path": c.doc_path,
+        "section": c.section,
+    });
+    json!({
+        "chunks_scanned": report.chunks_scanned,
+        "pair_count": report.pairs.len(),
+        "pairs": report.pairs.iter().take(max_pairs).map(|p| json!({
+            "a": chunk(&p.a),
+            "b": chunk(&p.b),
+            "text_similarity": p.text_similarity,
+            "embedding_similarity": p.embedding_similarity,
+            "diff": format_diff(&p.a.text, &p.b.text, diff_lines),
+        })).collect::<Vec<_>>(),
+        "clusters": report.clusters.iter().map(|c| json!({
+            "chunk_ids": c.chunk_ids,
+            "doc_paths": c.doc_paths,
+        })).collect::<Vec<_>>(),
+        "documents": report.documents.iter().map(|d| json!({
+            "doc_a": d.doc_a,
+            "doc_b": d.doc_b,
+            "shared_chunks": d.shared_chunks,
+            "text_similarity": d.text_similarity,
+            "embedding_similarity": d.embedding_similarity,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn nullable(kind: &str) -> Value {
+    json!({ "type": [kind, "null"] })
+}
+
+fn chunk_result_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "rank": { "type": "integer" },
+            "chunk_id": { "type": "string" },
+            "chunk_index": nullable("integer"),
+            "doc_path": { "type": "string" },
+            "doc_type": { "type": "string" },
+            "agent_name": nullable("string"),
+            "section": nullable("string"),
+            "score": { "type": "number" },
+            "text": { "type": "string", "description": "Full chunk text" },
+            "offset": {
+                "type": ["object", "null"],
+                "description": "Character range of the chunk in the document text (null if not locatable)",
+                "properties": {
+                    "start": { "type": "integer" },
+                    "end": { "type": "integer" }
+                }
+            },
+            "page": { "type": ["integer", "null"], "description": "Source page for PDFs, for citations" },
+            "boosts": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "kind": { "type": "string" },
+                        "factor": { "type": "number" },
+                        "detail": { "type": "string" }
+                    }
+                }
+            }
+        },
+        "required": ["rank", "chunk_id", "doc_path", "score", "text"]
+    })
+}
+
+/// `outputSchema` of ragmcp_search
+pub fn search_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "query": { "type": "string" },
+            "granularity": { "type": "string", "enum": ["chunk", "document"] },
+            "aggregation": { "type": "string", "description": "Document mode only: aggregation actually used" },
+            "query_id": { "type": "string", "description": "Pass to ragmcp_feedback" },
+            "latency_ms": { "type": "integer" },
+            "cached": { "type": "boolean" },
+            "results": {
+                "type": "array",
+                "description": "Chunk results, or in document mode documents with their best-matching sections",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "rank": { "type": "integer" },
+                        "doc_path": { "type": "string" },
+                        "doc_type": { "type": "string" },
+                        "agent_name": nullable("string"),
+                        "score": { "type": "number" },
+                        "chunk_id": { "type": "string" },
+                        "title": nullable("string"),
+                        "sections": { "type": "array", "items": chunk_result_schema() }
+                    },
+                    "required": ["rank", "doc_path", "score"]
+                }
+            }
+        },
+        "required": ["query", "granularity", "query_id", "results"]
+    })
+}
+
+/// `outputSchema` of ragmcp_get
+pub fn get_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "doc_path": { "type": "string" },
+            "doc_type": { "type": "string" },
+            "namespace": { "type": "string" },
+            "agent_name": nullable("string"),
+            "content_tokens": { "type": "integer" },
+            "last_modified": { "type": "string" },
+            "file_hash": { "type": "string" },
+            "binary_source": { "type": "boolean", "description": "True for PDF/Office files; content is the text extracted at ingest" },
+            "metadata": { "description": "Parsed document metadata (frontmatter etc.), if any" },
+            "content": { "type": ["string", "null"], "description": "Full text when return_full_doc=true" }
+        },
+        "required": ["doc_path", "doc_type", "namespace", "content_tokens", "last_modified"]
+    })
+}
+
+/// `outputSchema` of ragmcp_list
+pub fn list_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "list_type": { "type": "string" },
+            "count": { "type": "integer" },
+            "items": {
+                "type": "array",
+                "description": "{ name } for agents, namespaces and doc_types; { doc_path, doc_type, agent_name } for system_docs",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "doc_path": { "type": "string" },
+                        "doc_type": { "type": "string" },
+                        "agent_name": nullable("string")
+                    }
+                }
+            }
+        },
+        "required": ["list_type", "count", "items"]
+    })
+}
+
+/// `outputSchema` of ragmcp_related
+pub fn related_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "entity": { "type": "string" },
+            "max_depth": { "type": "integer" },
+            "relation_count": { "type": "integer" },
+            "relations": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "relation_id": { "type": "string" },
+                        "source": { "type": "string" },
+                        "type": { "type": "string" },
+                        "target": { "type": "string" },
+                        "metadata": {}
+                    },
+                    "required": ["source", "type", "target"]
+                }
+            }
+        },
+        "required": ["entity", "relation_count", "relations"]
+    })
+}
+
+/// `outputSchema` of ragmcp_explain; remaining fields depend on `explain_what`
+pub fn explain_output_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "explain_what": { "type": "string", "enum": ["index_stats", "doc_info", "freshness", "duplicates"] },
+            "document_count": { "type": "integer" },
+            "chunk_count": { "type": "integer" },
+            "embedded_chunk_count": { "type": "integer" },
+            "embedding_coverage": { "type": "number", "description": "Percent of chunks with embeddings" },
+            "last_update": nullable("string"),
+            "doc_path": { "type": "string" },
+            "stale_documents": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "doc_path": { "type": "string" },
+                        "last_modified": { "type": "string" }
+                    }
+                }
+            },
+            "pairs": { "type": "array", "description": "duplicates: near-duplicate chunk pairs with diffs" },
+            "clusters": { "type": "array" },
+            "documents": { "type": "array" }
+        },
+        "required": ["explain_what"]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_index() {
+        assert_eq!(chunk_index("abc123::7"), Some(7));
+        assert_eq!(chunk_index("no-index"), None);
+    }
+
+    #[test]
+    fn test_char_offset_counts_chars() {
+        let content = "Größe: intro\n\nSet it to 30 seconds.\n";
+        let offset = char_offset(content, "Set it to 30 seconds.\n").unwrap();
+        assert_eq!(offset, ChunkOffset { start: 14, end: 35 });
+        let chars: Vec<char> = content.chars().collect();
+        assert_eq!(chars[offset.start..offset.end].iter().collect::<String>(), "Set it to 30 seconds.");
+        assert_eq!(char_offset(content, "missing"), None);
+    }
+
+    #[test]
+    fn test_search_result_json_fields() {
+        let result = SearchResult {
+            chunk_id: "d1::2".to_string(),
+            doc_path: "ops/gateway.md".to_string(),
+            doc_type: "markdown".to_string(),
+            agent_name: None,
+            section: Some("Timeouts".to_string()),
+            chunk_text: "Set it to 30 seconds.".to_string(),
+            score: 0.5,
+            rank: 1,
+            boosts: Vec::new(),
+        };
+        let location = ChunkLocation {
+            offset: Some(ChunkOffset { start: 3, end: 24 }),
+            page: Some(4),
+        };
+        let value = search_result_json(&result, location);
+        assert_eq!(value["chunk_index"], 2);
+        assert_eq!(value["score"], 0.5);
+        assert_eq!(value["offset"]["end"], 24);
+        assert_eq!(value["page"], 4);
+        assert!(value["agent_name"].is_null());
+    }
+}
@@ -2,16 +2,33 @@ use crate::config::Config;
 use crate::db::Db;
 use crate::embeddings::OpenAIEmbedder;
 use crate::error::{Result, RagmcpError};
+use crate::mcp::structured::{
+    chunk_locations, document_result_json, duplicate_report_json, explain_output_schema,
+    get_output_schema, list_output_schema, related_output_schema, search_output_schema,
+    search_result_json,
+};
 use crate::mcp::types::{ContentItem, Tool, ToolsCallResult};
-use crate::mcp::roots::PathValidator;
+use crate::mcp::roots::validate_source_write_path;
 use crate::mcp::audit::log_operation;
-use crate::cache::ChunkEmbeddingCache;
+use crate::cache::result_cache::index_generation;
+use crate::cache::{ChunkEmbeddingCache, ResultCache};
+use crate::search::documents::{
+    aggregate_chunks, load_titles, rank_by_document_embedding, DocAggregation, DOC_CANDIDATE_FACTOR,
+};
+use crate::search::duplicates::{find_duplicates, format_report};
+use crate::search::feedback;
+use crate::search::similar::{resolve_seed, search_similar};
 use crate::search::hybrid::search_hybrid;
+use crate::search::MetadataFilter;
 use crate::graph::traverse_graph;
-use crate::ingest::metadata::{compute_file_hash, extract_agent_name, extract_namespace};
+use crate::ingest::metadata::compute_file_hash;
+use crate::ingest::rules::FileSettings;
 use crate::ingest::parsers::ParserRegistry;
-use crate::ingest::chunker::chunk_document;
-use crate::ingest::db_writer::{insert_chunks, insert_document};
+use crate::ingest::chunker::chunk_with_config;
+use crate::ingest::contextual::contextualize_chunks;
+use crate::ingest::db_writer::{
+    index_chunk_language, insert_chunks, insert_document, set_embedding_enabled, store_document_metadata,
+};
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
@@ -56,16 +73,86 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                         "type": "string",
                         "description": "Filter by entity/agent name (second-level directory, e.g. 'my-module', 'api-v2'). Use ragmcp_list with list_type=agents to see available values."
                     },
+                    "filters": {
+                        "type": "object",
+                        "description": "Filter by document metadata such as Markdown frontmatter, e.g. {\"status\": \"published\", \"tags\": \"networking\"}. Every key must match; list values (tags) must contain the value. Case-insensitive.",
+                        "additionalProperties": { "type": ["string", "number"] }
+                    },
                     "min_score": {
                         "type": "number",
                         "description": "Minimum relevance score (0-1)",
                         "default": 0.65,
                         "minimum": 0,
                         "maximum": 1
+                    },
+                    "prefer_recent": {
+                        "type": "boolean",
+                        "description": "Demote older documents using time-decay boosting (half-life from [search.recency]). Defaults to the server setting."
+                    },
+                    "explain": {
+                        "type": "boolean",
+                        "description": "Include a per-result score breakdown (e.g. recency boost) in the output",
+                        "default": false
+                    },
+                    "granularity": {
+                        "type": "string",
+                        "enum": ["chunk", "document"],
+                        "description": "'chunk' returns matching chunks; 'document' returns ranked documents with their best-matching sections",
+                        "default": "chunk"
+                    },
+                    "aggregation": {
+                        "type": "string",
+                        "enum": ["max", "sum_top_n", "embedding"],
+                        "description": "Document score for granularity='document': best chunk ('max'), sum of the top chunks ('sum_top_n') or document-level embedding of title + summary ('embedding'). Defaults to the server setting."
                     }
                 },
                 "required": ["query"]
             }),
+            output_schema: Some(search_output_schema()),
+        },
+        Tool {
+            name: "ragmcp_similar".to_string(),
+            description: "Find documents or chunks similar to an existing document or chunk (\"more like this\"). Uses stored embeddings; the seed document is excluded.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "like": {
+                        "type": "string",
+                        "description": "Seed: a document path (e.g. 'Runbooks/deploy.md', uses the centroid of its chunks) or a chunk ID from a search result"
+                    },
+                    "k": {
+                        "type": "integer",
+                        "description": "Number of results to return",
+                        "default": 5,
+                        "minimum": 1,
+                        "maximum": 20
+                    },
+                    "namespace": {
+                        "type": "string",
+                        "description": "Filter by namespace. Use 'all' to include all namespaces.",
+                        "default": "all"
+                    },
+                    "agent_filter": {
+                        "type": "string",
+                        "description": "Filter by entity/agent name (second-level directory)"
+                    },
+                    "min_score": {
+                        "type": "number",
+                        "description": "Minimum cosine similarity (0-1)",
+                        "default": 0.0,
+                        "minimum": 0,
+                        "maximum": 1
+                    },
+                    "granularity": {
+                        "type": "string",
+                        "enum": ["chunk", "document"],
+                        "description": "'chunk' returns similar chunks; 'document' returns similar documents with their closest sections",
+                        "default": "document"
+                    }
+                },
+                "required": ["like"]
+            }),
+            output_schema: None,
         },
         Tool {
             name: "ragmcp_get".to_string(),
@@ -90,6 +177,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["doc_path"]
             }),
+            output_schema: Some(get_output_schema()),
         },
         Tool {
             name: "ragmcp_list".to_string(),
@@ -114,6 +202,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["list_type"]
             }),
+            output_schema: Some(list_output_schema()),
         },
         Tool {
             name: "ragmcp_related".to_string(),
@@ -140,25 +229,59 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["entity"]
             }),
+            output_schema: Some(related_output_schema()),
         },
         Tool {
             name: "ragmcp_explain".to_string(),
-            description: "Get meta-information about RAGMcp index (stats, doc info, freshness)".to_string(),
+            description: "Get meta-information about RAGMcp index (stats, doc info, freshness, near-duplicate content)".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
                     "explain_what": {
                         "type": "string",
                         "description": "What to explain",
-                        "enum": ["index_stats", "doc_info", "freshness"]
+                        "enum": ["index_stats", "doc_info", "freshness", "duplicates"]
                     },
                     "doc_path": {
                         "type": "string",
-                        "description": "Document path (required for doc_info)"
+                        "description": "Document path (required for doc_info; for duplicates, only report content shared with this document)"
+                    },
+                    "namespace": {
+                        "type": "string",
+                        "description": "For duplicates: only scan this namespace (default: all)"
                     }
                 },
                 "required": ["explain_what"]
             }),
+            output_schema: Some(explain_output_schema()),
+        },
+        Tool {
+            name: "ragmcp_feedback".to_string(),
+            description: "Mark results of a previous ragmcp_search as helpful or unhelpful. Future searches for similar queries promote helpful chunks and demote unhelpful ones.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query_id": {
+                        "type": "string",
+                        "description": "Query ID printed at the end of the ragmcp_search output"
+                    },
+                    "chunks": {
+                        "type": "array",
+                        "description": "Per-chunk judgements; each chunk must have been returned by the query",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "chunk_id": { "type": "string", "description": "Chunk ID from the search result" },
+                                "helpful": { "type": "boolean", "description": "true = helpful, false = unhelpful" }
+                            },
+                            "required": ["chunk_id", "helpful"]
+                        },
+                        "minItems": 1
+                    }
+                },
+                "required": ["query_id", "chunks"]
+            }),
+            output_schema: None,
         },
         Tool {
             name: "ragmcp_create_doc".to_string(),
@@ -168,7 +291,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 "properties": {
                     "doc_path": {
                         "type": "string",
-                        "description": "Relative path from docs root (e.g. \"Namespace/subfolder/doc.md\")"
+                        "description": "Relative path from docs root (e.g. \"Namespace/subfolder/doc.md\"); documents of a mounted source start with its prefix (\"wiki/page.md\")"
                     },
                     "content": {
                         "type": "string",
@@ -181,6 +304,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["doc_path", "content"]
             }),
+            output_schema: None,
         },
         Tool {
             name: "ragmcp_update_doc".to_string(),
@@ -207,6 +331,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["doc_path", "updates"]
             }),
+            output_schema: None,
         },
     ];
 
@@ -240,6 +365,7 @@ pub fn get_tool_definitions(pageindex_enabled: bool) -> Vec<Tool> {
                 },
                 "required": ["query"]
             }),
+            output_schema: None,
         });
     }
 
@@ -260,11 +386,26 @@ struct SearchParams {
     #[serde(default = "default_namespace")]
     namespace: String,
     agent_filter: Option<String>,
+    /// Document metadata filters (`{"status": "published"}`)
+    #[serde(default)]
+    filters: Option<Value>,
     #[serde(default = "default_min_score")]
     min_score: f32,
+    /// Overrides `[search.recency].enabled` for this call
+    #[serde(default)]
+    prefer_recent: Option<bool>,
+    #[serde(default)]
+    explain: bool,
+    /// "chunk" (default) or "document"
+    #[serde(default = "default_granularity")]
+    granularity: String,
+    /// Document score aggregation; defaults to `[search.documents].aggregation`
+    #[serde(default)]
+    aggregation: Option<String>,
 }
 
 fn default_k() -> usize { 5 }
+fn default_granularity() -> String { "chunk".to_string() }
 fn default_namespace() -> String { "all".to_string() }
 fn default_min_score() -> f32 { 0.65 }
 
@@ -275,6 +416,7 @@ pub async fn handle_search(
     config: &Config,
     arguments: &Value,
     chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
+    result_cache: Option<&ResultCache>,
 ) -> Result<ToolsCallResult> {
     let start = std::time::Instant::now();
     
@@ -289,9 +431,56 @@ pub async fn handle_search(
                 text: "Error: Query must be at least 3 characters".to_string(),
             }],
             is_error: Some(true),
+            structured_content: None,
         });
     }
 
+    let document_mode = match params.granularity.as_str() {
+        "chunk" => false,
+        "document" => true,
+        other => {
+            return Ok(ToolsCallResult {
+                content: vec![ContentItem {
+                    content_type: "text".to_string(),
+                    text: format!("Error: granularity must be 'chunk' or 'document' (got '{}')", other),
+                }],
+                is_error: Some(true),
+                structured_content: None,
+            });
+        }
+    };
+    let aggregation_name = params
+        .aggregation
+        .as_deref()
+        .unwrap_or(&config.search.documents.aggregation);
+    let Some(aggregation) = DocAggregation::from_name(aggregation_name) else {
+        return Ok(ToolsCallResult {
+            content: vec![ContentItem {
+                content_type: "text".to_string(),
+                text: format!(
+                    "Error: aggregation must be 'max', 'sum_top_n' or 'embedding' (got '{}')",
+                    aggregation_name
+                ),
+            }],
+            is_error: Some(true),
+            structured_content: None,
+        });
+    };
+
+    let metadata_filter = match params.filters.as_ref().map(MetadataFilter::from_json).transpose() {
+        Ok(filter) => filter.filter(|f| !f.is_empty()),
+        Err(e) => {
+            return Ok(ToolsCallResult {
+                content: vec![ContentItem {
+                    content_type: "text".to_string(),
+                    text: format!("Error: {}", e),
+                }],
+                is_error: Some(true),
+                structured_content: None,
+            });
+        }
+    };
+
     // Convert namespace="all" to None (search all namespaces)
     let namespace_filter = if params.namespace == "all" {
         None
@@ -304,7 +493,13 @@ pub async fn handle_search(
     // - Use overfetch as the internal k for search_hybrid (how many fused results to retrieve)
     // - Disable score-based filtering inside search_hybrid by setting min_score = 0.0
     //   (this gives the caller access to the raw fused candidate set).
+    // Document mode aggregates a larger pool of chunk candidates into `k` documents.
     let effective_k = params.overfetch.unwrap_or(params.k);
+    let effective_k = if document_mode {
+        effective_k * DOC_CANDIDATE_FACTOR
+    } else {
+        effective_k
+    };
     let effective_min_score = if params.overfetch.is_some() {
         0.0
     } else {
@@ -313,31 +508,80 @@ pub async fn handle_search(
     
     let agent_filter = params.agent_filter.as_deref();
 
-    // Execute hybrid search (namespace and agent filter applied in vector SQL)
-    let results = match search_hybrid(
-        db,
-        embedder,
-        &params.query,
-        namespace_filter,
-        agent_filter,
-        effective_k,
-        effective_min_score,
-        config.search.hybrid_bm25_weight,
-        config.search.hybrid_vector_weight,
-        chunk_cache,
-    )
-    .await
-    {
-        Ok(results) => results,
-        Err(e) => {
-            return Err(e);
+    let mut search_config = config.search.clone();
+    if let Some(prefer_recent) = params.prefer_recent {
+        search_config.recency.enabled = prefer_recent;
+    }
+
+    // Repeated identical searches are served from the result cache until the index changes
+    let cache_slot = match result_cache {
+        Some(cache) => {
+            let key = ResultCache::key(
+                &params.query,
+                namespace_filter,
+                agent_filter,
+                metadata_filter.as_ref(),
+                effective_k,
+                effective_min_score,
+                params.prefer_recent,
+            );
+            Some((cache, key, index_generation(db).await?))
         }
+        None => None,
     };
+    let cached = cache_slot
+        .as_ref()
+        .and_then(|(cache, key, generation)| cache.get(key, *generation));
+    let cache_hit = cached.is_some();
+
+    let results = match cached {
+        Some(results) => results,
+        None => {
+            // Execute hybrid search (namespace and agent filter applied in vector SQL)
+            let results = search_hybrid(
+                db,
+                embedder,
+                config.embeddings.dimensions,
+                &params.query,
+                namespace_filter,
+                agent_filter,
+                metadata_filter.as_ref(),
+                effective_k,
+                effective_min_score,
+                &search_config,
+                chunk_cache,
+            )
+            .await?;
+            if let Some((cache, key, generation)) = cache_slot {
+                cache.put(key, generation, results.clone());
+            }
+            results
+        }
+    };
+
+    if document_mode {
+        return search_documents_result(
+            db,
+            embedder,
+            config,
+            &params,
+            namespace_filter,
+            metadata_filter.as_ref(),
+            results,
+            aggregation,
+            cache_hit,
+            start,
+        )
+        .await;
+    }
 
     let latency_ms = start.elapsed().as_millis() as i64;
 
     // Log query to database
-    log_query(db, &params.query, "hybrid", &results, latency_ms).await?;
+    let retrieval_method = if cache_hit { "hybrid_cached" } else { "hybrid" };
+    let query_id = log_query(db, &params.query, retrieval_method, &results, latency_ms).await?;
+
+    let locations = chunk_locations(db, &results).await?;
 
     // Format results
     let mut result_text = format!(
@@ -354,25 +598,330 @@ pub async fn handle_search(
             result.doc_path,
             result.score
         ));
+        result_text.push_str(&format!("   Chunk: {}\n", result.chunk_id));
         if let Some(section) = &result.section {
             result_text.push_str(&format!("   Section: {}\n", section));
         }
+        if let Some(page) = locations.get(&result.chunk_id).and_then(|l| l.page) {
+            result_text.push_str(&format!("   Page: {}\n", page));
+        }
         if let Some(agent) = &result.agent_name {
             result_text.push_str(&format!("   Agent: {}\n", agent));
         }
-        // Truncate chunk text for display (must not split multi-byte UTF-8 chars)
-        let preview_len = 200.min(result.chunk_text.len());
-        let safe_end = (0..=preview_len)
-            .rev()
-            .find(|&i| result.chunk_text.is_char_boundary(i))
-            .unwrap_or(0);
+        if params.explain {
+            for boost in &result.boosts {
+                result_text.push_str(&format!(
+                    "   Boost: {} x{:.3} ({})\n",
+                    boost.kind, boost.factor, boost.detail
+                ));
+            }
+        }
         result_text.push_str(&format!(
             "   Content: {}\n\n",
-            &result.chunk_text[..safe_end]
+            preview(&result.chunk_text, 200)
+        ));
+    }
+
+    if cache_hit {
+        result_text.push_str(&format!("Latency: {}ms (cached)\n", latency_ms));
+    } else {
+        result_text.push_str(&format!("Latency: {}ms\n", latency_ms));
+    }
+    result_text.push_str(&format!("Query ID: {} (use with ragmcp_feedback)\n", query_id));
+
+    let structured = json!({
+        "query": params.query,
+        "granularity": "chunk",
+        "query_id": query_id,
+        "latency_ms": latency_ms,
+        "cached": cache_hit,
+        "results": results.iter()
+            .map(|r| search_result_json(r, locations.get(&r.chunk_id).copied().unwrap_or_default()))
+            .collect::<Vec<_>>(),
+    });
+
+    Ok(ToolsCallResult {
+        content: vec![ContentItem {
+            content_type: "text".to_string(),
+            text: result_text,
+        }],
+        is_error: None,
+        structured_content: Some(structured),
+    })
+}
+
+/// Truncate text for display without splitting multi-byte UTF-8 chars
+fn preview(text: &str, max_bytes: usize) -> &str {
+    let preview_len = max_bytes.min(text.len());
+    let safe_end = (0..=preview_len)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0);
+    &text[..safe_end]
+}
+
+/// Rank documents from chunk candidates and format the `granularity: "document"` response
+#[allow(clippy::too_many_arguments)]
+async fn search_documents_result(
+    db: &Db,
+    embedder: &OpenAIEmbedder,
+    config: &Config,
+    params: &SearchParams,
+    namespace_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
+    candidates: Vec<crate::search::SearchResult>,
+    aggregation: DocAggregation,
+    cache_hit: bool,
+    start: std::time::Instant,
+) -> Result<ToolsCallResult> {
+    let doc_config = &config.search.documents;
+    let mut used = aggregation;
+    let mut documents = if aggregation == DocAggregation::Embedding {
+        let query_vec = embedder.embed_with_cache(&params.query, 3).await?;
+        rank_by_document_embedding(
+            db,
+            &query_vec,
+            namespace_filter,
+            params.agent_filter.as_deref(),
+            metadata_filter,
+            candidates.clone(),
+            doc_config.sections_per_doc,
+            params.k,
+        )
+        .await?
+    } else {
+        Vec::new()
+    };
+    if documents.is_empty() {
+        // No document-level embeddings yet (run `embed`) or a chunk-based aggregation
+        if aggregation == DocAggregation::Embedding {
+            used = DocAggregation::Max;
+        }
+        documents = aggregate_chunks(
+            candidates,
+            used,
+            doc_config.top_n,
+            doc_config.sections_per_doc,
+            params.k,
+        );
+    }
+    load_titles(db, &mut documents).await?;
+
+    let latency_ms = start.elapsed().as_millis() as i64;
+    let sections: Vec<crate::search::SearchResult> = documents
+        .iter()
+        .flat_map(|d| d.sections.iter().cloned())
+        .collect();
+    let retrieval_method = if cache_hit { "document_cached" } else { "document" };
+    let query_id = log_query(db, &params.query, retrieval_method, &sections, latency_ms).await?;
+
+    let mut result_text = format!(
+        "Found {} documents for query: \"{}\" (aggregation: {})\n",
+        documents.len(),
+        params.query,
+        used.name()
+    );
+    if used != aggregation {
+        result_text.push_str("Note: no document embeddings yet (run `embed`); ranked by best chunk instead\n");
+    }
+    result_text.push('\n');
+
+    for doc in &documents {
+        result_text.push_str(&format!(
+            "{}. [{}] {} (score: {:.3})\n",
+            doc.rank, doc.doc_type, doc.doc_path, doc.score
         ));
+        if let Some(title) = &doc.title {
+            result_text.push_str(&format!("   Title: {}\n", title));
+        }
+        if let Some(agent) = &doc.agent_name {
+            result_text.push_str(&format!("   Agent: {}\n", agent));
+        }
+        for section in &doc.sections {
+            result_text.push_str(&format!(
+                "   - {} (score: {:.3}, chunk: {})\n",
+                section.section.as_deref().unwrap_or("(no section)"),
+                section.score,
+                section.chunk_id
+            ));
+            if params.explain {
+                for boost in &section.boosts {
+                    result_text.push_str(&format!(
+                        "     Boost: {} x{:.3} ({})\n",
+                        boost.kind, boost.factor, boost.detail
+                    ));
+                }
+            }
+            result_text.push_str(&format!("     {}\n", preview(&section.chunk_text, 160)));
+        }
+        result_text.push('\n');
     }
 
+    if cache_hit {
+        result_text.push_str(&format!("Latency: {}ms (cached)\n", latency_ms));
+    } else {
+        result_text.push_str(&format!("Latency: {}ms\n", latency_ms));
+    }
+    result_text.push_str(&format!("Query ID: {} (use with ragmcp_feedback)\n", query_id));
+
+    let locations = chunk_locations(db, &sections).await?;
+    let structured = json!({
+        "query": params.query,
+        "granularity": "document",
+        "aggregation": used.name(),
+        "query_id": query_id,
+        "latency_ms": latency_ms,
+        "cached": cache_hit,
+        "results": documents.iter()
+            .map(|d| document_result_json(d, &locations))
+            .collect::<Vec<_>>(),
+    });
+
+    Ok(ToolsCallResult {
+        content: vec![ContentItem {
+            content_type: "text".to_string(),
+            text: result_text,
+        }],
+        is_error: None,
+        structured_content: Some(structured),
+    })
+}
+
+/// Similar-search parameters
+#[derive(Debug, Deserialize)]
+struct SimilarParams {
+    like: String,
+    #[serde(default = "default_k")]
+    k: usize,
+    #[serde(default = "default_namespace")]
+    namespace: String,
+    agent_filter: Option<String>,
+    #[serde(default)]
+    min_score: f32,
+    #[serde(default = "default_similar_granularity")]
+    granularity: String,
+}
+
+fn default_similar_granularity() -> String { "document".to_string() }
+
+/// Handle ragmcp_similar tool
+pub async fn handle_similar(
+    db: &Db,
+    config: &Config,
+    arguments: &Value,
+    chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
+) -> Result<ToolsCallResult> {
+    let start = std::time::Instant::now();
+    let params: SimilarParams = serde_json::from_value(arguments.clone())
+        .map_err(|e| RagmcpError::Config(format!("Invalid similar params: {}", e)))?;
+
+    let document_mode = match params.granularity.as_str() {
+        "chunk" => false,
+        "document" => true,
+        other => {
+            return Ok(ToolsCallResult {
+                content: vec![ContentItem {
+                    content_type: "text".to_string(),
+                    text: format!("Error: granularity must be 'chunk' or 'document' (got '{}')", other),
+                }],
+                is_error: Some(true),
+                structured_content: None,
+            });
+        }
+    };
+
+    let seed = match resolve_seed(db, &params.like, config.embeddings.dimensions).await {
+        Ok(seed) => seed,
+        Err(e @ (RagmcpError::DocumentNotFound(_) | RagmcpError::InvalidInput(_))) => {
+            return Ok(ToolsCallResult {
+                content: vec![ContentItem {
+                    content_type: "text".to_string(),
+                    text: format!("Error: {}", e),
+                }],
+                is_error: Some(true),
+                structured_content: None,
+            });
+        }
+        Err(e) => return Err(e),
+    };
+
+    let namespace_filter = if params.namespace == "all" {
+        None
+    } else {
+        Some(params.namespace.as_str())
+    };
+    let fetch_k = if document_mode {
+        params.k * DOC_CANDIDATE_FACTOR
+    } else {
+        params.k
+    };
+    let results = search_similar(
+        db,
+        &seed,
+        namespace_filter,
+        params.agent_filter.as_deref(),
+        fetch_k,
+        params.min_score,
+        chunk_cache,
+    )
+    .await?;
+
+    let seed_label = match &seed.chunk_id {
+        Some(chunk_id) => format!("chunk {} ({})", chunk_id, seed.doc_path),
+        None => format!("document {}", seed.doc_path),
+    };
+    let mut result_text;
+    let logged: Vec<crate::search::SearchResult>;
+    if document_mode {
+        let doc_config = &config.search.documents;
+        let mut documents = aggregate_chunks(
+            results,
+            DocAggregation::Max,
+            doc_config.top_n,
+            doc_config.sections_per_doc,
+            params.k,
+        );
+        load_titles(db, &mut documents).await?;
+        result_text = format!("Found {} documents similar to {}\n\n", documents.len(), seed_label);
+        for doc in &documents {
+            result_text.push_str(&format!(
+                "{}. [{}] {} (similarity: {:.3})\n",
+                doc.rank, doc.doc_type, doc.doc_path, doc.score
+            ));
+            if let Some(title) = &doc.title {
+                result_text.push_str(&format!("   Title: {}\n", title));
+            }
+            for section in &doc.sections {
+                result_text.push_str(&format!(
+                    "   - {} (similarity: {:.3}, chunk: {})\n",
+                    section.section.as_deref().unwrap_or("(no section)"),
+                    section.score,
+                    section.chunk_id
+                ));
+            }
+            result_text.push('\n');
+        }
+        logged = documents.into_iter().flat_map(|d| d.sections).collect();
+    } else {
+        result_text = format!("Found {} chunks similar to {}\n\n", results.len(), seed_label);
+        for result in &results {
+            result_text.push_str(&format!(
+                "{}. [{}] {} (similarity: {:.3})\n",
+                result.rank, result.doc_type, result.doc_path, result.score
+            ));
+            result_text.push_str(&format!("   Chunk: {}\n", result.chunk_id));
+            if let Some(section) = &result.section {
+                result_text.push_str(&format!("   Section: {}\n", section));
+            }
+            result_text.push_str(&format!("   Content: {}\n\n", preview(&result.chunk_text, 200)));
+        }
+        logged = results;
+    }
+
+    let latency_ms = start.elapsed().as_millis() as i64;
+    let query_id = log_query(db, &format!("like:{}", params.like), "similar", &logged, latency_ms).await?;
     result_text.push_str(&format!("Latency: {}ms\n", latency_ms));
+    result_text.push_str(&format!("Query ID: {} (use with ragmcp_feedback)\n", query_id));
 
     Ok(ToolsCallResult {
         content: vec![ContentItem {
@@ -380,6 +929,67 @@ pub async fn handle_search(
             text: result_text,
         }],
         is_error: None,
+        structured_content: None,
+    })
+}
+
+/// Feedback parameters
+#[derive(Debug, Deserialize)]
+struct FeedbackParams {
+    query_id: String,
+    chunks: Vec<ChunkJudgement>,
+}
+
+/// Single chunk judgement for ragmcp_feedback
+#[derive(Debug, Deserialize)]
+struct ChunkJudgement {
+    chunk_id: String,
+    helpful: bool,
+}
+
+/// Handle ragmcp_feedback tool (also served by the HTTP `/feedback` endpoint)
+pub async fn handle_feedback(
+    db: &Db,
+    embedder: &OpenAIEmbedder,
+    arguments: &Value,
+) -> Result<ToolsCallResult> {
+    let params: FeedbackParams = serde_json::from_value(arguments.clone())
+        .map_err(|e| RagmcpError::InvalidInput(format!("Invalid feedback params: {}", e)))?;
+    if params.chunks.is_empty() {
+        return Err(RagmcpError::InvalidInput("chunks must not be empty".to_string()));
+    }
+
+    let query_text = feedback::query_text_for(db, &params.query_id).await?;
+    // Embedding lets the judgement apply to similar (not just identical) queries
+    let query_embedding = match embedder.embed_with_cache(&query_text, 3).await {
+        Ok(embedding) => Some(embedding),
+        Err(e) => {
+            log::warn!("Storing feedback without query embedding: {}", e);
+            None
+        }
+    };
+
+    let helpful = params.chunks.iter().filter(|c| c.helpful).count();
+    let judgements = params
+        .chunks
+        .into_iter()
+        .map(|c| (c.chunk_id, c.helpful))
+        .collect();
+    let stored = feedback::record_feedback(db, &params.query_id, query_embedding, judgements).await?;
+
+    Ok(ToolsCallResult {
+        content: vec![ContentItem {
+            content_type: "text".to_string(),
+            text: format!(
+                "Recorded feedback for {} chunk(s) of query {} ({} helpful, {} unhelpful)",
+                stored,
+                params.query_id,
+                helpful,
+                stored - helpful
+            ),
+        }],
+        is_error: None,
+        structured_content: None,
     })
 }
 
@@ -437,7 +1047,21 @@ pub async fn handle_get(
         Ok::<_, RagmcpError>(row)
     }).await?;
 
-    let (_doc_id, doc_path, doc_type, namespace, agent_name, content_text, content_tokens, last_modified, file_hash, _metadata_json) = doc;
+    let (_doc_id, doc_path, doc_type, namespace, agent_name, content_text, content_tokens, last_modified, file_hash, metadata_json) = doc;
+    let binary_source = is_binary_format(&doc_path);
+
+    let structured = json!({
+        "doc_path": doc_path,
+        "doc_type": doc_type,
+        "namespace": namespace,
+        "agent_name": agent_name,
+        "content_tokens": content_tokens,
+        "last_modified": last_modified,
+        "file_hash": file_hash,
+        "binary_source": binary_source,
+        "metadata": metadata_json.as_deref().and_then(|m| serde_json::from_str::<Value>(m).ok()),
+        "content": params.return_full_doc.then_some(&content_text),
+    });
 
     let mut result_text = format!("Document: {}\n", doc_path);
     result_text.push_str(&format!("Type: {}\n", doc_type));
@@ -450,7 +1074,7 @@ pub async fn handle_get(
     result_text.push_str(&format!("Hash: {}\n\n", file_hash));
 
     if params.return_full_doc {
-        result_text.push_str("Full Content:\n");
+        result_text.push_str(if binary_source { "Extracted Text:\n" } else { "Full Content:\n" });
         result_text.push_str(&content_text);
     } else {
         result_text.push_str("(Use return_full_doc=true to see full content)\n");
@@ -462,6 +1086,7 @@ pub async fn handle_get(
             text: result_text,
         }],
         is_error: None,
+        structured_content: Some(structured),
     })
 }
 
@@ -488,7 +1113,7 @@ pub async fn handle_list(
     let params: ListParams = serde_json::from_value(arguments.clone())
         .map_err(|e| RagmcpError::Config(format!("Invalid list params: {}", e)))?;
 
-    let result_text = match params.list_type.as_str() {
+    let (result_text, items) = match params.list_type.as_str() {
         "agents" => {
             let agents = db.with_connection(|conn| {
                 let mut stmt = conn.prepare(
@@ -502,10 +1127,10 @@ pub async fn handle_list(
             }).await?;
 
             let mut text = format!("Found {} agents:\n\n", agents.len());
-            for agent in agents {
+            for agent in &agents {
                 text.push_str(&format!("- {}\n", agent));
             }
-            text
+            (text, agents.iter().map(|a| json!({ "name": a })).collect::<Vec<_>>())
         }
         "system_docs" => {
             let agent_name_clone = params.agent_name.clone();
@@ -535,14 +1160,18 @@ pub async fn handle_list(
             }).await?;
 
             let mut text = format!("Found {} system documents:\n\n", docs.len());
-            for (path, doc_type, agent) in docs {
+            for (path, doc_type, agent) in &docs {
                 text.push_str(&format!("- {} ({})", path, doc_type));
                 if let Some(agent) = agent {
                     text.push_str(&format!(" [Agent: {}]", agent));
                 }
                 text.push('\n');
             }
-            text
+            let items = docs
+                .iter()
+                .map(|(path, doc_type, agent)| json!({ "doc_path": path, "doc_type": doc_type, "agent_name": agent }))
+                .collect();
+            (text, items)
         }
         "namespaces" => {
             let namespaces = db.with_connection(|conn| {
@@ -557,10 +1186,10 @@ pub async fn handle_list(
             }).await?;
 
             let mut text = format!("Found {} namespaces:\n\n", namespaces.len());
-            for ns in namespaces {
+            for ns in &namespaces {
                 text.push_str(&format!("- {}\n", ns));
             }
-            text
+            (text, namespaces.iter().map(|ns| json!({ "name": ns })).collect())
         }
         "doc_types" => {
             let doc_types = db.with_connection(|conn| {
@@ -575,10 +1204,10 @@ pub async fn handle_list(
             }).await?;
 
             let mut text = format!("Found {} document types:\n\n", doc_types.len());
-            for dt in doc_types {
+            for dt in &doc_types {
                 text.push_str(&format!("- {}\n", dt));
             }
-            text
+            (text, doc_types.iter().map(|dt| json!({ "name": dt })).collect())
         }
         _ => {
             return Ok(ToolsCallResult {
@@ -587,16 +1216,24 @@ pub async fn handle_list(
                     text: format!("Error: Unknown list_type: {}", params.list_type),
                 }],
                 is_error: Some(true),
+                structured_content: None,
             });
         }
     };
 
+    let structured = json!({
+        "list_type": params.list_type,
+        "count": items.len(),
+        "items": items,
+    });
+
     Ok(ToolsCallResult {
         content: vec![ContentItem {
             content_type: "text".to_string(),
             text: result_text,
         }],
         is_error: None,
+        structured_content: Some(structured),
     })
 }
 
@@ -652,6 +1289,7 @@ pub async fn handle_related(
             text,
         }],
         is_error: None,
+        structured_content: Some(result_json),
     })
 }
 
@@ -660,17 +1298,19 @@ pub async fn handle_related(
 struct ExplainParams {
     explain_what: String,
     doc_path: Option<String>,
+    namespace: Option<String>,
 }
 
 /// Handle ragmcp_explain tool
 pub async fn handle_explain(
     db: &Db,
+    config: &Config,
     arguments: &Value,
 ) -> Result<ToolsCallResult> {
     let params: ExplainParams = serde_json::from_value(arguments.clone())
         .map_err(|e| RagmcpError::Config(format!("Invalid explain params: {}", e)))?;
 
-    let result_text = match params.explain_what.as_str() {
+    let (result_text, structured) = match params.explain_what.as_str() {
         "index_stats" => {
             let stats = db.with_connection(|conn| {
                 let doc_count: i64 = conn.query_row(
@@ -697,7 +1337,20 @@ pub async fn handle_explain(
             }).await?;
 
             let (doc_count, chunk_count, embedded_count, last_update) = stats;
-            format!(
+            let coverage = if chunk_count > 0 {
+                (embedded_count as f64 / chunk_count as f64) * 100.0
+            } else {
+                0.0
+            };
+            let structured = json!({
+                "explain_what": "index_stats",
+                "document_count": doc_count,
+                "chunk_count": chunk_count,
+                "embedded_chunk_count": embedded_count,
+                "embedding_coverage": coverage,
+                "last_update": last_update,
+            });
+            let text = format!(
                 "Index Statistics:\n\n\
                 Total Documents: {}\n\
                 Total Chunks: {}\n\
@@ -707,13 +1360,10 @@ pub async fn handle_explain(
                 doc_count,
                 chunk_count,
                 embedded_count,
-                if chunk_count > 0 {
-                    (embedded_count as f64 / chunk_count as f64) * 100.0
-                } else {
-                    0.0
-                },
+                coverage,
                 last_update.unwrap_or_else(|| "Unknown".to_string())
-            )
+            );
+            (text, structured)
         }
         "doc_info" => {
             let doc_path = params.doc_path.ok_or_else(|| {
@@ -750,6 +1400,17 @@ pub async fn handle_explain(
 
             let (doc_path, doc_type, namespace, agent_name, content_tokens, last_modified, file_hash) = info;
 
+            let mut structured = json!({
+                "explain_what": "doc_info",
+                "doc_path": doc_path,
+                "doc_type": doc_type,
+                "namespace": namespace,
+                "agent_name": agent_name,
+                "content_tokens": content_tokens,
+                "last_modified": last_modified,
+                "file_hash": file_hash,
+            });
+
             let mut text = format!("Document Information:\n\n");
             text.push_str(&format!("Path: {}\n", doc_path));
             text.push_str(&format!("Type: {}\n", doc_type));
@@ -778,7 +1439,18 @@ pub async fn handle_explain(
             }).await?;
 
             text.push_str(&format!("Chunks: {}\n", chunk_count));
-            text
+            structured["chunk_count"] = json!(chunk_count);
+            (text, structured)
+        }
+        "duplicates" => {
+            let namespace = params.namespace.as_deref().filter(|ns| *ns != "all");
+            let mut report = find_duplicates(db, &config.duplicates, namespace).await?;
+            if let Some(doc_path) = &params.doc_path {
+                report = report.involving(doc_path);
+            }
+            let mut structured = duplicate_report_json(&report, config.duplicates.max_pairs, 12);
+            structured["explain_what"] = json!("duplicates");
+            (format_report(&report, config.duplicates.max_pairs, 12), structured)
         }
         "freshness" => {
             let stale_docs = db.with_connection(|conn| {
@@ -798,6 +1470,13 @@ pub async fn handle_explain(
                 Ok::<_, RagmcpError>(docs)
             }).await?;
 
+            let structured = json!({
+                "explain_what": "freshness",
+                "stale_documents": stale_docs.iter()
+                    .map(|(path, modified)| json!({ "doc_path": path, "last_modified": modified }))
+                    .collect::<Vec<_>>(),
+            });
+
             let mut text = format!("Stale Documents (>7 days old):\n\n");
             if stale_docs.is_empty() {
                 text.push_str("No stale documents found.\n");
@@ -807,7 +1486,7 @@ pub async fn handle_explain(
                     text.push_str(&format!("- {} (last modified: {})\n", path, modified));
                 }
             }
-            text
+            (text, structured)
         }
         _ => {
             return Ok(ToolsCallResult {
@@ -816,6 +1495,7 @@ pub async fn handle_explain(
                     text: format!("Error: Unknown explain_what: {}", params.explain_what),
                 }],
                 is_error: Some(true),
+                structured_content: None,
             });
         }
     };
@@ -826,6 +1506,7 @@ pub async fn handle_explain(
             text: result_text,
         }],
         is_error: None,
+        structured_content: Some(structured),
     })
 }
 
@@ -837,6 +1518,19 @@ struct CreateDocParams {
     doc_type: Option<String>,
 }
 
+/// Whether `doc_path` is a binary format (PDF, Office) whose text is extracted at ingest;
+/// such files cannot be written through create_doc/update_doc
+fn is_binary_format(doc_path: &str) -> bool {
+    let extension = Path::new(doc_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    ParserRegistry::new()
+        .find_parser(&extension)
+        .is_some_and(|p| p.is_binary())
+}
+
 /// Create a new document: validate path, create dirs, write file, parse, chunk, insert, audit.
 pub async fn handle_create_doc(
     db: &Db,
@@ -849,8 +1543,13 @@ pub async fn handle_create_doc(
     let params: CreateDocParams = serde_json::from_value(arguments.clone())
         .map_err(|e| RagmcpError::InvalidInput(format!("Invalid parameters: {}", e)))?;
 
-    let validator = PathValidator::new(&config.ragmcp.rag_folder)?;
-    let absolute_path = validator.validate_write_path(&params.doc_path)?;
+    let absolute_path = validate_source_write_path(&config.sources(), &params.doc_path)?;
+    if is_binary_format(&params.doc_path) {
+        return Err(RagmcpError::InvalidInput(format!(
+            "{} is a binary format; only text documents can be written",
+            params.doc_path
+        )));
+    }
 
     if absolute_path.exists() {
         let _ = log_operation(
@@ -878,41 +1577,49 @@ pub async fn handle_create_doc(
     let metadata = fs::metadata(&absolute_path).map_err(RagmcpError::Io)?;
     let last_modified = metadata.modified().map_err(RagmcpError::Io)?;
 
-    // Parse and chunk in a block so ParserRegistry (non-Send) is dropped before any await.
-    let (doc_type, namespace, agent_name, chunks, total_tokens, content) = {
+    // Parse in a block so ParserRegistry (non-Send) is dropped before any await.
+    let (doc_type, settings, parsed) = {
         let extension = Path::new(&params.doc_path)
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
-        let registry = ParserRegistry::new();
+        let settings = FileSettings::resolve(&params.doc_path, &extension, config)?;
+        let registry = ParserRegistry::with_config(&config.ingest);
         let parsed = registry
-            .parse(&params.content, &params.doc_path, &extension)
+            .parse(&params.content, &params.doc_path, &settings.parser_extension)
             .map_err(|e| RagmcpError::Parse(e.to_string()))?;
+        // An explicit doc_type wins over [[ingest.rules]]
         let doc_type = params
             .doc_type
+            .or_else(|| settings.doc_type.clone())
             .unwrap_or_else(|| parsed.doc_type.clone());
-        let namespace = extract_namespace(&params.doc_path);
-        let agent_name = extract_agent_name(&params.doc_path);
-        let chunks = chunk_document(&parsed, &config.performance)?;
-        let total_tokens = chunks.iter().map(|c| c.tokens).sum::<usize>();
-        (doc_type, namespace, agent_name, chunks, total_tokens, parsed.content)
+        (doc_type, settings, parsed)
     };
+    let (chunks, total_tokens) = chunk_with_config(&parsed, &settings, config).await?;
+    let namespace = &settings.namespace;
+    let (content, doc_metadata) = (parsed.content, parsed.metadata);
 
     let doc_id = insert_document(
         db,
         &params.doc_path,
         &doc_type,
-        &namespace,
-        agent_name.as_deref(),
+        namespace,
+        settings.agent_name.as_deref(),
         &content,
         total_tokens,
         &file_hash,
         last_modified,
     )
     .await?;
+    store_document_metadata(db, &doc_id, doc_metadata.as_ref()).await?;
+    set_embedding_enabled(db, &doc_id, settings.embed).await?;
 
     let chunk_count = insert_chunks(db, &doc_id, chunks).await?;
+    contextualize_chunks(db, &doc_id, &content, config).await?;
+    let languages = &config.search.languages;
+    let language = languages.resolve(namespace, &content);
+    index_chunk_language(db, &doc_id, language, languages.stop_words_for(language)).await?;
 
     let meta_json = json!({
         "doc_type": doc_type,
@@ -969,6 +1676,7 @@ pub async fn handle_create_doc(
             text,
         }],
         is_error: None,
+        structured_content: None,
     })
 }
 
@@ -997,8 +1705,13 @@ pub async fn handle_update_doc(
     let params: UpdateDocParams = serde_json::from_value(arguments.clone())
         .map_err(|e| RagmcpError::InvalidInput(format!("Invalid parameters: {}", e)))?;
 
-    let validator = PathValidator::new(&config.ragmcp.rag_folder)?;
-    let absolute_path = validator.validate_write_path(&params.doc_path)?;
+    let absolute_path = validate_source_write_path(&config.sources(), &params.doc_path)?;
+    if is_binary_format(&params.doc_path) {
+        return Err(RagmcpError::InvalidInput(format!(
+            "{} is a binary format; only text documents can be written",
+            params.doc_path
+        )));
+    }
 
     // Read existing content
     let mut current_content = if absolute_path.exists() {
@@ -1028,38 +1741,44 @@ pub async fn handle_update_doc(
     let metadata = fs::metadata(&absolute_path).map_err(RagmcpError::Io)?;
     let last_modified = metadata.modified().map_err(RagmcpError::Io)?;
 
-    let (doc_type, namespace, agent_name, chunks, total_tokens, content) = {
+    let (doc_type, settings, parsed) = {
         let extension = Path::new(&params.doc_path)
             .extension()
             .and_then(|e| e.to_str())
             .unwrap_or("")
             .to_lowercase();
-        let registry = ParserRegistry::new();
+        let settings = FileSettings::resolve(&params.doc_path, &extension, config)?;
+        let registry = ParserRegistry::with_config(&config.ingest);
         let parsed = registry
-            .parse(&current_content, &params.doc_path, &extension)
+            .parse(&current_content, &params.doc_path, &settings.parser_extension)
             .map_err(|e| RagmcpError::Parse(e.to_string()))?;
-        let doc_type = parsed.doc_type.clone();
-        let namespace = extract_namespace(&params.doc_path);
-        let agent_name = extract_agent_name(&params.doc_path);
-        let chunks = chunk_document(&parsed, &config.performance)?;
-        let total_tokens = chunks.iter().map(|c| c.tokens).sum::<usize>();
-        (doc_type, namespace, agent_name, chunks, total_tokens, parsed.content)
+        let doc_type = settings.doc_type.clone().unwrap_or_else(|| parsed.doc_type.clone());
+        (doc_type, settings, parsed)
     };
+    let (chunks, total_tokens) = chunk_with_config(&parsed, &settings, config).await?;
+    let namespace = &settings.namespace;
+    let (content, doc_metadata) = (parsed.content, parsed.metadata);
 
     let doc_id = insert_document(
         db,
         &params.doc_path,
         &doc_type,
-        &namespace,
-        agent_name.as_deref(),
+        namespace,
+        settings.agent_name.as_deref(),
         &content,
         total_tokens,
         &file_hash,
         last_modified,
     )
     .await?;
+    store_document_metadata(db, &doc_id, doc_metadata.as_ref()).await?;
+    set_embedding_enabled(db, &doc_id, settings.embed).await?;
 
     let chunk_count = insert_chunks(db, &doc_id, chunks).await?;
+    contextualize_chunks(db, &doc_id, &content, config).await?;
+    let languages = &config.search.languages;
+    let language = languages.resolve(namespace, &content);
+    index_chunk_language(db, &doc_id, language, languages.stop_words_for(language)).await?;
 
     let meta_json = json!({
         "doc_type": doc_type,
@@ -1116,6 +1835,7 @@ pub async fn handle_update_doc(
             text,
         }],
         is_error: None,
+        structured_content: None,
     })
 }
 
@@ -1126,7 +1846,7 @@ async fn log_query(
     retrieval_method: &str,
     results: &[crate::search::SearchResult],
     latency_ms: i64,
-) -> Result<()> {
+) -> Result<String> {
     let query_id = Uuid::new_v4().to_string();
     let chunk_ids: Vec<String> = results.iter().map(|r| r.chunk_id.clone()).collect();
     let chunk_ids_json = serde_json::to_string(&chunk_ids)
@@ -1158,7 +1878,7 @@ async fn log_query(
         Ok::<_, RagmcpError>(())
     }).await?;
 
-    Ok(())
+    Ok(query_id)
 }
 
 /// Log pageindex query to pageindex_query_logs table
@@ -1235,7 +1955,7 @@ fn default_reason_model() -> String { "gpt-4o-mini".to_string() }
 pub async fn handle_reason(
     db: &Db,
     embedder: &OpenAIEmbedder,
-    _config: &Config,
+    config: &Config,
     pi: Arc<crate::pageindex::PageIndexManager>,
     arguments: &Value,
     chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
@@ -1248,16 +1968,22 @@ pub async fn handle_reason(
     } else {
         // Fallback: search for the best document candidate first
         log::info!("[pageindex] No doc_path provided, searching for candidate...");
+        let candidate_search = crate::config::SearchConfig {
+            hybrid_bm25_weight: 0.5,
+            hybrid_vector_weight: 0.5,
+            ..config.search.clone()
+        };
         let search_results = search_hybrid(
             db,
             embedder,
+            config.embeddings.dimensions,
             &params.query,
             None,
             None,
+            None,
             1,
             0.5,
-            0.5,
-            0.5,
+            &candidate_search,
             chunk_cache,
         ).await?;
 
@@ -1271,6 +1997,7 @@ pub async fn handle_reason(
                     text: "No relevant documents found to reason within. Please provide a doc_path or a more specific query.".to_string(),
                 }],
                 is_error: Some(true),
+                structured_content: None,
             });
         }
     };
@@ -1298,5 +2025,6 @@ pub async fn handle_reason(
             text: result_text,
         }],
         is_error: None,
+        structured_content: None,
     })
 }
@@ -87,6 +87,9 @@ pub struct Tool {
     pub description: String,
     #[serde(rename = "inputSchema")]
     pub input_schema: Value,
+    /// JSON Schema of `structuredContent`, for tools that return it
+    #[serde(rename = "outputSchema", skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<Value>,
 }
 
 /// MCP Tools/List response
@@ -109,6 +112,9 @@ pub struct ToolsCallResult {
     pub content: Vec<ContentItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_error: Option<bool>,
+    /// Machine-readable result matching the tool's `outputSchema`
+    #[serde(rename = "structuredContent", skip_serializing_if = "Option::is_none")]
+    pub structured_content: Option<Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1,6 +1,10 @@
+use crate::config::SearchConfig;
 use crate::db::Db;
 use crate::error::{Result, RagmcpError};
+use crate::search::language::{analyze, tokenize, Language};
+use crate::search::filter::{filter_param, metadata_filter_sql, MetadataFilter};
 use rusqlite::params;
+use std::collections::HashSet;
 
 /// Search result containing chunk information and relevance score
 #[derive(Debug, Clone)]
@@ -13,14 +17,35 @@ pub struct SearchResult {
     pub chunk_text: String,
     pub score: f32,
     pub rank: usize,
+    /// Post-fusion score adjustments (e.g. recency), for explain output
+    pub boosts: Vec<ScoreBoost>,
+}
+
+/// A multiplicative score adjustment applied after fusion
+#[derive(Debug, Clone)]
+pub struct ScoreBoost {
+    pub kind: String,
+    pub factor: f32,
+    pub detail: String,
 }
 
 /// Sanitize and format FTS5 query string for optimal matching
 /// 
 /// Escapes special characters and formats multi-word queries for better recall.
 /// Uses OR logic for space-separated terms to improve recall (any term matching is better than all).
-/// Removes FTS5 special characters that cause syntax errors (? * etc.) and filters out common stop words.
+/// Removes FTS5 special characters that cause syntax errors (? * etc.) and filters out common
+/// English stop words. See `sanitize_fts5_query_with_stop_words` for other languages.
 pub fn sanitize_fts5_query(query: &str) -> String {
+    let stop_words: HashSet<String> = Language::English
+        .stop_words()
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+    sanitize_fts5_query_with_stop_words(query, &stop_words)
+}
+
+/// Sanitize an FTS5 query, filtering the given (lowercase) stop words
+pub fn sanitize_fts5_query_with_stop_words(query: &str, stop_words: &HashSet<String>) -> String {
     let trimmed = query.trim();
     
     // Remove FTS5 special characters that cause syntax errors
@@ -30,15 +55,8 @@ pub fn sanitize_fts5_query(query: &str) -> String {
         .filter(|c| !matches!(c, '?' | '*' | '(' | ')' | '{' | '}' | '-' | '\''))
         .collect();
     
-    // Split into terms and filter out common stop words for better matching
+    // Split into terms and filter out stop words for better matching
     // Stop words add noise and don't help with retrieval
-    let stop_words: std::collections::HashSet<&str> = [
-        "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with",
-        "by", "from", "as", "is", "are", "was", "were", "be", "been", "being", "have",
-        "has", "had", "do", "does", "did", "will", "would", "should", "could", "what",
-        "which", "who", "where", "when", "why", "how", "this", "that", "these", "those"
-    ].iter().cloned().collect();
-    
     let terms: Vec<&str> = cleaned
         .split_whitespace()
         .filter(|term| {
@@ -68,6 +86,23 @@ pub fn sanitize_fts5_query(query: &str) -> String {
     escaped_terms.join(" OR ")
 }
 
+/// Build an FTS5 OR query for `chunks_fts_stemmed` from analyzed query terms
+/// 
+/// Returns None when every term was a stop word. Analyzed terms are alphanumeric only,
+/// so no further escaping is needed beyond quoting.
+fn stemmed_fts5_query(terms: &[String]) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    Some(
+        terms
+            .iter()
+            .map(|t| format!("\"{}\"", t))
+            .collect::<Vec<_>>()
+            .join(" OR "),
+    )
+}
+
 /// Normalize BM25 score from negative range to 0-1 range
 /// 
 /// BM25 scores are negative (better matches = lower scores).
@@ -94,26 +129,35 @@ pub fn normalize_bm25_score(raw_score: f64) -> f32 {
 /// Performs a full-text search across chunk text and section headers,
 /// returning results ranked by BM25 relevance score.
 /// 
+/// The query is analyzed once per document language in scope: English (and documents
+/// indexed before language support) go through the Porter-stemmed `chunks_fts`, other
+/// languages through `chunks_fts_stemmed` with the matching Snowball stemmer and stop words.
+/// 
 /// # Arguments
 /// 
 /// * `db` - Database connection wrapper
 /// * `query` - Search query text (will be sanitized for FTS5)
 /// * `namespace` - Optional namespace filter (directory-derived; e.g. agents, system, self, community); None = search all
 /// * `agent_filter` - Optional agent name filter
+/// * `metadata_filter` - Optional document metadata filter (e.g. frontmatter `status`)
 /// * `k` - Maximum number of results to return
 /// * `min_score` - Minimum normalized score threshold (0.0-1.0)
+/// * `search_config` - Search settings (`[search.languages]` selects stemmers and stop words)
 /// 
 /// # Returns
 /// 
 /// Vector of SearchResult structs, sorted by relevance (highest score first),
 /// with ranks assigned (1-indexed).
+#[allow(clippy::too_many_arguments)]
 pub async fn search_bm25(
     db: &Db,
     query: &str,
     namespace: Option<&str>,
     agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
     k: usize,
     min_score: f32,
+    search_config: &SearchConfig,
 ) -> Result<Vec<SearchResult>> {
     let start = std::time::Instant::now();
     
@@ -122,73 +166,63 @@ pub async fn search_bm25(
         return Ok(Vec::new());
     }
     
-    // Sanitize query to prevent FTS5 syntax errors
-    let sanitized_query = sanitize_fts5_query(query);
-    
     // Clone values to move into closure
-    let sanitized_query_clone = sanitized_query.clone();
+    let query_clone = query.to_string();
     let namespace_clone = namespace.map(|s| s.to_string());
     let agent_filter_clone = agent_filter.map(|s| s.to_string());
-    let k_clone = k;
+    let metadata_filter_clone = filter_param(metadata_filter);
+    let search_config = search_config.clone();
+    let languages = search_config.languages.clone();
     
     // Execute query using async database connection
     let mut rows = db.with_connection(move |conn| {
-        let mut stmt = conn.prepare(
-            r#"
-            SELECT 
-                c.chunk_id,
-                c.chunk_text,
-                c.section_header,
-                d.doc_path,
-                d.doc_type,
-                d.agent_name,
-                bm25(chunks_fts) AS raw_score
-            FROM chunks_fts
-            JOIN chunks c ON chunks_fts.chunk_id = c.chunk_id
-            JOIN documents d ON c.doc_id = d.doc_id
-            WHERE chunks_fts MATCH ?1
-                AND (?2 IS NULL OR d.namespace = ?2)
-                AND (?3 IS NULL OR d.agent_name = ?3)
-            ORDER BY raw_score
-            LIMIT ?4
-            "#
+        let (porter, stemmed) = languages_in_scope(
+            conn,
+            namespace_clone.as_deref(),
+            agent_filter_clone.as_deref(),
+            metadata_filter_clone.as_deref(),
         )?;
         
-        let mut rows = stmt.query(params![
-            sanitized_query_clone,
-            namespace_clone,
-            agent_filter_clone,
-            k_clone as i64,
-        ])?;
-        
         let mut results = Vec::new();
-        while let Some(row) = rows.next()? {
-            let chunk_id: String = row.get(0)?;
-            let chunk_text: String = row.get(1)?;
-            let section_header: Option<String> = row.get(2)?;
-            let doc_path: String = row.get(3)?;
-            let doc_type: String = row.get(4)?;
-            let agent_name: Option<String> = row.get(5)?;
-            let raw_score: f64 = row.get(6)?;
-            
-            // Normalize score to 0-1 range
-            let normalized_score = normalize_bm25_score(raw_score);
-            
-            // Filter by min_score threshold
-            if normalized_score < min_score {
+        if porter {
+            // Sanitize query to prevent FTS5 syntax errors
+            let english_stop_words = languages.stop_words_for(Language::English);
+            let sanitized_query =
+                sanitize_fts5_query_with_stop_words(&query_clone, &english_stop_words);
+            let terms: Vec<String> = tokenize(&query_clone)
+                .into_iter()
+                .filter(|t| !english_stop_words.contains(t))
+                .collect();
+            results.extend(query_fts_table(
+                conn,
+                FtsTable::Porter,
+                &sanitized_query,
+                &terms,
+                namespace_clone.as_deref(),
+                agent_filter_clone.as_deref(),
+                metadata_filter_clone.as_deref(),
+                k,
+                min_score,
+                &search_config,
+            )?);
+        }
+        for language in stemmed {
+            let terms = analyze(&query_clone, language, &languages.stop_words_for(language));
+            let Some(stemmed_query) = stemmed_fts5_query(&terms) else {
                 continue;
-            }
-            
-            results.push(SearchResult {
-                chunk_id,
-                doc_path,
-                doc_type,
-                agent_name,
-                section: section_header,
-                chunk_text,
-                score: normalized_score,
-                rank: 0, // Will be set after collecting all results
-            });
+            };
+            results.extend(query_fts_table(
+                conn,
+                FtsTable::Stemmed(language),
+                &stemmed_query,
+                &terms,
+                namespace_clone.as_deref(),
+                agent_filter_clone.as_deref(),
+                metadata_filter_clone.as_deref(),
+                k,
+                min_score,
+                &search_config,
+            )?);
         }
         
         Ok::<Vec<SearchResult>, RagmcpError>(results)
@@ -196,8 +230,10 @@ pub async fn search_bm25(
     
     // Assign ranks (1-indexed) and ensure results are sorted by score descending
     // Note: SQL orders by raw_score ASC (lower = better), which after normalization
-    // becomes highest normalized scores first. We sort explicitly to ensure correctness.
+    // becomes highest normalized scores first. We sort explicitly to ensure correctness,
+    // and because results from several language tables are merged here.
     rows.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    rows.truncate(k);
     
     for (idx, result) in rows.iter_mut().enumerate() {
         result.rank = idx + 1;
@@ -209,11 +245,322 @@ pub async fn search_bm25(
     Ok(rows)
 }
 
+/// FTS5's built-in `bm25()` constants; other `[search]` values enable BM25F re-scoring
+const FTS5_K1: f64 = 1.2;
+const FTS5_B: f64 = 0.75;
+
+/// Candidate over-fetch factor when re-scoring with custom k1/b
+const RESCORE_OVERFETCH: usize = 4;
+
+/// FTS5 table to run a BM25 query against
+enum FtsTable {
+    /// `chunks_fts` (Porter stemmer): English and pre-language-support documents
+    Porter,
+    /// `chunks_fts_stemmed` rows for one language
+    Stemmed(Language),
+}
+
+impl FtsTable {
+    fn name(&self) -> &'static str {
+        match self {
+            FtsTable::Porter => "chunks_fts",
+            FtsTable::Stemmed(_) => "chunks_fts_stemmed",
+        }
+    }
+    
+    fn language(&self) -> Language {
+        match self {
+            FtsTable::Porter => Language::English,
+            FtsTable::Stemmed(language) => *language,
+        }
+    }
+    
+    /// Token stream of an indexed column, comparable with `term_key`
+    fn field_tokens(&self, text: &str) -> Vec<String> {
+        match self {
+            FtsTable::Porter => analyze(text, Language::English, &HashSet::new()),
+            // Stemmed rows already hold analyzed, space-separated terms
+            FtsTable::Stemmed(_) => text.split_whitespace().map(str::to_string).collect(),
+        }
+    }
+    
+    fn term_key(&self, term: &str) -> Option<String> {
+        match self {
+            FtsTable::Porter => analyze(term, Language::English, &HashSet::new()).into_iter().next(),
+            FtsTable::Stemmed(_) => Some(term.to_string()),
+        }
+    }
+}
+
+/// Whether k1/b differ from the FTS5 constants and require Rust-side scoring
+fn needs_rescore(search_config: &SearchConfig) -> bool {
+    (search_config.bm25_k1 - FTS5_K1).abs() > f64::EPSILON
+        || (search_config.bm25_b - FTS5_B).abs() > f64::EPSILON
+}
+
+/// Determine which FTS tables hold documents matching the filters
+/// 
+/// Returns whether the Porter table is needed and the list of stemmed languages present.
+fn languages_in_scope(
+    conn: &rusqlite::Connection,
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    metadata_filter: Option<&str>,
+) -> Result<(bool, Vec<Language>)> {
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT DISTINCT language FROM documents d
+        WHERE (?1 IS NULL OR namespace = ?1)
+            AND (?2 IS NULL OR agent_name = ?2)
+            AND {}
+        "#,
+        metadata_filter_sql("?3")
+    ))?;
+    let codes = stmt
+        .query_map(params![namespace, agent_filter, metadata_filter], |row| row.get::<_, Option<String>>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    
+    let mut porter = false;
+    let mut stemmed = Vec::new();
+    for code in codes {
+        match code.as_deref().and_then(Language::from_name) {
+            Some(language) if !language.uses_porter_table() => {
+                if !stemmed.contains(&language) {
+                    stemmed.push(language);
+                }
+            }
+            _ => porter = true,
+        }
+    }
+    Ok((porter, stemmed))
+}
+
+/// A matched row with its indexed column texts (body, breadcrumb, title, path)
+struct Candidate {
+    result: SearchResult,
+    raw_score: f64,
+    fields: [String; 4],
+}
+
+/// Run a BM25 MATCH query against one FTS table and normalize the scores
+/// 
+/// Columns are weighted with `[search.field_weights]`. When k1/b are not the FTS5
+/// defaults, an over-fetched candidate set is re-scored with BM25F (`bm25f_rescore`).
+#[allow(clippy::too_many_arguments)]
+fn query_fts_table(
+    conn: &rusqlite::Connection,
+    table: FtsTable,
+    fts_query: &str,
+    terms: &[String],
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    metadata_filter: Option<&str>,
+    k: usize,
+    min_score: f32,
+    search_config: &SearchConfig,
+) -> Result<Vec<SearchResult>> {
+    let name = table.name();
+    let metadata_condition = metadata_filter_sql("?10");
+    // bm25() takes one weight per column, including UNINDEXED ones
+    let (unindexed_weights, language_filter) = match table {
+        FtsTable::Porter => ("0.0", "COALESCE(d.language, 'en') = ?5".to_string()),
+        FtsTable::Stemmed(_) => ("0.0, 0.0", format!("{name}.language = ?5")),
+    };
+    let sql = format!(
+        r#"
+        SELECT 
+            c.chunk_id,
+            c.chunk_text,
+            c.section_header,
+            d.doc_path,
+            d.doc_type,
+            d.agent_name,
+            bm25({name}, {unindexed_weights}, ?6, ?7, ?8, ?9) AS raw_score,
+            {name}.chunk_text,
+            {name}.breadcrumb,
+            {name}.title,
+            {name}.path
+        FROM {name}
+        JOIN chunks c ON {name}.chunk_id = c.chunk_id
+        JOIN documents d ON c.doc_id = d.doc_id
+        WHERE {name} MATCH ?1
+            AND (?2 IS NULL OR d.namespace = ?2)
+            AND (?3 IS NULL OR d.agent_name = ?3)
+            AND {language_filter}
+            AND {metadata_condition}
+        ORDER BY raw_score
+        LIMIT ?4
+        "#
+    );
+    
+    let rescore = needs_rescore(search_config);
+    let limit = if rescore { k * RESCORE_OVERFETCH } else { k };
+    let weights = &search_config.field_weights;
+    
+    let mut stmt = conn.prepare(&sql)?;
+    let mut rows = stmt.query(params![
+        fts_query,
+        namespace,
+        agent_filter,
+        limit as i64,
+        table.language().code(),
+        weights.body,
+        weights.breadcrumb,
+        weights.title,
+        weights.path,
+        metadata_filter,
+    ])?;
+    
+    let mut candidates = Vec::new();
+    while let Some(row) = rows.next()? {
+        let field = |idx: usize| -> rusqlite::Result<String> {
+            Ok(row.get::<_, Option<String>>(idx)?.unwrap_or_default())
+        };
+        candidates.push(Candidate {
+            result: SearchResult {
+                chunk_id: row.get(0)?,
+                doc_path: row.get(3)?,
+                doc_type: row.get(4)?,
+                agent_name: row.get(5)?,
+                section: row.get(2)?,
+                chunk_text: row.get(1)?,
+                score: 0.0,
+                rank: 0, // Will be set after collecting all results
+                boosts: Vec::new(),
+            },
+            raw_score: row.get(6)?,
+            fields: [field(7)?, field(8)?, field(9)?, field(10)?],
+        });
+    }
+    
+    if rescore {
+        bm25f_rescore(conn, &table, terms, &mut candidates, search_config)?;
+    }
+    
+    let mut results = Vec::new();
+    for candidate in candidates {
+        // Normalize score to 0-1 range
+        let normalized_score = normalize_bm25_score(candidate.raw_score);
+        
+        // Filter by min_score threshold
+        if normalized_score < min_score {
+            continue;
+        }
+        
+        results.push(SearchResult {
+            score: normalized_score,
+            ..candidate.result
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Re-score candidates with BM25F using the configured k1, b and field weights
+/// 
+/// FTS5 hard-codes k1 = 1.2 and b = 0.75, so custom values are applied here over the
+/// candidate set. Document frequencies come from the FTS index; lengths are measured
+/// in characters for both the row and the column average. Scores are written back
+/// negated to match the `bm25()` convention used by `normalize_bm25_score`.
+fn bm25f_rescore(
+    conn: &rusqlite::Connection,
+    table: &FtsTable,
+    terms: &[String],
+    candidates: &mut [Candidate],
+    search_config: &SearchConfig,
+) -> Result<()> {
+    if candidates.is_empty() || terms.is_empty() {
+        return Ok(());
+    }
+    let name = table.name();
+    // The Porter table holds every document; stemmed rows are filtered by language
+    let language = match table {
+        FtsTable::Porter => None,
+        FtsTable::Stemmed(language) => Some(language.code()),
+    };
+    let language_filter = |param: usize| match language {
+        Some(_) => format!("AND {name}.language = ?{param}"),
+        None => String::new(),
+    };
+    
+    // Corpus statistics: row count and average column lengths
+    let (total, avg_lengths): (f64, [f64; 4]) = conn.query_row(
+        &format!(
+            r#"
+            SELECT COUNT(*),
+                AVG(LENGTH(COALESCE(chunk_text, ''))),
+                AVG(LENGTH(COALESCE(breadcrumb, ''))),
+                AVG(LENGTH(COALESCE(title, ''))),
+                AVG(LENGTH(COALESCE(path, '')))
+            FROM {name} WHERE 1 = 1 {}
+            "#,
+            language_filter(1)
+        ),
+        rusqlite::params_from_iter(language),
+        |row| {
+            let avg = |idx: usize| -> rusqlite::Result<f64> {
+                Ok(row.get::<_, Option<f64>>(idx)?.unwrap_or(0.0))
+            };
+            Ok((row.get::<_, i64>(0)? as f64, [avg(1)?, avg(2)?, avg(3)?, avg(4)?]))
+        },
+    )?;
+    
+    let mut df_stmt = conn.prepare(&format!(
+        "SELECT COUNT(*) FROM {name} WHERE {name} MATCH ?1 {}",
+        language_filter(2)
+    ))?;
+    let mut idf_terms = Vec::new();
+    for term in terms {
+        let Some(key) = table.term_key(term) else { continue };
+        let match_expr = format!("\"{}\"", term.replace('"', "\"\""));
+        let df: i64 = df_stmt.query_row(
+            rusqlite::params_from_iter(std::iter::once(match_expr.as_str()).chain(language)),
+            |row| row.get(0),
+        )?;
+        let df = df as f64;
+        let idf = (1.0 + (total - df + 0.5) / (df + 0.5)).ln();
+        idf_terms.push((key, idf));
+    }
+    
+    let weights = &search_config.field_weights;
+    let field_weights = [weights.body, weights.breadcrumb, weights.title, weights.path];
+    let k1 = search_config.bm25_k1;
+    let b = search_config.bm25_b;
+    
+    for candidate in candidates.iter_mut() {
+        let field_tokens: Vec<Vec<String>> = candidate
+            .fields
+            .iter()
+            .map(|text| table.field_tokens(text))
+            .collect();
+        let mut score = 0.0;
+        for (key, idf) in &idf_terms {
+            let mut tf = 0.0;
+            for (idx, tokens) in field_tokens.iter().enumerate() {
+                let count = tokens.iter().filter(|t| *t == key).count() as f64;
+                if count == 0.0 {
+                    continue;
+                }
+                let length = candidate.fields[idx].chars().count() as f64;
+                let norm = if avg_lengths[idx] > 0.0 {
+                    1.0 - b + b * length / avg_lengths[idx]
+                } else {
+                    1.0
+                };
+                tf += field_weights[idx] * count / norm;
+            }
+            score += idf * tf * (k1 + 1.0) / (tf + k1);
+        }
+        candidate.raw_score = -score;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::{Db, migrate};
-    use crate::ingest::db_writer::{insert_document, insert_chunks};
+    use crate::ingest::db_writer::{insert_document, insert_chunks, store_document_metadata};
     use crate::ingest::chunker::Chunk;
     use std::path::Path;
     use tempfile::TempDir;
@@ -250,18 +597,21 @@ mod tests {
                 tokens: 15,
                 section_header: Some("Identity".to_string()),
                 chunk_type: Some("identity".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Another chunk discussing SQLite database and FTS5 search".to_string(),
                 tokens: 12,
                 section_header: Some("RoleStack".to_string()),
                 chunk_type: Some("rolestack".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Final chunk with different content about machine learning".to_string(),
                 tokens: 12,
                 section_header: None,
                 chunk_type: None,
+                page: None,
             },
         ];
         
@@ -346,7 +696,7 @@ mod tests {
         let _doc_id = insert_test_data(&db).await;
         
         // Search for "Rust"
-        let results = search_bm25(&db, "Rust", None, None, 10, 0.0).await.unwrap();
+        let results = search_bm25(&db, "Rust", None, None, None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         
         assert!(!results.is_empty(), "Should return at least one result");
         
@@ -376,7 +726,7 @@ mod tests {
         let _doc_id = insert_test_data(&db).await;
         
         // Search with namespace filter
-        let results = search_bm25(&db, "test", Some("agents"), None, 10, 0.0).await.unwrap();
+        let results = search_bm25(&db, "test", Some("agents"), None, None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         assert!(!results.is_empty(), "Should return results for agents namespace");
         
         // Verify all results are from agents namespace
@@ -387,7 +737,7 @@ mod tests {
         }
         
         // Search with agent filter
-        let results = search_bm25(&db, "test", None, Some("test_agent"), 10, 0.0).await.unwrap();
+        let results = search_bm25(&db, "test", None, Some("test_agent"), None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         assert!(!results.is_empty(), "Should return results for test_agent");
         
         // Verify all results are from test_agent
@@ -401,8 +751,10 @@ mod tests {
             "test", 
             Some("agents"), 
             Some("test_agent"), 
+            None,
             10, 
-            0.0
+            0.0,
+            &SearchConfig::default(),
         ).await.unwrap();
         assert!(!results.is_empty(), "Should return results with both filters");
     }
@@ -413,7 +765,7 @@ mod tests {
         let _doc_id = insert_test_data(&db).await;
         
         // Search with empty query - should return empty results (not error)
-        let results = search_bm25(&db, "", None, None, 10, 0.0).await.unwrap();
+        let results = search_bm25(&db, "", None, None, None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         assert_eq!(results.len(), 0, "Empty query should return empty results");
     }
     
@@ -423,10 +775,10 @@ mod tests {
         let _doc_id = insert_test_data(&db).await;
         
         // Search with very high min_score (should filter out most/all results)
-        let results_high = search_bm25(&db, "test", None, None, 10, 0.99).await.unwrap();
+        let results_high = search_bm25(&db, "test", None, None, None, 10, 0.99, &SearchConfig::default()).await.unwrap();
         
         // Search with low min_score (should return more results)
-        let results_low = search_bm25(&db, "test", None, None, 10, 0.0).await.unwrap();
+        let results_low = search_bm25(&db, "test", None, None, None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         
         // High threshold should return fewer or equal results
         assert!(
@@ -449,11 +801,166 @@ mod tests {
         let _doc_id = insert_test_data(&db).await;
         
         // Search with k=1
-        let results = search_bm25(&db, "test", None, None, 1, 0.0).await.unwrap();
+        let results = search_bm25(&db, "test", None, None, None, 1, 0.0, &SearchConfig::default()).await.unwrap();
         assert!(results.len() <= 1, "Should respect k limit");
         
         // Search with k=10 (more than available chunks)
-        let results = search_bm25(&db, "test", None, None, 10, 0.0).await.unwrap();
+        let results = search_bm25(&db, "test", None, None, None, 10, 0.0, &SearchConfig::default()).await.unwrap();
         assert!(results.len() <= 10, "Should respect k limit");
     }
+    
+    #[tokio::test]
+    async fn test_search_bm25_stemmed_language() {
+        use crate::ingest::db_writer::index_chunk_language;
+        let (db, _temp_dir) = setup_test_db().await;
+        let _english = insert_test_data(&db).await;
+        
+        let doc_id = insert_document(
+            &db,
+            "docs_de/planung.md",
+            "markdown",
+            "docs_de",
+            None,
+            "Planung",
+            20,
+            "hash_de",
+            std::time::SystemTime::now(),
+        ).await.unwrap();
+        insert_chunks(&db, &doc_id, vec![Chunk {
+            text: "Die Häuser der Agenten werden täglich geprüft".to_string(),
+            tokens: 10,
+            section_header: Some("Prüfungen".to_string()),
+            chunk_type: None,
+            page: None,
+        }]).await.unwrap();
+        
+        let mut config = SearchConfig::default();
+        config.languages.namespaces.insert("docs_de".to_string(), "german".to_string());
+        let language = config.languages.resolve("docs_de", "");
+        assert_eq!(language, Language::German);
+        index_chunk_language(&db, &doc_id, language, config.languages.stop_words_for(language))
+            .await
+            .unwrap();
+        
+        // Singular form matches the indexed plural via the German stemmer
+        let results = search_bm25(&db, "das Haus", Some("docs_de"), None, None, 10, 0.0, &config)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_path, "docs_de/planung.md");
+        assert!(results[0].chunk_text.contains("Häuser"), "original text is returned");
+        
+        // Unscoped searches merge the Porter and stemmed tables
+        let results = search_bm25(&db, "Haus Rust", None, None, None, 10, 0.0, &config)
+            .await
+            .unwrap();
+        assert!(results.iter().any(|r| r.doc_path == "docs_de/planung.md"));
+        assert!(results.iter().any(|r| r.doc_path == "test/agent.xml"));
+    }
+    
+    async fn insert_ranking_docs(db: &Db) {
+        let titled = insert_document(
+            db,
+            "runbooks/failover.md",
+            "markdown",
+            "runbooks",
+            None,
+            "# Failover\n\nSteps for switching the primary region.",
+            20,
+            "hash_titled",
+            std::time::SystemTime::now(),
+        ).await.unwrap();
+        insert_chunks(db, &titled, vec![Chunk {
+            text: "Steps for switching the primary region when the health checks go red".to_string(),
+            tokens: 15,
+            section_header: Some("Procedure".to_string()),
+            chunk_type: None,
+            page: None,
+        }]).await.unwrap();
+        
+        let incidental = insert_document(
+            db,
+            "notes/weekly.md",
+            "markdown",
+            "notes",
+            None,
+            "Weekly notes",
+            20,
+            "hash_incidental",
+            std::time::SystemTime::now(),
+        ).await.unwrap();
+        insert_chunks(db, &incidental, vec![Chunk {
+            text: "We discussed the budget and hiring, and briefly mentioned failover testing".to_string(),
+            tokens: 15,
+            section_header: Some("Agenda".to_string()),
+            chunk_type: None,
+            page: None,
+        }]).await.unwrap();
+    }
+    
+    #[tokio::test]
+    async fn test_search_bm25_title_outranks_body() {
+        let (db, _temp_dir) = setup_test_db().await;
+        insert_ranking_docs(&db).await;
+        
+        let results = search_bm25(&db, "failover", None, None, None, 10, 0.0, &SearchConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_path, "runbooks/failover.md");
+        
+        // Path segments are searchable even without a body match
+        let results = search_bm25(&db, "runbooks", None, None, None, 10, 0.0, &SearchConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        
+        // Equal weights let the body match compete again
+        let mut flat = SearchConfig::default();
+        flat.field_weights.title = 0.0;
+        flat.field_weights.path = 0.0;
+        let results = search_bm25(&db, "failover", None, None, None, 10, 0.0, &flat).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_path, "notes/weekly.md");
+    }
+    
+    #[tokio::test]
+    async fn test_search_bm25_custom_k1_b_rescores() {
+        let (db, _temp_dir) = setup_test_db().await;
+        insert_ranking_docs(&db).await;
+        
+        let config = SearchConfig {
+            bm25_k1: 2.0,
+            bm25_b: 0.3,
+            ..SearchConfig::default()
+        };
+        let results = search_bm25(&db, "failover", None, None, None, 10, 0.0, &config).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].doc_path, "runbooks/failover.md");
+        assert!(results[0].score > results[1].score);
+        assert!(results.iter().all(|r| r.score > 0.5 && r.score <= 1.0));
+    }
+    
+    #[tokio::test]
+    async fn test_search_bm25_metadata_filter() {
+        let (db, _temp_dir) = setup_test_db().await;
+        insert_ranking_docs(&db).await;
+        use sha2::Digest;
+        let doc_id = format!("{:x}", sha2::Sha256::digest(b"runbooks/failover.md"));
+        let metadata = serde_json::json!({ "status": "published", "tags": ["dr", "networking"] });
+        store_document_metadata(&db, &doc_id, Some(&metadata)).await.unwrap();
+        
+        let filter = MetadataFilter::from_pairs(&["tags=DR"]).unwrap();
+        let results = search_bm25(&db, "failover", None, None, Some(&filter), 10, 0.0, &SearchConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_path, "runbooks/failover.md");
+        
+        let filter = MetadataFilter::from_pairs(&["status=draft"]).unwrap();
+        let results = search_bm25(&db, "failover", None, None, Some(&filter), 10, 0.0, &SearchConfig::default())
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
 }
@@ -0,0 +1,274 @@
+//! Document-level retrieval (`granularity: "document"`).
+//!
+//! Chunk candidates from the hybrid pipeline are grouped per document and ranked by an
+//! aggregate score, or documents are ranked directly by their document-level embedding
+//! (title + summary, see `embeddings::storage::document_embedding_text`). Each document
+//! carries its best-matching chunks as sections.
+
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::filter::{filter_param, metadata_filter_sql, MetadataFilter};
+use crate::search::vector::{cosine_similarity, parse_embedding};
+use crate::search::SearchResult;
+use std::collections::HashMap;
+
+/// Chunk candidates fetched per requested document
+pub const DOC_CANDIDATE_FACTOR: usize = 10;
+
+/// How chunk scores are combined into a document score
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocAggregation {
+    /// Best chunk score
+    Max,
+    /// Sum of the `top_n` best chunk scores (favours documents with several relevant sections)
+    SumTopN,
+    /// Cosine similarity of the query and the document-level embedding
+    Embedding,
+}
+
+impl DocAggregation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "max" => Some(DocAggregation::Max),
+            "sum_top_n" | "sum" => Some(DocAggregation::SumTopN),
+            "embedding" | "doc_embedding" => Some(DocAggregation::Embedding),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DocAggregation::Max => "max",
+            DocAggregation::SumTopN => "sum_top_n",
+            DocAggregation::Embedding => "embedding",
+        }
+    }
+}
+
+/// A ranked document with its best-matching sections
+#[derive(Debug, Clone)]
+pub struct DocumentResult {
+    pub doc_path: String,
+    pub doc_type: String,
+    pub agent_name: Option<String>,
+    pub title: Option<String>,
+    pub score: f32,
+    pub rank: usize,
+    /// Best-matching chunks of this document, highest score first
+    pub sections: Vec<SearchResult>,
+}
+
+/// Group chunk results by document and rank documents by `aggregation`
+///
+/// `aggregation` must be `Max` or `SumTopN`; `Embedding` ranking needs the database
+/// (see `rank_by_document_embedding`) and is treated as `Max` here.
+pub fn aggregate_chunks(
+    results: Vec<SearchResult>,
+    aggregation: DocAggregation,
+    top_n: usize,
+    sections_per_doc: usize,
+    k: usize,
+) -> Vec<DocumentResult> {
+    let mut documents = group_by_document(results);
+    for doc in documents.iter_mut() {
+        doc.score = match aggregation {
+            DocAggregation::SumTopN => doc.sections.iter().take(top_n.max(1)).map(|r| r.score).sum(),
+            DocAggregation::Max | DocAggregation::Embedding => {
+                doc.sections.first().map(|r| r.score).unwrap_or(0.0)
+            }
+        };
+        doc.sections.truncate(sections_per_doc);
+    }
+    rank(documents, k)
+}
+
+/// Rank documents by cosine similarity of `query_vec` to their document-level embedding
+///
+/// Sections are taken from `chunk_results` (documents without a matching chunk have none).
+/// Returns an empty list when no document in scope has an embedding yet.
+#[allow(clippy::too_many_arguments)]
+pub async fn rank_by_document_embedding(
+    db: &Db,
+    query_vec: &[f32],
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
+    chunk_results: Vec<SearchResult>,
+    sections_per_doc: usize,
+    k: usize,
+) -> Result<Vec<DocumentResult>> {
+    let ns = namespace.map(String::from);
+    let agent = agent_filter.map(String::from);
+    let metadata = filter_param(metadata_filter);
+    let rows = db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT doc_path, doc_type, agent_name, title, embedding
+            FROM documents d
+            WHERE embedding IS NOT NULL
+                AND (?1 IS NULL OR namespace = ?1)
+                AND (?2 IS NULL OR agent_name = ?2)
+                AND {}
+            "#,
+            metadata_filter_sql("?3")
+        ))?;
+        let rows = stmt
+            .query_map(rusqlite::params![ns, agent, metadata], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Vec<u8>>(4)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok::<_, RagmcpError>(rows)
+    }).await?;
+
+    let mut sections: HashMap<String, Vec<SearchResult>> = HashMap::new();
+    for doc in group_by_document(chunk_results) {
+        sections.insert(doc.doc_path, doc.sections);
+    }
+
+    let documents: Vec<DocumentResult> = rows
+        .into_iter()
+        .filter_map(|(doc_path, doc_type, agent_name, title, blob)| {
+            let embedding = parse_embedding(&blob)?;
+            if embedding.len() != query_vec.len() {
+                return None;
+            }
+            let mut doc_sections = sections.remove(&doc_path).unwrap_or_default();
+            doc_sections.truncate(sections_per_doc);
+            Some(DocumentResult {
+                score: cosine_similarity(query_vec, &embedding),
+                doc_path,
+                doc_type,
+                agent_name,
+                title,
+                rank: 0,
+                sections: doc_sections,
+            })
+        })
+        .collect();
+    Ok(rank(documents, k))
+}
+
+/// Fill in `title` for documents ranked from chunk results
+pub async fn load_titles(db: &Db, documents: &mut [DocumentResult]) -> Result<()> {
+    let paths: Vec<String> = documents.iter().map(|d| d.doc_path.clone()).collect();
+    let titles: HashMap<String, String> = db.with_connection(move |conn| {
+        let mut stmt = conn.prepare("SELECT title FROM documents WHERE doc_path = ?1")?;
+        let mut titles = HashMap::new();
+        for path in paths {
+            if let Ok(Some(title)) = stmt.query_row([&path], |row| row.get::<_, Option<String>>(0)) {
+                titles.insert(path, title);
+            }
+        }
+        Ok::<_, RagmcpError>(titles)
+    }).await?;
+    for doc in documents.iter_mut() {
+        if doc.title.is_none() {
+            doc.title = titles.get(&doc.doc_path).cloned();
+        }
+    }
+    Ok(())
+}
+
+/// Group results per document, preserving first-seen metadata and sorting sections by score
+fn group_by_document(results: Vec<SearchResult>) -> Vec<DocumentResult> {
+    let mut order: Vec<String> = Vec::new();
+    let mut grouped: HashMap<String, DocumentResult> = HashMap::new();
+    for result in results {
+        let doc = grouped.entry(result.doc_path.clone()).or_insert_with(|| {
+            order.push(result.doc_path.clone());
+            DocumentResult {
+                doc_path: result.doc_path.clone(),
+                doc_type: result.doc_type.clone(),
+                agent_name: result.agent_name.clone(),
+                title: None,
+                score: 0.0,
+                rank: 0,
+                sections: Vec::new(),
+            }
+        });
+        doc.sections.push(result);
+    }
+    order
+        .into_iter()
+        .filter_map(|path| grouped.remove(&path))
+        .map(|mut doc| {
+            doc.sections
+                .sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+            doc
+        })
+        .collect()
+}
+
+fn rank(mut documents: Vec<DocumentResult>, k: usize) -> Vec<DocumentResult> {
+    documents.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    documents.truncate(k);
+    for (idx, doc) in documents.iter_mut().enumerate() {
+        doc.rank = idx + 1;
+    }
+    documents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(doc_path: &str, idx: usize, score: f32) -> SearchResult {
+        SearchResult {
+            chunk_id: format!("{}::{}", doc_path, idx),
+            doc_path: doc_path.to_string(),
+            doc_type: "markdown".to_string(),
+            agent_name: None,
+            section: Some(format!("Section {}", idx)),
+            chunk_text: "text".to_string(),
+            score,
+            rank: 0,
+            boosts: Vec::new(),
+        }
+    }
+
+    fn candidates() -> Vec<SearchResult> {
+        vec![
+            chunk("a.md", 0, 0.95),
+            chunk("b.md", 0, 0.80),
+            chunk("b.md", 1, 0.75),
+            chunk("b.md", 2, 0.70),
+            chunk("a.md", 1, 0.10),
+        ]
+    }
+
+    #[test]
+    fn test_aggregate_max() {
+        let docs = aggregate_chunks(candidates(), DocAggregation::Max, 3, 2, 10);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].doc_path, "a.md");
+        assert_eq!(docs[0].rank, 1);
+        assert!((docs[0].score - 0.95).abs() < 1e-6);
+        assert_eq!(docs[0].sections[0].chunk_id, "a.md::0", "best section first");
+        assert_eq!(docs[1].sections.len(), 2, "sections capped per document");
+    }
+
+    #[test]
+    fn test_aggregate_sum_top_n_favours_broad_matches() {
+        let docs = aggregate_chunks(candidates(), DocAggregation::SumTopN, 3, 3, 10);
+        assert_eq!(docs[0].doc_path, "b.md");
+        assert!((docs[0].score - 2.25).abs() < 1e-5);
+
+        let top_one = aggregate_chunks(candidates(), DocAggregation::SumTopN, 1, 3, 1);
+        assert_eq!(top_one.len(), 1);
+        assert_eq!(top_one[0].doc_path, "a.md");
+    }
+
+    #[test]
+    fn test_aggregation_names() {
+        assert_eq!(DocAggregation::from_name("sum_top_n"), Some(DocAggregation::SumTopN));
+        assert_eq!(DocAggregation::from_name("Embedding"), Some(DocAggregation::Embedding));
+        assert_eq!(DocAggregation::from_name("median"), None);
+        assert_eq!(DocAggregation::Max.name(), "max");
+    }
+}
@@ -0,0 +1,773 @@
+//! Near-duplicate content detection.
+//!
+//! Copy-pasted sections drift out of sync. Candidate chunk pairs are found with two
+//! locality-sensitive hashes so the corpus is never compared pairwise:
+//! MinHash over word shingles (lexical copies) and random-hyperplane SimHash over the
+//! stored embeddings (paraphrased copies). Candidates are verified with the exact shingle
+//! Jaccard similarity and embedding cosine, clustered with union-find, and rolled up
+//! into document pairs. Chunks of the same document are never paired, since chunk
+//! overlap would report every document as duplicating itself.
+
+use crate::config::DuplicatesConfig;
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::similar::centroid;
+use crate::search::vector::{cosine_similarity, parse_embedding};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// SimHash bands and the hyperplane bits per band key
+///
+/// Embeddings of one corpus sit close together, so short keys put most chunks in the
+/// same few buckets; 20-bit keys keep buckets small while 20 bands still catch pairs
+/// at the default 0.95 cosine threshold about nine times out of ten.
+const SIMHASH_BANDS: usize = 20;
+const SIMHASH_BAND_BITS: usize = 20;
+
+/// Buckets larger than this (boilerplate shared by most of the corpus) are skipped
+const MAX_BUCKET_SIZE: usize = 200;
+
+/// A chunk taking part in a duplicate pair
+#[derive(Debug, Clone)]
+pub struct DuplicateChunk {
+    pub chunk_id: String,
+    pub doc_path: String,
+    pub section: Option<String>,
+    pub text: String,
+}
+
+/// Two near-duplicate chunks from different documents
+#[derive(Debug, Clone)]
+pub struct DuplicatePair {
+    pub a: DuplicateChunk,
+    pub b: DuplicateChunk,
+    /// Jaccard similarity of word shingles
+    pub text_similarity: f32,
+    /// Cosine similarity of the chunk embeddings, when both are embedded
+    pub embedding_similarity: Option<f32>,
+}
+
+impl DuplicatePair {
+    /// Ranking score: the stronger of the two signals
+    pub fn score(&self) -> f32 {
+        self.text_similarity.max(self.embedding_similarity.unwrap_or(0.0))
+    }
+}
+
+/// Chunks connected by duplicate pairs (transitively)
+#[derive(Debug, Clone)]
+pub struct DuplicateCluster {
+    pub chunk_ids: Vec<String>,
+    /// Distinct documents in the cluster, sorted
+    pub doc_paths: Vec<String>,
+}
+
+/// Two documents sharing near-duplicate chunks
+#[derive(Debug, Clone)]
+pub struct DocumentPair {
+    pub doc_a: String,
+    pub doc_b: String,
+    /// Number of duplicate chunk pairs between the documents
+    pub shared_chunks: usize,
+    /// Jaccard similarity of the documents' shingle sets
+    pub text_similarity: f32,
+    /// Cosine similarity of the documents' chunk-embedding centroids
+    pub embedding_similarity: Option<f32>,
+}
+
+/// Result of a duplicate scan
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateReport {
+    pub chunks_scanned: usize,
+    /// All duplicate pairs, highest score first
+    pub pairs: Vec<DuplicatePair>,
+    /// Clusters, largest first
+    pub clusters: Vec<DuplicateCluster>,
+    /// Document pairs, most shared chunks first
+    pub documents: Vec<DocumentPair>,
+}
+
+impl DuplicateReport {
+    /// Keep only pairs, clusters and document pairs involving `doc_path`
+    pub fn involving(mut self, doc_path: &str) -> Self {
+        let target = normalize_path(doc_path);
+        let matches = |path: &str| normalize_path(path) == target;
+        self.pairs.retain(|p| matches(&p.a.doc_path) || matches(&p.b.doc_path));
+        self.clusters.retain(|c| c.doc_paths.iter().any(|p| matches(p)));
+        self.documents.retain(|d| matches(&d.doc_a) || matches(&d.doc_b));
+        self
+    }
+}
+
+/// Chunk loaded for scanning
+#[derive(Debug, Clone)]
+pub struct ScanChunk {
+    pub chunk: DuplicateChunk,
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// Hashed word shingles of `text` (lowercased, punctuation ignored)
+///
+/// Texts shorter than `size` words yield a single shingle of all their words.
+pub fn shingles(text: &str, size: usize) -> HashSet<u64> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    let size = size.max(1);
+    if words.is_empty() {
+        return HashSet::new();
+    }
+    if words.len() < size {
+        return HashSet::from([hash_words(&words)]);
+    }
+    words.windows(size).map(hash_words).collect()
+}
+
+fn hash_words(words: &[String]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    words.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Exact Jaccard similarity of two shingle sets
+pub fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    intersection as f32 / (a.len() + b.len() - intersection) as f32
+}
+
+/// SplitMix64 finalizer, used as a seeded hash family
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// MinHash signature: per seed, the minimum hash over all shingles
+pub fn minhash_signature(shingles: &HashSet<u64>, num_hashes: usize) -> Vec<u64> {
+    (0..num_hashes as u64)
+        .map(|seed| {
+            let salt = mix(seed);
+            shingles.iter().map(|s| mix(s ^ salt)).min().unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// Fraction of agreeing MinHash positions (estimates Jaccard similarity)
+pub fn estimated_jaccard(a: &[u64], b: &[u64]) -> f32 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).filter(|(x, y)| x == y).count() as f32 / a.len() as f32
+}
+
+/// Random-hyperplane band keys: bit i of a band's key is set when the vector lies on
+/// the positive side of the band's plane i
+fn simhash_band_keys(vector: &[f32], planes: &[Vec<f32>]) -> Vec<u64> {
+    planes
+        .chunks(SIMHASH_BAND_BITS)
+        .map(|band| {
+            band.iter().enumerate().fold(0u64, |acc, (i, plane)| {
+                let dot: f32 = plane.iter().zip(vector).map(|(p, v)| p * v).sum();
+                if dot >= 0.0 {
+                    acc | (1 << i)
+                } else {
+                    acc
+                }
+            })
+        })
+        .collect()
+}
+
+/// Deterministic hyperplanes with components in [-1, 1]
+fn hyperplanes(dim: usize) -> Vec<Vec<f32>> {
+    (0..(SIMHASH_BANDS * SIMHASH_BAND_BITS) as u64)
+        .map(|plane| {
+            (0..dim as u64)
+                .map(|i| {
+                    let h = mix(plane.wrapping_mul(0x1_0000_0001) ^ i);
+                    (h as f64 / u64::MAX as f64 * 2.0 - 1.0) as f32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Index pairs that share at least one band bucket
+fn banded_candidates(band_keys: &[Vec<u64>], pairs: &mut HashSet<(usize, usize)>) {
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (idx, keys) in band_keys.iter().enumerate() {
+        for (band, key) in keys.iter().enumerate() {
+            buckets.entry((band, *key)).or_default().push(idx);
+        }
+    }
+    let mut oversized = 0;
+    for members in buckets.values() {
+        if members.len() > MAX_BUCKET_SIZE {
+            oversized += 1;
+            continue;
+        }
+        for (i, &a) in members.iter().enumerate() {
+            for &b in &members[i + 1..] {
+                pairs.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+    if oversized > 0 {
+        log::warn!(
+            "Duplicate detection skipped {} LSH buckets with more than {} chunks",
+            oversized,
+            MAX_BUCKET_SIZE
+        );
+    }
+}
+
+/// Semantic candidates: SimHash LSH over embeddings of the dominant dimension
+fn simhash_candidates(chunks: &[ScanChunk], pairs: &mut HashSet<(usize, usize)>) {
+    let Some(dim) = chunks.iter().find_map(|c| c.embedding.as_ref().map(|e| e.len())) else {
+        return;
+    };
+    let planes = hyperplanes(dim);
+    let keys: Vec<Vec<u64>> = chunks
+        .iter()
+        .map(|c| match &c.embedding {
+            Some(e) if e.len() == dim => simhash_band_keys(e, &planes),
+            _ => Vec::new(),
+        })
+        .collect();
+    banded_candidates(&keys, pairs);
+}
+
+fn band_hash(values: &[u64]) -> u64 {
+    values.iter().fold(0u64, |acc, v| mix(acc ^ v))
+}
+
+/// Find near-duplicate chunk pairs among `chunks` and build the report
+pub fn detect_duplicates(chunks: Vec<ScanChunk>, config: &DuplicatesConfig) -> DuplicateReport {
+    let shingle_sets: Vec<HashSet<u64>> = chunks
+        .iter()
+        .map(|c| shingles(&c.chunk.text, config.shingle_size))
+        .collect();
+
+    // Lexical candidates: MinHash LSH
+    let bands = config.bands.max(1);
+    let rows = (config.num_hashes / bands).max(1);
+    let minhash_keys: Vec<Vec<u64>> = shingle_sets
+        .iter()
+        .map(|set| {
+            if set.is_empty() {
+                return Vec::new();
+            }
+            minhash_signature(set, rows * bands).chunks(rows).map(band_hash).collect()
+        })
+        .collect();
+    let mut candidates = HashSet::new();
+    banded_candidates(&minhash_keys, &mut candidates);
+
+    simhash_candidates(&chunks, &mut candidates);
+
+    // Verify candidates
+    let mut verified: Vec<(usize, usize, f32, Option<f32>)> = candidates
+        .into_iter()
+        .filter(|(a, b)| chunks[*a].chunk.doc_path != chunks[*b].chunk.doc_path)
+        .filter_map(|(a, b)| {
+            let text = jaccard(&shingle_sets[a], &shingle_sets[b]);
+            let embedding = match (&chunks[a].embedding, &chunks[b].embedding) {
+                (Some(x), Some(y)) if x.len() == y.len() => Some(cosine_similarity(x, y)),
+                _ => None,
+            };
+            let is_duplicate = text >= config.text_threshold
+                || embedding.is_some_and(|e| e >= config.embedding_threshold);
+            is_duplicate.then_some((a, b, text, embedding))
+        })
+        .collect();
+    verified.sort_by(|x, y| {
+        let score = |p: &(usize, usize, f32, Option<f32>)| p.2.max(p.3.unwrap_or(0.0));
+        score(y)
+            .partial_cmp(&score(x))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| chunks[x.0].chunk.chunk_id.cmp(&chunks[y.0].chunk.chunk_id))
+    });
+
+    let clusters = cluster(&chunks, &verified);
+    let documents = document_pairs(&chunks, &shingle_sets, &verified);
+    let pairs = verified
+        .into_iter()
+        .map(|(a, b, text_similarity, embedding_similarity)| DuplicatePair {
+            a: chunks[a].chunk.clone(),
+            b: chunks[b].chunk.clone(),
+            text_similarity,
+            embedding_similarity,
+        })
+        .collect();
+
+    DuplicateReport {
+        chunks_scanned: chunks.len(),
+        pairs,
+        clusters,
+        documents,
+    }
+}
+
+/// Union-find over verified pairs
+fn cluster(chunks: &[ScanChunk], pairs: &[(usize, usize, f32, Option<f32>)]) -> Vec<DuplicateCluster> {
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    let mut parent: Vec<usize> = (0..chunks.len()).collect();
+    for &(a, b, _, _) in pairs {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        if ra != rb {
+            parent[ra.max(rb)] = ra.min(rb);
+        }
+    }
+
+    let mut groups: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+    for &(a, b, _, _) in pairs {
+        let root = find(&mut parent, a);
+        let group = groups.entry(root).or_default();
+        group.insert(a);
+        group.insert(b);
+    }
+
+    let mut clusters: Vec<DuplicateCluster> = groups
+        .into_values()
+        .map(|members| {
+            let doc_paths: BTreeSet<String> =
+                members.iter().map(|&i| chunks[i].chunk.doc_path.clone()).collect();
+            DuplicateCluster {
+                chunk_ids: members.iter().map(|&i| chunks[i].chunk.chunk_id.clone()).collect(),
+                doc_paths: doc_paths.into_iter().collect(),
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.chunk_ids
+            .len()
+            .cmp(&a.chunk_ids.len())
+            .then_with(|| a.chunk_ids.cmp(&b.chunk_ids))
+    });
+    clusters
+}
+
+/// Roll chunk pairs up into document pairs with whole-document similarities
+fn document_pairs(
+    chunks: &[ScanChunk],
+    shingle_sets: &[HashSet<u64>],
+    pairs: &[(usize, usize, f32, Option<f32>)],
+) -> Vec<DocumentPair> {
+    let mut shared: HashMap<(String, String), usize> = HashMap::new();
+    for &(a, b, _, _) in pairs {
+        let (x, y) = (&chunks[a].chunk.doc_path, &chunks[b].chunk.doc_path);
+        let key = if x < y { (x.clone(), y.clone()) } else { (y.clone(), x.clone()) };
+        *shared.entry(key).or_default() += 1;
+    }
+
+    let mut doc_shingles: HashMap<&str, HashSet<u64>> = HashMap::new();
+    let mut doc_embeddings: HashMap<&str, Vec<Vec<f32>>> = HashMap::new();
+    for (chunk, set) in chunks.iter().zip(shingle_sets) {
+        let path = chunk.chunk.doc_path.as_str();
+        doc_shingles.entry(path).or_default().extend(set);
+        if let Some(e) = &chunk.embedding {
+            doc_embeddings.entry(path).or_default().push(e.clone());
+        }
+    }
+    let centroids: HashMap<&str, Vec<f32>> = doc_embeddings
+        .iter()
+        .filter_map(|(path, vectors)| centroid(vectors).map(|c| (*path, c)))
+        .collect();
+
+    let mut documents: Vec<DocumentPair> = shared
+        .into_iter()
+        .map(|((doc_a, doc_b), shared_chunks)| {
+            let text_similarity = match (doc_shingles.get(doc_a.as_str()), doc_shingles.get(doc_b.as_str())) {
+                (Some(x), Some(y)) => jaccard(x, y),
+                _ => 0.0,
+            };
+            let embedding_similarity = match (centroids.get(doc_a.as_str()), centroids.get(doc_b.as_str())) {
+                (Some(x), Some(y)) if x.len() == y.len() => Some(cosine_similarity(x, y)),
+                _ => None,
+            };
+            DocumentPair {
+                doc_a,
+                doc_b,
+                shared_chunks,
+                text_similarity,
+                embedding_similarity,
+            }
+        })
+        .collect();
+    documents.sort_by(|a, b| {
+        b.shared_chunks
+            .cmp(&a.shared_chunks)
+            .then_with(|| {
+                b.text_similarity
+                    .partial_cmp(&a.text_similarity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| (&a.doc_a, &a.doc_b).cmp(&(&b.doc_a, &b.doc_b)))
+    });
+    documents
+}
+
+/// Load chunks (optionally one namespace) and detect near-duplicates
+///
+/// Detection runs on the blocking pool with the load, so a corpus-wide scan does not
+/// stall the async server.
+pub async fn find_duplicates(
+    db: &Db,
+    config: &DuplicatesConfig,
+    namespace: Option<&str>,
+) -> Result<DuplicateReport> {
+    let ns = namespace.map(String::from);
+    let min_tokens = config.min_tokens as i64;
+    let config = config.clone();
+    db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT c.chunk_id, d.doc_path, c.section_header, c.chunk_text, c.embedding
+            FROM chunks c
+            JOIN documents d ON c.doc_id = d.doc_id
+            WHERE c.chunk_tokens >= ?1
+                AND (?2 IS NULL OR d.namespace = ?2)
+            ORDER BY d.doc_path, c.chunk_index
+            "#,
+        )?;
+        let rows = stmt
+            .query_map(rusqlite::params![min_tokens, ns], |row| {
+                Ok(ScanChunk {
+                    chunk: DuplicateChunk {
+                        chunk_id: row.get(0)?,
+                        doc_path: row.get(1)?,
+                        section: row.get(2)?,
+                        text: row.get(3)?,
+                    },
+                    embedding: row
+                        .get::<_, Option<Vec<u8>>>(4)?
+                        .as_deref()
+                        .and_then(parse_embedding),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok::<_, RagmcpError>(detect_duplicates(rows, &config))
+    }).await
+}
+
+/// One step of a token diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffOp<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Longest-common-subsequence diff of two token sequences
+pub fn diff_tokens<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let (n, m) = (a.len(), b.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::with_capacity(n.max(m));
+    while i < n && j < m {
+        if a[i] == b[j] {
+            ops.push(DiffOp::Same(a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Removed(a[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added(b[j]));
+            j += 1;
+        }
+    }
+    ops.extend(a[i..].iter().map(|t| DiffOp::Removed(t)));
+    ops.extend(b[j..].iter().map(|t| DiffOp::Added(t)));
+    ops
+}
+
+/// Human-readable diff of two chunk texts
+///
+/// Multi-line texts get a line diff (`- `/`+ ` lines, unchanged runs collapsed); short
+/// texts get an inline word diff with `[-removed-]{+added+}` markers. Identical texts
+/// yield "(identical)". At most `max_lines` lines are returned.
+pub fn format_diff(a: &str, b: &str, max_lines: usize) -> String {
+    if a.trim() == b.trim() {
+        return "(identical)".to_string();
+    }
+
+    let lines_a: Vec<&str> = a.lines().map(str::trim_end).filter(|l| !l.trim().is_empty()).collect();
+    let lines_b: Vec<&str> = b.lines().map(str::trim_end).filter(|l| !l.trim().is_empty()).collect();
+
+    if lines_a.len() <= 2 && lines_b.len() <= 2 {
+        let words_a: Vec<&str> = a.split_whitespace().collect();
+        let words_b: Vec<&str> = b.split_whitespace().collect();
+        let mut out = String::new();
+        let mut pending: Option<(bool, Vec<&str>)> = None;
+        let flush = |out: &mut String, pending: &mut Option<(bool, Vec<&str>)>| {
+            if let Some((added, words)) = pending.take() {
+                let (open, close) = if added { ("{+", "+}") } else { ("[-", "-]") };
+                out.push_str(&format!("{}{}{} ", open, words.join(" "), close));
+            }
+        };
+        for op in diff_tokens(&words_a, &words_b) {
+            match op {
+                DiffOp::Same(word) => {
+                    flush(&mut out, &mut pending);
+                    out.push_str(word);
+                    out.push(' ');
+                }
+                DiffOp::Removed(word) | DiffOp::Added(word) => {
+                    let added = matches!(op, DiffOp::Added(_));
+                    if pending.as_ref().is_some_and(|(kind, _)| *kind != added) {
+                        flush(&mut out, &mut pending);
+                    }
+                    pending.get_or_insert_with(|| (added, Vec::new())).1.push(word);
+                }
+            }
+        }
+        flush(&mut out, &mut pending);
+        return out.trim_end().to_string();
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let mut unchanged = 0;
+    for op in diff_tokens(&lines_a, &lines_b) {
+        match op {
+            DiffOp::Same(_) => unchanged += 1,
+            DiffOp::Removed(line) | DiffOp::Added(line) => {
+                if unchanged > 0 {
+                    out.push(format!("  ({} unchanged lines)", unchanged));
+                    unchanged = 0;
+                }
+                let sign = if matches!(op, DiffOp::Added(_)) { '+' } else { '-' };
+                out.push(format!("{} {}", sign, line));
+            }
+        }
+    }
+    if unchanged > 0 {
+        out.push(format!("  ({} unchanged lines)", unchanged));
+    }
+    if out.len() > max_lines {
+        let hidden = out.len() - max_lines;
+        out.truncate(max_lines);
+        out.push(format!("  ... {} more diff lines", hidden));
+    }
+    out.join("\n")
+}
+
+/// Plain-text report shared by the `duplicates` command and `ragmcp_explain`
+///
+/// Lists document pairs, clusters and the top `max_pairs` chunk pairs; with `diff_lines > 0`
+/// each pair is followed by a diff of at most that many lines.
+pub fn format_report(report: &DuplicateReport, max_pairs: usize, diff_lines: usize) -> String {
+    let mut text = format!(
+        "Near-duplicate report: {} chunks scanned, {} duplicate pairs, {} clusters, {} document pairs\n",
+        report.chunks_scanned,
+        report.pairs.len(),
+        report.clusters.len(),
+        report.documents.len()
+    );
+    if report.pairs.is_empty() {
+        text.push_str("\nNo near-duplicates found.\n");
+        return text;
+    }
+
+    text.push_str("\nDocuments sharing content:\n");
+    for doc in &report.documents {
+        text.push_str(&format!(
+            "  {} <-> {}: {} shared chunks (text: {:.2}{})\n",
+            doc.doc_a,
+            doc.doc_b,
+            doc.shared_chunks,
+            doc.text_similarity,
+            format_embedding(doc.embedding_similarity)
+        ));
+    }
+
+    text.push_str("\nClusters:\n");
+    for (idx, cluster) in report.clusters.iter().enumerate() {
+        text.push_str(&format!(
+            "  {}. {} chunks across {}\n",
+            idx + 1,
+            cluster.chunk_ids.len(),
+            cluster.doc_paths.join(", ")
+        ));
+    }
+
+    text.push_str("\nChunk pairs:\n");
+    for (idx, pair) in report.pairs.iter().take(max_pairs).enumerate() {
+        text.push_str(&format!(
+            "\n{}. text: {:.2}{}\n   A: {} [{}] {}\n   B: {} [{}] {}\n",
+            idx + 1,
+            pair.text_similarity,
+            format_embedding(pair.embedding_similarity),
+            pair.a.doc_path,
+            pair.a.chunk_id,
+            pair.a.section.as_deref().unwrap_or(""),
+            pair.b.doc_path,
+            pair.b.chunk_id,
+            pair.b.section.as_deref().unwrap_or(""),
+        ));
+        if diff_lines > 0 {
+            for line in format_diff(&pair.a.text, &pair.b.text, diff_lines).lines() {
+                text.push_str(&format!("   | {}\n", line));
+            }
+        }
+    }
+    if report.pairs.len() > max_pairs {
+        text.push_str(&format!("\n... {} more pairs\n", report.pairs.len() - max_pairs));
+    }
+    text
+}
+
+fn format_embedding(similarity: Option<f32>) -> String {
+    similarity
+        .map(|s| format!(", embedding: {:.2}", s))
+        .unwrap_or_default()
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPLOY: &str = "To deploy the service run the release pipeline, wait for the canary \
+        to report healthy, then promote the build to all regions and watch the error rate dashboard.";
+
+    fn scan_chunk(doc_path: &str, idx: usize, text: &str, embedding: Option<Vec<f32>>) -> ScanChunk {
+        ScanChunk {
+            chunk: DuplicateChunk {
+                chunk_id: format!("{}::{}", doc_path, idx),
+                doc_path: doc_path.to_string(),
+                section: None,
+                text: text.to_string(),
+            },
+            embedding,
+        }
+    }
+
+    #[test]
+    fn test_shingles_and_jaccard() {
+        let a = shingles("The quick brown fox jumps", 2);
+        let b = shingles("the QUICK brown fox, jumps!", 2);
+        assert_eq!(a.len(), 4);
+        assert_eq!(jaccard(&a, &b), 1.0);
+        assert_eq!(shingles("two words", 5).len(), 1);
+        assert!(shingles("", 5).is_empty());
+    }
+
+    #[test]
+    fn test_minhash_estimates_jaccard() {
+        let a = shingles(DEPLOY, 3);
+        let b = shingles(&DEPLOY.replace("all regions", "every region"), 3);
+        let exact = jaccard(&a, &b);
+        let estimate = estimated_jaccard(&minhash_signature(&a, 256), &minhash_signature(&b, 256));
+        assert!((exact - estimate).abs() < 0.15, "exact {} vs estimate {}", exact, estimate);
+    }
+
+    #[test]
+    fn test_detect_text_duplicates_across_documents() {
+        let edited = DEPLOY.replace("error rate", "latency");
+        let chunks = vec![
+            scan_chunk("a.md", 0, DEPLOY, None),
+            scan_chunk("a.md", 1, DEPLOY, None),
+            scan_chunk("b.md", 0, &edited, None),
+            scan_chunk("c.md", 0, "Completely unrelated text about holiday schedules and office hours.", None),
+        ];
+        let config = DuplicatesConfig {
+            text_threshold: 0.7,
+            ..DuplicatesConfig::default()
+        };
+        let report = detect_duplicates(chunks, &config);
+
+        assert_eq!(report.chunks_scanned, 4);
+        assert_eq!(report.pairs.len(), 2, "same-document pair is excluded");
+        assert!(report.pairs.iter().all(|p| p.a.doc_path != p.b.doc_path));
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].chunk_ids.len(), 3);
+        assert_eq!(report.clusters[0].doc_paths, vec!["a.md", "b.md"]);
+        assert_eq!(report.documents.len(), 1);
+        assert_eq!(report.documents[0].shared_chunks, 2);
+
+        assert!(report.clone().involving("c.md").pairs.is_empty());
+        assert_eq!(report.involving("b.md").pairs.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_embedding_duplicates() {
+        let mut x = vec![0.0; 1536];
+        x[0] = 1.0;
+        let mut y = x.clone();
+        y[1] = 0.1;
+        let chunks = vec![
+            scan_chunk("a.md", 0, "Restart the worker when the queue stalls.", Some(x)),
+            scan_chunk("b.md", 0, "If jobs stop draining, bounce the worker process.", Some(y)),
+        ];
+        let report = detect_duplicates(chunks, &DuplicatesConfig::default());
+        assert_eq!(report.pairs.len(), 1);
+        assert!(report.pairs[0].embedding_similarity.unwrap() > 0.99);
+        assert!(report.pairs[0].text_similarity < 0.5);
+    }
+
+    #[test]
+    fn test_simhash_candidates_stay_bounded() {
+        // Embeddings sharing a strong common direction (pairwise cosine around 0.5),
+        // like those of one corpus, plus one planted near-duplicate pair. The 4.5M pairs
+        // must not all become candidates
+        let dim = 128;
+        let unit = |seed: u64| (mix(seed) as f64 / u64::MAX as f64 * 2.0 - 1.0) as f32;
+        let base: Vec<f32> = (0..dim as u64).map(unit).collect();
+        let mut chunks: Vec<ScanChunk> = (0..3000u64)
+            .map(|n| {
+                let embedding = base
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| b + unit((n + 1) << 20 | i as u64))
+                    .collect();
+                scan_chunk(&format!("doc{}.md", n), 0, "text", Some(embedding))
+            })
+            .collect();
+        let mut near = chunks[0].embedding.clone().unwrap();
+        near[0] += 0.05;
+        chunks.push(scan_chunk("copy.md", 0, "text", Some(near)));
+
+        let mut candidates = HashSet::new();
+        simhash_candidates(&chunks, &mut candidates);
+        assert!(candidates.contains(&(0, 3000)));
+        assert!(candidates.len() < 25 * chunks.len(), "{} candidates", candidates.len());
+    }
+
+    #[test]
+    fn test_format_diff() {
+        assert_eq!(format_diff("same text", "same text", 10), "(identical)");
+        assert_eq!(
+            format_diff("set timeout to 30 seconds", "set timeout to 60 seconds", 10),
+            "set timeout to [-30-] {+60+} seconds"
+        );
+        let diff = format_diff("one\ntwo\nthree\nfour", "one\ntwo\n3\nfour", 10);
+        assert_eq!(diff, "  (2 unchanged lines)\n- three\n+ 3\n  (1 unchanged lines)");
+    }
+}
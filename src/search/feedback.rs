@@ -0,0 +1,309 @@
+use crate::config::FeedbackConfig;
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::vector::{cosine_similarity, parse_embedding};
+use crate::search::{ScoreBoost, SearchResult};
+use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+
+/// A stored helpful/unhelpful judgement for one chunk
+#[derive(Debug, Clone)]
+pub struct FeedbackRow {
+    pub chunk_id: String,
+    /// -1 (unhelpful) or 1 (helpful)
+    pub feedback: i32,
+    /// Text of the query the judgement was given for
+    pub query_text: String,
+    pub query_embedding: Option<Vec<f32>>,
+    pub age_days: f64,
+}
+
+/// Look up the text of a logged query
+pub async fn query_text_for(db: &Db, query_id: &str) -> Result<String> {
+    let query_id = query_id.to_string();
+    db.with_connection(move |conn| {
+        conn.query_row(
+            "SELECT query_text FROM query_logs WHERE query_id = ?1",
+            params![query_id],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                RagmcpError::InvalidInput(format!("Unknown query_id: {}", query_id))
+            }
+            other => RagmcpError::Database(other),
+        })
+    }).await
+}
+
+/// Record per-chunk feedback for a logged query
+///
+/// Every chunk must be one the query returned. `query_logs.user_feedback` is set to the
+/// sign of the query's total feedback. Returns the number of judgements stored.
+pub async fn record_feedback(
+    db: &Db,
+    query_id: &str,
+    query_embedding: Option<Vec<f32>>,
+    judgements: Vec<(String, bool)>,
+) -> Result<usize> {
+    let query_id = query_id.to_string();
+    let blob: Option<Vec<u8>> = query_embedding
+        .map(|v| v.iter().flat_map(|f| f.to_le_bytes()).collect());
+
+    db.with_connection(move |conn| {
+        let retrieved_json: String = conn
+            .query_row(
+                "SELECT retrieved_chunk_ids FROM query_logs WHERE query_id = ?1",
+                params![query_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    RagmcpError::InvalidInput(format!("Unknown query_id: {}", query_id))
+                }
+                other => RagmcpError::Database(other),
+            })?;
+        let retrieved: HashSet<String> = serde_json::from_str::<Vec<String>>(&retrieved_json)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        if let Some((chunk_id, _)) = judgements.iter().find(|(id, _)| !retrieved.contains(id)) {
+            return Err(RagmcpError::InvalidInput(format!(
+                "Chunk {} was not returned by query {}",
+                chunk_id, query_id
+            )));
+        }
+
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                r#"
+                INSERT INTO chunk_feedback (query_id, chunk_id, feedback, query_embedding)
+                VALUES (?1, ?2, ?3, ?4)
+                "#,
+            )?;
+            for (chunk_id, helpful) in &judgements {
+                let value: i32 = if *helpful { 1 } else { -1 };
+                insert.execute(params![query_id, chunk_id, value, blob])?;
+            }
+        }
+        tx.execute(
+            r#"
+            UPDATE query_logs
+            SET user_feedback = (
+                SELECT CASE WHEN SUM(feedback) > 0 THEN 1 WHEN SUM(feedback) < 0 THEN -1 ELSE 0 END
+                FROM chunk_feedback WHERE query_id = ?1
+            )
+            WHERE query_id = ?1
+            "#,
+            params![query_id],
+        )?;
+        tx.commit()?;
+        Ok::<usize, RagmcpError>(judgements.len())
+    }).await
+}
+
+/// Load stored feedback for the given chunks, newest first
+pub async fn load_feedback(
+    db: &Db,
+    chunk_ids: Vec<String>,
+    max_age_days: f64,
+) -> Result<Vec<FeedbackRow>> {
+    if chunk_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT f.chunk_id, f.feedback, q.query_text, f.query_embedding,
+                julianday('now') - julianday(f.created_at) AS age_days
+            FROM chunk_feedback f
+            JOIN query_logs q ON f.query_id = q.query_id
+            WHERE f.chunk_id = ?1
+                AND julianday('now') - julianday(f.created_at) <= ?2
+            ORDER BY f.created_at DESC
+            "#,
+        )?;
+        let mut rows = Vec::new();
+        for chunk_id in chunk_ids {
+            let found = stmt.query_map(params![chunk_id, max_age_days], |row| {
+                let blob: Option<Vec<u8>> = row.get(3)?;
+                Ok(FeedbackRow {
+                    chunk_id: row.get(0)?,
+                    feedback: row.get(1)?,
+                    query_text: row.get(2)?,
+                    query_embedding: blob.as_deref().and_then(parse_embedding),
+                    age_days: row.get::<_, Option<f64>>(4)?.unwrap_or(0.0),
+                })
+            })?;
+            for row in found {
+                rows.push(row?);
+            }
+        }
+        Ok::<_, RagmcpError>(rows)
+    }).await
+}
+
+/// Apply the learned feedback boost to fused results
+///
+/// Feedback given for a similar query (cosine similarity of query embeddings at or above
+/// `similarity_threshold`, or identical normalized text when no embedding was stored)
+/// contributes `feedback * similarity * 0.5^(age / half_life)`. The summed signal maps to
+/// a factor in `(1 - weight, 1 + weight)` via `tanh`. Scores are rescaled if any exceeds 1.0.
+pub fn apply_feedback_boost(
+    results: Vec<SearchResult>,
+    feedback: &[FeedbackRow],
+    query_text: &str,
+    query_embedding: Option<&[f32]>,
+    config: &FeedbackConfig,
+) -> Vec<SearchResult> {
+    let normalized_query = normalize_query(query_text);
+    let mut signals: HashMap<&str, (f64, usize)> = HashMap::new();
+    for row in feedback {
+        let similarity = match (query_embedding, row.query_embedding.as_deref()) {
+            (Some(a), Some(b)) if a.len() == b.len() => cosine_similarity(a, b) as f64,
+            _ if normalize_query(&row.query_text) == normalized_query => 1.0,
+            _ => 0.0,
+        };
+        if similarity < config.similarity_threshold {
+            continue;
+        }
+        let decay = 0.5_f64.powf(row.age_days.max(0.0) / config.half_life_days);
+        let entry = signals.entry(row.chunk_id.as_str()).or_insert((0.0, 0));
+        entry.0 += row.feedback as f64 * similarity * decay;
+        entry.1 += 1;
+    }
+    if signals.is_empty() {
+        return results;
+    }
+
+    let mut boosted: Vec<SearchResult> = results
+        .into_iter()
+        .map(|mut result| {
+            if let Some((signal, votes)) = signals.get(result.chunk_id.as_str()) {
+                let factor = (1.0 + config.weight * signal.tanh()) as f32;
+                result.score *= factor;
+                result.boosts.push(ScoreBoost {
+                    kind: "feedback".to_string(),
+                    factor,
+                    detail: format!("{} judgement(s) on similar queries, signal {:+.2}", votes, signal),
+                });
+            }
+            result
+        })
+        .collect();
+
+    let max_score = boosted.iter().map(|r| r.score).fold(0.0_f32, f32::max);
+    if max_score > 1.0 {
+        for result in boosted.iter_mut() {
+            result.score /= max_score;
+        }
+    }
+    boosted.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    for (idx, result) in boosted.iter_mut().enumerate() {
+        result.rank = idx + 1;
+    }
+    boosted
+}
+
+fn normalize_query(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn result(chunk_id: &str, score: f32, rank: usize) -> SearchResult {
+        SearchResult {
+            chunk_id: chunk_id.to_string(),
+            doc_path: format!("{}.md", chunk_id),
+            doc_type: "markdown".to_string(),
+            agent_name: None,
+            section: None,
+            chunk_text: "text".to_string(),
+            score,
+            rank,
+            boosts: Vec::new(),
+        }
+    }
+
+    fn row(chunk_id: &str, feedback: i32, embedding: Vec<f32>) -> FeedbackRow {
+        FeedbackRow {
+            chunk_id: chunk_id.to_string(),
+            feedback,
+            query_text: "how do I rotate keys".to_string(),
+            query_embedding: Some(embedding),
+            age_days: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_feedback_boost_promotes_helpful_for_similar_query() {
+        let config = FeedbackConfig::default();
+        let results = vec![result("a", 1.0, 1), result("b", 0.9, 2)];
+        let feedback = vec![row("b", 1, vec![1.0, 0.0]), row("a", -1, vec![1.0, 0.0])];
+
+        let boosted = apply_feedback_boost(results, &feedback, "rotate keys", Some(&[1.0, 0.0]), &config);
+        assert_eq!(boosted[0].chunk_id, "b");
+        assert_eq!(boosted[0].rank, 1);
+        assert!(boosted[0].score <= 1.0);
+        assert_eq!(boosted[0].boosts[0].kind, "feedback");
+    }
+
+    #[test]
+    fn test_feedback_boost_ignores_dissimilar_queries() {
+        let config = FeedbackConfig::default();
+        let results = vec![result("a", 1.0, 1), result("b", 0.9, 2)];
+        let feedback = vec![row("b", 1, vec![0.0, 1.0])];
+
+        let boosted = apply_feedback_boost(results, &feedback, "unrelated", Some(&[1.0, 0.0]), &config);
+        assert_eq!(boosted[0].chunk_id, "a");
+        assert!(boosted.iter().all(|r| r.boosts.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_record_and_load_feedback() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+        db.with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO query_logs (query_id, query_text, retrieval_method, retrieved_chunk_ids, latency_ms, result_count) VALUES ('q1', 'rotate keys', 'hybrid', '[\"c1\",\"c2\"]', 5, 2)",
+                [],
+            )?;
+            Ok::<_, RagmcpError>(())
+        }).await.unwrap();
+
+        assert_eq!(query_text_for(&db, "q1").await.unwrap(), "rotate keys");
+        assert!(query_text_for(&db, "missing").await.is_err());
+
+        let stored = record_feedback(&db, "q1", Some(vec![1.0, 0.0]), vec![("c1".to_string(), true)])
+            .await
+            .unwrap();
+        assert_eq!(stored, 1);
+        let err = record_feedback(&db, "q1", None, vec![("c9".to_string(), true)]).await;
+        assert!(err.is_err(), "chunks not returned by the query are rejected");
+
+        let user_feedback: Option<i64> = db.with_connection(|conn| {
+            Ok::<_, RagmcpError>(conn.query_row(
+                "SELECT user_feedback FROM query_logs WHERE query_id = 'q1'",
+                [],
+                |row| row.get(0),
+            )?)
+        }).await.unwrap();
+        assert_eq!(user_feedback, Some(1));
+
+        let rows = load_feedback(&db, vec!["c1".to_string(), "c2".to_string()], 365.0).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].feedback, 1);
+        assert_eq!(rows[0].query_embedding.as_deref(), Some(&[1.0, 0.0][..]));
+    }
+}
@@ -0,0 +1,165 @@
+//! Document metadata filters for search (`{"status": "published", "tags": "networking"}`).
+//!
+//! Filters match the parser metadata stored in `documents.metadata_json` (Markdown
+//! frontmatter, HTML meta tags, e-mail headers, ...). Every key must match: a scalar
+//! value must equal the filter value and a list value (e.g. `tags`) must contain it,
+//! both compared case-insensitively.
+
+use serde_json::{Map, Value};
+
+use crate::error::{Result, RagmcpError};
+
+/// Key/value conditions on document metadata, all of which must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MetadataFilter {
+    conditions: Vec<(String, String)>,
+}
+
+impl MetadataFilter {
+    /// Build a filter from a JSON object of string or number values.
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let object = value.as_object().ok_or_else(|| {
+            RagmcpError::InvalidInput("filters must be an object of metadata key/value pairs".to_string())
+        })?;
+        let mut filter = Self::default();
+        for (key, value) in object {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                other => {
+                    return Err(RagmcpError::InvalidInput(format!(
+                        "filter '{}' must be a string or number (got {})",
+                        key, other
+                    )))
+                }
+            };
+            filter.push(key, &value)?;
+        }
+        Ok(filter)
+    }
+
+    /// Build a filter from `key=value` pairs (CLI `--filter status=published`).
+    pub fn from_pairs<S: AsRef<str>>(pairs: &[S]) -> Result<Self> {
+        let mut filter = Self::default();
+        for pair in pairs {
+            let (key, value) = pair.as_ref().split_once('=').ok_or_else(|| {
+                RagmcpError::InvalidInput(format!("filter '{}' must be key=value", pair.as_ref()))
+            })?;
+            filter.push(key.trim(), value.trim())?;
+        }
+        Ok(filter)
+    }
+
+    fn push(&mut self, key: &str, value: &str) -> Result<()> {
+        // Keys become part of a JSON path, so only plain identifiers are accepted
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+            return Err(RagmcpError::InvalidInput(format!(
+                "invalid filter key '{}' (letters, digits, '_' and '-' only)",
+                key
+            )));
+        }
+        self.conditions.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// JSON object bound to the parameter of `metadata_filter_sql`; `None` when empty,
+    /// which disables the condition.
+    pub fn sql_param(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        let object: Map<String, Value> = self
+            .conditions
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+        Some(Value::Object(object).to_string())
+    }
+
+    /// Human-readable form (`status=published, tags=dr`) for logs and CLI output.
+    pub fn describe(&self) -> String {
+        self.conditions
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// SQL condition matching `d.metadata_json` against a filter bound at `placeholder`
+/// (e.g. `?10`, or `?` bound twice). A NULL parameter matches every document.
+pub fn metadata_filter_sql(placeholder: &str) -> String {
+    format!(
+        r#"({p} IS NULL OR NOT EXISTS (
+            SELECT 1 FROM json_each({p}) AS f
+            WHERE NOT EXISTS (
+                SELECT 1 FROM json_each(d.metadata_json, '$."' || f.key || '"') AS m
+                WHERE lower(CAST(m.value AS TEXT)) = lower(f.value)
+            )
+        ))"#,
+        p = placeholder
+    )
+}
+
+/// Optional filter as the SQL parameter of `metadata_filter_sql`.
+pub fn filter_param(filter: Option<&MetadataFilter>) -> Option<String> {
+    filter.and_then(MetadataFilter::sql_param)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter_parsing() {
+        let filter = MetadataFilter::from_json(&json!({ "status": "published", "version": 2 })).unwrap();
+        assert_eq!(filter.describe(), "status=published, version=2");
+        assert!(MetadataFilter::from_json(&json!({ "tags": ["a"] })).is_err());
+        assert!(MetadataFilter::from_json(&json!("status")).is_err());
+
+        let filter = MetadataFilter::from_pairs(&["owner = sre", "tags=dr"]).unwrap();
+        assert_eq!(filter.sql_param().unwrap(), r#"{"owner":"sre","tags":"dr"}"#);
+        assert!(MetadataFilter::from_pairs(&["owner"]).is_err());
+        assert!(MetadataFilter::from_pairs(&["a.b=c"]).is_err());
+        assert_eq!(MetadataFilter::default().sql_param(), None);
+    }
+
+    #[test]
+    fn test_metadata_filter_sql() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE documents (doc_path TEXT, metadata_json TEXT);
+            INSERT INTO documents VALUES
+                ('a.md', '{"status":"published","tags":["Networking","dr"],"version":2}'),
+                ('b.md', '{"status":"draft","tags":["networking"]}'),
+                ('c.md', NULL);
+            "#,
+        )
+        .unwrap();
+        let matching = |filter: Option<MetadataFilter>| -> Vec<String> {
+            let sql = format!(
+                "SELECT doc_path FROM documents d WHERE {} ORDER BY doc_path",
+                metadata_filter_sql("?1")
+            );
+            let mut stmt = conn.prepare(&sql).unwrap();
+            stmt.query_map([filter_param(filter.as_ref())], |row| row.get(0))
+                .unwrap()
+                .collect::<std::result::Result<_, _>>()
+                .unwrap()
+        };
+
+        assert_eq!(matching(None), vec!["a.md", "b.md", "c.md"]);
+        let tags = MetadataFilter::from_pairs(&["tags=networking"]).unwrap();
+        assert_eq!(matching(Some(tags)), vec!["a.md", "b.md"]);
+        let both = MetadataFilter::from_pairs(&["tags=networking", "status=Published"]).unwrap();
+        assert_eq!(matching(Some(both)), vec!["a.md"]);
+        let number = MetadataFilter::from_json(&json!({ "version": 2 })).unwrap();
+        assert_eq!(matching(Some(number)), vec!["a.md"]);
+    }
+}
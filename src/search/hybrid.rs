@@ -1,11 +1,12 @@
 use crate::db::Db;
 use crate::embeddings::OpenAIEmbedder;
 use crate::error::Result;
-use crate::search::{bm25, vector, SearchResult};
+use crate::search::{bm25, feedback, recency, vector, MetadataFilter, SearchResult};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::cache::ChunkEmbeddingCache;
+use crate::config::SearchConfig;
 
 /// Search documents using hybrid approach combining BM25 and vector search
 ///
@@ -17,13 +18,14 @@ use crate::cache::ChunkEmbeddingCache;
 ///
 /// * `db` - Database connection wrapper
 /// * `embedder` - OpenAI embedder instance
+/// * `dimensions` - Expected embedding dimension (`[embeddings] dimensions`)
 /// * `query` - Search query text
 /// * `namespace` - Optional namespace filter (directory-derived; e.g. agents, system, self, community); None = search all
 /// * `agent_filter` - Optional agent name filter (documents.agent_name = ?)
+/// * `metadata_filter` - Optional document metadata filter (frontmatter/parser metadata in documents.metadata_json)
 /// * `k` - Maximum number of results to return
 /// * `min_score` - Minimum RRF score threshold (0.0-1.0)
-/// * `bm25_weight` - Weight for BM25 results in fusion (typically 0.3-0.7)
-/// * `vector_weight` - Weight for vector results in fusion (typically 0.3-0.7)
+/// * `search_config` - Fusion weights (`hybrid_bm25_weight`, `hybrid_vector_weight`), recency boosting and BM25 settings
 /// * `chunk_cache` - Optional in-memory chunk embedding cache for faster vector search
 ///
 /// # Returns
@@ -36,7 +38,12 @@ use crate::cache::ChunkEmbeddingCache;
 /// - Over-fetching: Retrieves `k * 2` results from each method for better fusion quality
 /// - Parallel execution: Runs both searches concurrently using `tokio::join!`
 /// - RRF constant: K = 60.0 (standard default from research)
-/// - Namespace and agent filtering are applied inside vector search SQL (no post-filter).
+/// - Namespace and agent filtering are applied inside vector search SQL (no post-filter);
+///   the metadata filter is applied in both BM25 and vector SQL.
+/// - Recency: when `search_config.recency.enabled`, normalized scores are time-decayed
+///   by document age before the threshold is applied (see `recency::apply_recency_boost`).
+/// - Feedback: chunks judged on similar past queries are promoted or demoted
+///   (see `feedback::apply_feedback_boost`) unless `search_config.feedback.enabled` is off.
 ///
 /// # Example
 ///
@@ -55,13 +62,14 @@ use crate::cache::ChunkEmbeddingCache;
 /// let results = search_hybrid(
 ///     &db,
 ///     &embedder,
+///     config.embeddings.dimensions,
 ///     "What are the core concepts of module-alpha?",
 ///     None,  // namespace
 ///     None,  // agent_filter
+///     None,  // metadata_filter
 ///     5,
 ///     0.65,
-///     0.5,
-///     0.5,
+///     &config.search,
 ///     None,  // chunk_cache
 /// ).await?;
 ///
@@ -71,16 +79,18 @@ use crate::cache::ChunkEmbeddingCache;
 /// # Ok(())
 /// # }
 /// ```
+#[allow(clippy::too_many_arguments)]
 pub async fn search_hybrid(
     db: &Db,
     embedder: &OpenAIEmbedder,
+    dimensions: usize,
     query: &str,
     namespace: Option<&str>,
     agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
     k: usize,
     min_score: f32,
-    bm25_weight: f32,
-    vector_weight: f32,
+    search_config: &SearchConfig,
     chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
 ) -> Result<Vec<SearchResult>> {
     let total_start = std::time::Instant::now();
@@ -88,18 +98,21 @@ pub async fn search_hybrid(
     // Over-fetch from each method (k * 4) for better fusion quality in RAG use case
     let fetch_k = k * 4;
 
-    // Run both searches in parallel; vector search applies namespace/agent filter in SQL
+    // Run both searches in parallel; vector search applies namespace/agent filter in SQL,
+    // both apply the metadata filter
     let search_start = std::time::Instant::now();
     let (bm25_results, vector_results) = tokio::join!(
-        bm25::search_bm25(db, query, namespace, None, fetch_k, 0.0),
+        bm25::search_bm25(db, query, namespace, None, metadata_filter, fetch_k, 0.0, search_config),
         vector::search_vector(
             db,
             embedder,
+            dimensions,
             query,
             fetch_k,
             0.0,
             namespace,
             agent_filter,
+            metadata_filter,
             chunk_cache,
         )
     );
@@ -115,8 +128,8 @@ pub async fn search_hybrid(
         bm25_results,
         vector_results,
         k,
-        bm25_weight,
-        vector_weight,
+        search_config.hybrid_bm25_weight,
+        search_config.hybrid_vector_weight,
     );
     let fusion_duration = fusion_start.elapsed();
     log::debug!("Hybrid search: RRF fusion took {:?}", fusion_duration);
@@ -148,6 +161,34 @@ pub async fn search_hybrid(
         min_score
     };
     
+    // Optional time-decay boost, applied to fused scores before thresholding
+    let normalized = if search_config.recency.enabled {
+        recency::apply_recency_boost(db, normalized, &search_config.recency, chrono::Utc::now()).await?
+    } else {
+        normalized
+    };
+    
+    // Learned boost from relevance feedback on similar queries (kill switch: feedback.enabled)
+    let normalized = if search_config.feedback.enabled {
+        let chunk_ids = normalized.iter().map(|r| r.chunk_id.clone()).collect();
+        let rows = feedback::load_feedback(db, chunk_ids, search_config.feedback.max_age_days).await?;
+        if rows.is_empty() {
+            normalized
+        } else {
+            // A cache hit after the vector leg when the embedder has an LRU cache
+            let query_vec = embedder.embed_with_cache(query, 3).await.ok();
+            feedback::apply_feedback_boost(
+                normalized,
+                &rows,
+                query,
+                query_vec.as_deref(),
+                &search_config.feedback,
+            )
+        }
+    } else {
+        normalized
+    };
+    
     // Filter by adaptive threshold
     // The adaptive threshold (lowered to 0.2 for tight distributions) ensures good recall
     let filtered: Vec<SearchResult> = normalized
@@ -279,6 +320,7 @@ mod tests {
             chunk_text: "test content".to_string(),
             score,
             rank,
+            boosts: Vec::new(),
         }
     }
 
@@ -475,6 +517,7 @@ mod tests {
             chunk_text: "Module Alpha provides core analysis capabilities".to_string(),
             score: 0.9,
             rank: 1,
+            boosts: Vec::new(),
         }];
 
         let vector_results = vec![];
@@ -0,0 +1,250 @@
+use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::HashSet;
+
+/// Languages supported by the BM25 analyzer
+///
+/// English documents are served by the Porter-stemmed `chunks_fts` table.
+/// Every other language is indexed in `chunks_fts_stemmed` with text that has
+/// already been stemmed (Snowball) and stripped of stop words in Rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Italian,
+    Dutch,
+    Portuguese,
+}
+
+const ENGLISH_STOP_WORDS: &[&str] = &[
+    "the", "a", "an", "and", "or", "but", "in", "on", "at", "to", "for", "of", "with",
+    "by", "from", "as", "is", "are", "was", "were", "be", "been", "being", "have",
+    "has", "had", "do", "does", "did", "will", "would", "should", "could", "what",
+    "which", "who", "where", "when", "why", "how", "this", "that", "these", "those",
+];
+
+const GERMAN_STOP_WORDS: &[&str] = &[
+    "der", "die", "das", "den", "dem", "des", "ein", "eine", "einer", "eines", "einem",
+    "einen", "und", "oder", "aber", "in", "im", "an", "am", "auf", "aus", "bei", "mit",
+    "nach", "von", "vom", "zu", "zum", "zur", "für", "ist", "sind", "war", "waren",
+    "wird", "werden", "wurde", "hat", "haben", "hatte", "nicht", "auch", "als", "wie",
+    "was", "wer", "wo", "wann", "warum", "dass", "sich", "es", "er", "sie", "wir",
+    "ich", "du", "ihr", "noch", "nur", "so", "über", "unter", "durch", "wenn",
+];
+
+const FRENCH_STOP_WORDS: &[&str] = &[
+    "le", "la", "les", "un", "une", "des", "du", "de", "et", "ou", "mais", "dans", "en",
+    "sur", "au", "aux", "à", "pour", "par", "avec", "sans", "est", "sont", "était",
+    "été", "être", "avoir", "a", "ont", "ce", "cet", "cette", "ces", "qui", "que",
+    "quoi", "où", "quand", "comment", "pourquoi", "ne", "pas", "plus", "il", "elle",
+    "ils", "elles", "nous", "vous", "je", "tu", "on", "se", "sa", "son", "ses", "leur",
+];
+
+const SPANISH_STOP_WORDS: &[&str] = &[
+    "el", "la", "los", "las", "un", "una", "unos", "unas", "y", "o", "pero", "en", "de",
+    "del", "al", "a", "por", "para", "con", "sin", "es", "son", "era", "fue", "ser",
+    "estar", "está", "están", "ha", "han", "que", "qué", "quien", "donde", "cuando",
+    "como", "cómo", "porque", "no", "más", "se", "su", "sus", "lo", "le", "les", "este",
+    "esta", "estos", "estas", "ese", "esa",
+];
+
+const ITALIAN_STOP_WORDS: &[&str] = &[
+    "il", "lo", "la", "i", "gli", "le", "un", "uno", "una", "e", "o", "ma", "in", "di",
+    "del", "della", "dei", "delle", "a", "al", "alla", "da", "dal", "per", "con", "su",
+    "è", "sono", "era", "essere", "ha", "hanno", "che", "chi", "dove", "quando", "come",
+    "perché", "non", "più", "si", "questo", "questa", "quello", "quella",
+];
+
+const DUTCH_STOP_WORDS: &[&str] = &[
+    "de", "het", "een", "en", "of", "maar", "in", "op", "aan", "te", "van", "voor",
+    "met", "door", "bij", "uit", "is", "zijn", "was", "waren", "wordt", "worden", "heeft",
+    "hebben", "had", "niet", "ook", "als", "dat", "die", "dit", "deze", "wat", "wie",
+    "waar", "wanneer", "waarom", "hoe", "er", "zich", "hij", "zij", "wij", "ik", "je",
+];
+
+const PORTUGUESE_STOP_WORDS: &[&str] = &[
+    "o", "a", "os", "as", "um", "uma", "uns", "umas", "e", "ou", "mas", "em", "no", "na",
+    "nos", "nas", "de", "do", "da", "dos", "das", "por", "para", "com", "sem", "é",
+    "são", "era", "foi", "ser", "estar", "tem", "têm", "que", "quem", "onde", "quando",
+    "como", "porque", "não", "mais", "se", "seu", "sua", "este", "esta", "esse", "essa",
+];
+
+impl Language {
+    /// All supported languages, in detection priority order
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Dutch,
+        Language::Portuguese,
+    ];
+
+    /// Parse a language from its English name or ISO 639-1 code (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "en" | "english" => Some(Language::English),
+            "de" | "german" | "deutsch" => Some(Language::German),
+            "fr" | "french" | "français" | "francais" => Some(Language::French),
+            "es" | "spanish" | "español" | "espanol" => Some(Language::Spanish),
+            "it" | "italian" | "italiano" => Some(Language::Italian),
+            "nl" | "dutch" | "nederlands" => Some(Language::Dutch),
+            "pt" | "portuguese" | "português" | "portugues" => Some(Language::Portuguese),
+            _ => None,
+        }
+    }
+
+    /// ISO 639-1 code, stored in `documents.language` and `chunks_fts_stemmed.language`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Dutch => "nl",
+            Language::Portuguese => "pt",
+        }
+    }
+
+    /// Built-in stop words for this language (lowercase)
+    pub fn stop_words(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => ENGLISH_STOP_WORDS,
+            Language::German => GERMAN_STOP_WORDS,
+            Language::French => FRENCH_STOP_WORDS,
+            Language::Spanish => SPANISH_STOP_WORDS,
+            Language::Italian => ITALIAN_STOP_WORDS,
+            Language::Dutch => DUTCH_STOP_WORDS,
+            Language::Portuguese => PORTUGUESE_STOP_WORDS,
+        }
+    }
+
+    /// Whether this language is served by the Porter-stemmed `chunks_fts` table
+    pub fn uses_porter_table(&self) -> bool {
+        *self == Language::English
+    }
+
+    fn algorithm(&self) -> Algorithm {
+        match self {
+            Language::English => Algorithm::English,
+            Language::German => Algorithm::German,
+            Language::French => Algorithm::French,
+            Language::Spanish => Algorithm::Spanish,
+            Language::Italian => Algorithm::Italian,
+            Language::Dutch => Algorithm::Dutch,
+            Language::Portuguese => Algorithm::Portuguese,
+        }
+    }
+}
+
+/// Split `text` into lowercase alphanumeric tokens of at least two characters
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 2)
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Tokenize, drop stop words and stem `text` for the given language
+///
+/// Tokens are split on non-alphanumeric characters and lowercased. Single-character
+/// tokens are dropped, mirroring `sanitize_fts5_query`.
+pub fn analyze(text: &str, language: Language, stop_words: &HashSet<String>) -> Vec<String> {
+    let stemmer = Stemmer::create(language.algorithm());
+    tokenize(text)
+        .into_iter()
+        .filter(|t| !stop_words.contains(t))
+        .map(|t| stemmer.stem(&t).into_owned())
+        .collect()
+}
+
+/// Guess the language of `text` by counting stop-word hits
+///
+/// Returns `None` when the text is too short or no language clearly wins,
+/// in which case callers fall back to the configured default.
+pub fn detect_language(text: &str) -> Option<Language> {
+    // Only the first few thousand characters are needed for a stable guess
+    let sample: String = text.chars().take(4000).collect();
+    let tokens: Vec<String> = sample
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect();
+    if tokens.len() < 5 {
+        return None;
+    }
+
+    let mut best: Option<(Language, usize)> = None;
+    let mut runner_up = 0;
+    for lang in Language::ALL {
+        let words: HashSet<&str> = lang.stop_words().iter().copied().collect();
+        let hits = tokens.iter().filter(|t| words.contains(t.as_str())).count();
+        match best {
+            Some((_, best_hits)) if hits <= best_hits => runner_up = runner_up.max(hits),
+            _ => {
+                runner_up = best.map(|(_, h)| h).unwrap_or(0);
+                best = Some((lang, hits));
+            }
+        }
+    }
+
+    match best {
+        Some((lang, hits)) if hits >= 3 && hits > runner_up => Some(lang),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop_set(lang: Language) -> HashSet<String> {
+        lang.stop_words().iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Language::from_name("de"), Some(Language::German));
+        assert_eq!(Language::from_name("French"), Some(Language::French));
+        assert_eq!(Language::from_name(" english "), Some(Language::English));
+        assert_eq!(Language::from_name("klingon"), None);
+    }
+
+    #[test]
+    fn test_analyze_german_stems_and_drops_stop_words() {
+        let stop = stop_set(Language::German);
+        let plural = analyze("Die Häuser und die Gärten", Language::German, &stop);
+        let singular = analyze("das Haus", Language::German, &stop);
+        assert_eq!(plural.len(), 2, "stop words should be removed: {:?}", plural);
+        assert_eq!(singular.len(), 1);
+        assert_eq!(plural[0], singular[0], "Häuser and Haus should share a stem");
+    }
+
+    #[test]
+    fn test_analyze_french() {
+        let stop = stop_set(Language::French);
+        let a = analyze("les documents techniques", Language::French, &stop);
+        let b = analyze("le document technique", Language::French, &stop);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("Der Agent ist für die Planung und die Ausführung der Aufgaben zuständig."),
+            Some(Language::German)
+        );
+        assert_eq!(
+            detect_language("Le système est responsable de la planification et de la gestion des tâches."),
+            Some(Language::French)
+        );
+        assert_eq!(
+            detect_language("The agent is responsible for the planning and the execution of tasks."),
+            Some(Language::English)
+        );
+        assert_eq!(detect_language("short"), None);
+    }
+}
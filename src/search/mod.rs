@@ -1,5 +1,13 @@
 pub mod bm25;
 pub mod vector;
 pub mod hybrid;
+pub mod documents;
+pub mod duplicates;
+pub mod feedback;
+pub mod language;
+pub mod recency;
+pub mod similar;
+pub mod filter;
 
-pub use bm25::SearchResult;
+pub use bm25::{ScoreBoost, SearchResult};
+pub use filter::MetadataFilter;
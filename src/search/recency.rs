@@ -0,0 +1,158 @@
+use crate::config::RecencyConfig;
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::{ScoreBoost, SearchResult};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Time-decay factor for a document of the given age
+///
+/// `decay = 0.5^(age / half_life)` is blended with `weight` so fresh documents keep
+/// their score (factor 1.0) and very old ones fall towards `1.0 - weight`.
+pub fn recency_factor(age_days: f64, half_life_days: f64, weight: f64) -> f32 {
+    if half_life_days <= 0.0 {
+        return 1.0;
+    }
+    let decay = 0.5_f64.powf(age_days.max(0.0) / half_life_days);
+    (1.0 - weight + weight * decay) as f32
+}
+
+/// Apply recency boosting to fused search results
+///
+/// Looks up `last_modified` and namespace for each result's document, multiplies the
+/// score by `recency_factor` (half-life per namespace, falling back to the global value)
+/// and re-sorts. The applied factor is recorded in `SearchResult::boosts` for explain output.
+pub async fn apply_recency_boost(
+    db: &Db,
+    results: Vec<SearchResult>,
+    config: &RecencyConfig,
+    now: DateTime<Utc>,
+) -> Result<Vec<SearchResult>> {
+    if results.is_empty() {
+        return Ok(results);
+    }
+
+    let mut doc_paths: Vec<String> = results.iter().map(|r| r.doc_path.clone()).collect();
+    doc_paths.sort();
+    doc_paths.dedup();
+
+    // doc_path -> (namespace, last_modified)
+    let documents: HashMap<String, (String, String)> = db.with_connection(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT namespace, last_modified FROM documents WHERE doc_path = ?1",
+        )?;
+        let mut map = HashMap::new();
+        for path in doc_paths {
+            if let Ok(row) = stmt.query_row(rusqlite::params![path], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            }) {
+                map.insert(path, row);
+            }
+        }
+        Ok::<_, RagmcpError>(map)
+    }).await?;
+
+    let mut boosted: Vec<SearchResult> = results
+        .into_iter()
+        .map(|mut result| {
+            let Some((namespace, last_modified)) = documents.get(&result.doc_path) else {
+                return result;
+            };
+            let Ok(modified) = DateTime::parse_from_rfc3339(last_modified) else {
+                return result;
+            };
+            let age_days = (now - modified.with_timezone(&Utc)).num_seconds() as f64 / 86_400.0;
+            let half_life = config.half_life_for(namespace);
+            let factor = recency_factor(age_days, half_life, config.weight);
+            result.score *= factor;
+            result.boosts.push(ScoreBoost {
+                kind: "recency".to_string(),
+                factor,
+                detail: format!(
+                    "modified {:.0} days ago, half-life {:.0} days",
+                    age_days.max(0.0),
+                    half_life
+                ),
+            });
+            result
+        })
+        .collect();
+
+    boosted.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    for (idx, result) in boosted.iter_mut().enumerate() {
+        result.rank = idx + 1;
+    }
+
+    Ok(boosted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+    use crate::ingest::db_writer::insert_document;
+    use std::path::Path;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recency_factor() {
+        assert!((recency_factor(0.0, 30.0, 0.5) - 1.0).abs() < 1e-6);
+        assert!((recency_factor(30.0, 30.0, 0.5) - 0.75).abs() < 1e-6);
+        let ancient = recency_factor(3650.0, 30.0, 0.5);
+        assert!((0.5..0.51).contains(&ancient), "floor is 1 - weight");
+        assert!((recency_factor(100.0, 0.0, 0.5) - 1.0).abs() < 1e-6, "zero half-life disables decay");
+    }
+
+    fn result(doc_path: &str, score: f32, rank: usize) -> SearchResult {
+        SearchResult {
+            chunk_id: format!("{}::0", doc_path),
+            doc_path: doc_path.to_string(),
+            doc_type: "markdown".to_string(),
+            agent_name: None,
+            section: None,
+            chunk_text: "text".to_string(),
+            score,
+            rank,
+            boosts: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_recency_boost_reorders_stale_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+
+        let now = SystemTime::now();
+        let year_ago = now - Duration::from_secs(365 * 86_400);
+        insert_document(&db, "runbooks/old.md", "markdown", "runbooks", None, "old", 1, "h1", year_ago)
+            .await
+            .unwrap();
+        insert_document(&db, "runbooks/new.md", "markdown", "runbooks", None, "new", 1, "h2", now)
+            .await
+            .unwrap();
+
+        let config = RecencyConfig {
+            enabled: true,
+            ..RecencyConfig::default()
+        };
+        let boosted = apply_recency_boost(
+            &db,
+            vec![result("runbooks/old.md", 1.0, 1), result("runbooks/new.md", 0.9, 2)],
+            &config,
+            Utc::now(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(boosted[0].doc_path, "runbooks/new.md");
+        assert_eq!(boosted[0].rank, 1);
+        assert_eq!(boosted[1].boosts[0].kind, "recency");
+        assert!(boosted[1].boosts[0].factor < 0.8);
+    }
+}
@@ -0,0 +1,271 @@
+//! "More like this" search seeded by a stored document or chunk.
+//!
+//! The query vector is a stored chunk embedding, or the centroid of a document's chunk
+//! embeddings, so no text is embedded. Chunks of the seed document are excluded.
+
+use crate::cache::ChunkEmbeddingCache;
+use crate::db::Db;
+use crate::error::{Result, RagmcpError};
+use crate::search::vector::{parse_embedding, search_vector_by_embedding};
+use crate::search::SearchResult;
+use rusqlite::params;
+use std::sync::Arc;
+
+/// Resolved seed of a similarity search
+#[derive(Debug, Clone)]
+pub struct SimilarSeed {
+    pub doc_path: String,
+    /// Set when the seed is a single chunk rather than a whole document
+    pub chunk_id: Option<String>,
+    /// Query vector: the chunk embedding or the document centroid
+    pub vector: Vec<f32>,
+    /// Number of chunks in the seed document (used to over-fetch before exclusion)
+    pub doc_chunk_count: usize,
+}
+
+/// Mean of equally sized vectors, or `None` if there are none
+pub fn centroid(vectors: &[Vec<f32>]) -> Option<Vec<f32>> {
+    let first = vectors.first()?;
+    let mut sum = vec![0.0_f32; first.len()];
+    let mut count = 0;
+    for vector in vectors.iter().filter(|v| v.len() == first.len()) {
+        for (acc, value) in sum.iter_mut().zip(vector) {
+            *acc += value;
+        }
+        count += 1;
+    }
+    Some(sum.into_iter().map(|v| v / count as f32).collect())
+}
+
+/// Resolve `like` to a seed: a chunk id if one matches, otherwise a document path
+///
+/// Seeds whose embeddings do not have the configured `dimensions` (left over from
+/// another model) are rejected.
+pub async fn resolve_seed(db: &Db, like: &str, dimensions: usize) -> Result<SimilarSeed> {
+    let like = like.trim().to_string();
+    let seed = db.with_connection(move |conn| {
+        let chunk = conn.query_row(
+            r#"
+            SELECT d.doc_path, c.embedding,
+                (SELECT COUNT(*) FROM chunks WHERE doc_id = c.doc_id)
+            FROM chunks c
+            JOIN documents d ON c.doc_id = d.doc_id
+            WHERE c.chunk_id = ?1
+            "#,
+            params![like],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<Vec<u8>>>(1)?, row.get::<_, i64>(2)?)),
+        );
+        match chunk {
+            Ok((doc_path, blob, doc_chunk_count)) => {
+                let vector = blob.as_deref().and_then(parse_embedding).ok_or_else(|| {
+                    RagmcpError::InvalidInput(format!(
+                        "Chunk {} has no embedding yet; run `embed` first",
+                        like
+                    ))
+                })?;
+                return Ok(SimilarSeed {
+                    doc_path,
+                    chunk_id: Some(like),
+                    vector,
+                    doc_chunk_count: doc_chunk_count as usize,
+                });
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(RagmcpError::Database(e)),
+        }
+
+        let doc_id: String = conn
+            .query_row(
+                "SELECT doc_id FROM documents WHERE doc_path = ?1",
+                params![like],
+                |row| row.get(0),
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => RagmcpError::DocumentNotFound(like.clone()),
+                other => RagmcpError::Database(other),
+            })?;
+        let mut stmt = conn.prepare("SELECT embedding FROM chunks WHERE doc_id = ?1")?;
+        let blobs: Vec<Option<Vec<u8>>> = stmt
+            .query_map(params![doc_id], |row| row.get(0))?
+            .collect::<std::result::Result<_, _>>()?;
+        let doc_chunk_count = blobs.len();
+        let vectors: Vec<Vec<f32>> = blobs.iter().flatten().filter_map(|b| parse_embedding(b)).collect();
+        let vector = centroid(&vectors).ok_or_else(|| {
+            RagmcpError::InvalidInput(format!(
+                "Document {} has no chunk embeddings yet; run `embed` first",
+                like
+            ))
+        })?;
+        Ok::<_, RagmcpError>(SimilarSeed {
+            doc_path: like,
+            chunk_id: None,
+            vector,
+            doc_chunk_count,
+        })
+    }).await?;
+
+    if seed.vector.len() != dimensions {
+        return Err(RagmcpError::InvalidInput(format!(
+            "{} has {}-dimensional embeddings but the index uses {}; re-run `embed` for it",
+            seed.chunk_id.as_deref().unwrap_or(&seed.doc_path),
+            seed.vector.len(),
+            dimensions
+        )));
+    }
+    Ok(seed)
+}
+
+/// Find chunks most similar to the seed, excluding the seed document
+///
+/// Over-fetches by the seed document's chunk count so excluding it still leaves `k` results.
+pub async fn search_similar(
+    db: &Db,
+    seed: &SimilarSeed,
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    k: usize,
+    min_score: f32,
+    chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
+) -> Result<Vec<SearchResult>> {
+    let fetch_k = k + seed.doc_chunk_count;
+    let results = search_vector_by_embedding(
+        db,
+        &seed.vector,
+        fetch_k,
+        min_score,
+        namespace,
+        agent_filter,
+        None,
+        chunk_cache,
+    )
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .filter(|r| r.doc_path != seed.doc_path)
+        .take(k)
+        .enumerate()
+        .map(|(idx, mut r)| {
+            r.rank = idx + 1;
+            r
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+    use crate::embeddings::store_embedding;
+    use crate::ingest::chunker::Chunk;
+    use crate::ingest::db_writer::{insert_chunks, insert_document};
+    use std::path::Path;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_centroid() {
+        let c = centroid(&[vec![1.0, 0.0], vec![0.0, 1.0]]).unwrap();
+        assert_eq!(c, vec![0.5, 0.5]);
+        assert!(centroid(&[]).is_none());
+    }
+
+    /// Unit vector in 1536 dims pointing mostly along `axis`
+    fn embedding(axis: usize, noise_axis: usize) -> Vec<f32> {
+        let mut v = vec![0.0; 1536];
+        v[axis] = 1.0;
+        v[noise_axis] = 0.2;
+        v
+    }
+
+    async fn add_doc(db: &Db, path: &str, embeddings: Vec<Vec<f32>>) {
+        let doc_id = insert_document(db, path, "markdown", "runbooks", None, "text", 10, path, SystemTime::now())
+            .await
+            .unwrap();
+        let chunks = embeddings
+            .iter()
+            .map(|_| Chunk {
+                text: format!("chunk of {}", path),
+                tokens: 3,
+                section_header: None,
+                chunk_type: None,
+                page: None,
+            })
+            .collect();
+        insert_chunks(db, &doc_id, chunks).await.unwrap();
+        for (idx, e) in embeddings.iter().enumerate() {
+            store_embedding(db, &format!("{}::{}", doc_id, idx), e).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_similar_excludes_seed_document() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+
+        add_doc(&db, "runbooks/deploy.md", vec![embedding(0, 1), embedding(0, 2)]).await;
+        add_doc(&db, "runbooks/rollback.md", vec![embedding(0, 3)]).await;
+        add_doc(&db, "guides/style.md", vec![embedding(5, 6)]).await;
+
+        let seed = resolve_seed(&db, "runbooks/deploy.md", 1536).await.unwrap();
+        assert!(seed.chunk_id.is_none());
+        assert_eq!(seed.doc_chunk_count, 2);
+
+        let results = search_similar(&db, &seed, None, None, 2, 0.0, None).await.unwrap();
+        assert!(results.iter().all(|r| r.doc_path != "runbooks/deploy.md"));
+        assert_eq!(results[0].doc_path, "runbooks/rollback.md");
+        assert_eq!(results[0].rank, 1);
+
+        let chunk_seed = resolve_seed(&db, &results[0].chunk_id, 1536).await.unwrap();
+        assert_eq!(chunk_seed.doc_path, "runbooks/rollback.md");
+        assert!(chunk_seed.chunk_id.is_some());
+
+        assert!(matches!(
+            resolve_seed(&db, "missing.md", 1536).await,
+            Err(RagmcpError::DocumentNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_dimensions_do_not_panic() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Db::new(&db_path);
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        db.with_connection(move |conn| migrate::run_migrations(conn, &migrations_dir))
+            .await
+            .unwrap();
+
+        // Left over from a model with 768 dimensions
+        add_doc(&db, "runbooks/legacy.md", vec![vec![0.1; 768]]).await;
+        add_doc(&db, "runbooks/deploy.md", vec![embedding(0, 1)]).await;
+        add_doc(&db, "runbooks/rollback.md", vec![embedding(0, 3)]).await;
+
+        assert!(matches!(
+            resolve_seed(&db, "runbooks/legacy.md", 1536).await,
+            Err(RagmcpError::InvalidInput(_))
+        ));
+
+        let seed = resolve_seed(&db, "runbooks/deploy.md", 1536).await.unwrap();
+        let results = search_similar(&db, &seed, None, None, 5, 0.0, None).await.unwrap();
+        let paths: Vec<&str> = results.iter().map(|r| r.doc_path.as_str()).collect();
+        assert_eq!(paths, vec!["runbooks/rollback.md"]);
+
+        // With `dimensions = 768` the legacy document is the valid seed and the rest are skipped
+        let legacy = resolve_seed(&db, "runbooks/legacy.md", 768).await.unwrap();
+        assert_eq!(legacy.vector.len(), 768);
+        assert!(matches!(
+            resolve_seed(&db, "runbooks/deploy.md", 768).await,
+            Err(RagmcpError::InvalidInput(_))
+        ));
+        add_doc(&db, "runbooks/legacy-copy.md", vec![vec![0.1; 768]]).await;
+        let results = search_similar(&db, &legacy, None, None, 5, 0.0, None).await.unwrap();
+        let paths: Vec<&str> = results.iter().map(|r| r.doc_path.as_str()).collect();
+        assert_eq!(paths, vec!["runbooks/legacy-copy.md"]);
+    }
+}
@@ -2,6 +2,7 @@ use crate::cache::ChunkEmbeddingCache;
 use crate::db::Db;
 use crate::embeddings::OpenAIEmbedder;
 use crate::error::{Result, RagmcpError};
+use crate::search::filter::{filter_param, metadata_filter_sql, MetadataFilter};
 use crate::search::SearchResult;
 use std::sync::Arc;
 
@@ -15,20 +16,25 @@ use std::sync::Arc;
 ///
 /// * `db` - Database connection wrapper
 /// * `embedder` - OpenAI embedder instance
+/// * `dimensions` - Expected embedding dimension (`[embeddings] dimensions`)
 /// * `query` - Search query text
 /// * `k` - Maximum number of results to return
 /// * `min_score` - Minimum cosine similarity threshold (0.0-1.0)
 /// * `namespace` - Optional namespace filter (documents.namespace = ?)
 /// * `agent_filter` - Optional agent filter (documents.agent_name = ?)
+/// * `metadata_filter` - Optional filter on documents.metadata_json
 /// * `chunk_cache` - Optional in-memory chunk embedding cache for fast path
+#[allow(clippy::too_many_arguments)]
 pub async fn search_vector(
     db: &Db,
     embedder: &OpenAIEmbedder,
+    dimensions: usize,
     query: &str,
     k: usize,
     min_score: f32,
     namespace: Option<&str>,
     agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
     chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
 ) -> Result<Vec<SearchResult>> {
     let _start = std::time::Instant::now();
@@ -38,26 +44,48 @@ pub async fn search_vector(
     let embed_duration = embed_start.elapsed();
     log::debug!("Vector search: query embedding took {:?}", embed_duration);
 
-    if query_vec.len() != 1536 {
+    if query_vec.len() != dimensions {
         return Err(RagmcpError::Embedding(format!(
-            "Unexpected embedding dimension: expected 1536, got {}",
+            "Unexpected embedding dimension: expected {}, got {}",
+            dimensions,
             query_vec.len()
         )));
     }
 
+    search_vector_by_embedding(db, &query_vec, k, min_score, namespace, agent_filter, metadata_filter, chunk_cache)
+        .await
+}
+
+/// Search chunks by a precomputed query vector (e.g. a stored chunk embedding or a
+/// document centroid for "more like this" searches).
+///
+/// Uses the same chunk cache fast path and namespace/agent/metadata filters as `search_vector`.
+#[allow(clippy::too_many_arguments)]
+pub async fn search_vector_by_embedding(
+    db: &Db,
+    query_vec: &[f32],
+    k: usize,
+    min_score: f32,
+    namespace: Option<&str>,
+    agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
+    chunk_cache: Option<Arc<ChunkEmbeddingCache>>,
+) -> Result<Vec<SearchResult>> {
     // Fast path: use chunk cache when available and loaded
     if let Some(ref cache) = chunk_cache {
         cache.load_if_needed(db).await?;
         if cache.is_loaded() && cache.len() > 0 {
-            return search_vector_cached(db, &query_vec, k, min_score, namespace, agent_filter, cache).await;
+            return search_vector_cached(db, query_vec, k, min_score, namespace, agent_filter, metadata_filter, cache)
+                .await;
         }
     }
 
     // Full-scan path: fetch all chunks with embeddings and filter by namespace/agent in SQL
-    search_vector_full_scan(db, &query_vec, k, min_score, namespace, agent_filter).await
+    search_vector_full_scan(db, query_vec, k, min_score, namespace, agent_filter, metadata_filter).await
 }
 
-/// Fast path: score in memory, then one metadata query for top-k chunk_ids (with namespace/agent).
+/// Fast path: score in memory, then one metadata query for top-k chunk_ids (with namespace/agent/metadata).
+#[allow(clippy::too_many_arguments)]
 async fn search_vector_cached(
     db: &Db,
     query_vec: &[f32],
@@ -65,6 +93,7 @@ async fn search_vector_cached(
     min_score: f32,
     namespace: Option<&str>,
     agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
     cache: &ChunkEmbeddingCache,
 ) -> Result<Vec<SearchResult>> {
     let top = cache.top_k_chunk_ids(query_vec, k, min_score);
@@ -75,11 +104,13 @@ async fn search_vector_cached(
     let chunk_ids: Vec<String> = top.iter().map(|(_, id)| id.clone()).collect();
     let ns = namespace.map(String::from);
     let agent = agent_filter.map(String::from);
+    let metadata = filter_param(metadata_filter);
 
     let rows = db
         .with_connection(move |conn| {
             let placeholders = chunk_ids.iter().map(|_| "?").collect::<Vec<_>>().join(",");
-            // Params: [chunk_id...], namespace, namespace, agent, agent (for IS NULL OR col = ?)
+            // Params: [chunk_id...], namespace, namespace, agent, agent (for IS NULL OR col = ?),
+            // metadata filter (twice)
             let sql = format!(
                 r#"
                 SELECT c.chunk_id, c.chunk_text, c.section_header, d.doc_path, d.doc_type, d.agent_name
@@ -88,8 +119,10 @@ async fn search_vector_cached(
                 WHERE c.chunk_id IN ({})
                 AND (? IS NULL OR d.namespace = ?)
                 AND (? IS NULL OR d.agent_name = ?)
+                AND {}
                 "#,
-                placeholders
+                placeholders,
+                metadata_filter_sql("?")
             );
             let mut stmt = conn.prepare(&sql)?;
             let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
@@ -100,6 +133,8 @@ async fn search_vector_cached(
             params.push(Box::new(ns.clone()));
             params.push(Box::new(agent.clone()));
             params.push(Box::new(agent.clone()));
+            params.push(Box::new(metadata.clone()));
+            params.push(Box::new(metadata.clone()));
             let mut rows = stmt.query(rusqlite::params_from_iter(params))?;
             let mut results = Vec::new();
             while let Some(row) = rows.next()? {
@@ -134,6 +169,7 @@ async fn search_vector_cached(
                 chunk_text: chunk_text.clone(),
                 score,
                 rank: 0,
+                boosts: Vec::new(),
             })
         })
         .enumerate()
@@ -154,13 +190,15 @@ async fn search_vector_full_scan(
     min_score: f32,
     namespace: Option<&str>,
     agent_filter: Option<&str>,
+    metadata_filter: Option<&MetadataFilter>,
 ) -> Result<Vec<SearchResult>> {
     let ns = namespace.map(String::from);
     let agent = agent_filter.map(String::from);
+    let metadata = filter_param(metadata_filter);
 
     let rows = db
         .with_connection(move |conn| {
-            let mut stmt = conn.prepare(
+            let mut stmt = conn.prepare(&format!(
                 r#"
                 SELECT
                     c.chunk_id,
@@ -175,9 +213,11 @@ async fn search_vector_full_scan(
                 WHERE c.embedding IS NOT NULL
                 AND (?1 IS NULL OR d.namespace = ?1)
                 AND (?2 IS NULL OR d.agent_name = ?2)
+                AND {}
                 "#,
-            )?;
-            let mut rows = stmt.query(rusqlite::params![ns, agent])?;
+                metadata_filter_sql("?3")
+            ))?;
+            let mut rows = stmt.query(rusqlite::params![ns, agent, metadata])?;
             let mut results = Vec::new();
             while let Some(row) = rows.next()? {
                 let chunk_id: String = row.get(0)?;
@@ -209,7 +249,8 @@ async fn search_vector_full_scan(
             Some(e) => e,
             None => continue,
         };
-        if embedding.len() != 1536 {
+        // Embeddings from another model or `dimensions` setting cannot be compared
+        if embedding.len() != query_vec.len() {
             continue;
         }
         let similarity = cosine_similarity(query_vec, &embedding);
@@ -227,6 +268,7 @@ async fn search_vector_full_scan(
                 chunk_text,
                 score: similarity,
                 rank: 0,
+                boosts: Vec::new(),
             },
         ));
     }
@@ -254,7 +296,7 @@ async fn search_vector_full_scan(
 /// # Returns
 /// 
 /// Some(Vec<f32>) if parsing succeeds, None otherwise
-fn parse_embedding(blob: &[u8]) -> Option<Vec<f32>> {
+pub(crate) fn parse_embedding(blob: &[u8]) -> Option<Vec<f32>> {
     if blob.len() % 4 != 0 {
         return None;
     }
@@ -281,7 +323,7 @@ fn parse_embedding(blob: &[u8]) -> Option<Vec<f32>> {
 /// # Panics
 /// 
 /// Panics if vectors have different lengths (should not happen in normal operation)
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     assert_eq!(
         a.len(),
         b.len(),
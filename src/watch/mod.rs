@@ -1,4 +1,5 @@
-//! File watcher: re-ingest and re-embed when files under rag_folder change.
+//! File watcher: re-ingest and re-embed when files under rag_folder or a `[[sources]]`
+//! root change.
 //!
 //! Uses the notify crate to watch the directory, debounces events, and for each
 //! changed file runs the existing ingest pipeline plus embedding for that doc.
@@ -11,16 +12,24 @@ use std::sync::{Arc, Mutex};
 
 use crate::config::Config;
 use crate::db::Db;
-use crate::embeddings::{get_chunks_without_embedding_for_doc, store_embeddings_batch, OpenAIEmbedder};
+use crate::embeddings::{
+    get_chunks_without_embedding_for_doc, get_documents_without_embedding, store_document_embeddings,
+    store_embeddings_batch, OpenAIEmbedder,
+};
 use crate::error::{Result, RagmcpError};
-use crate::ingest::{compute_file_hash, ingest_file, FileMetadata, ParserRegistry};
-use sha2::{Digest, Sha256};
+use crate::ingest::{
+    compute_file_hash, document_ids_for_source, ingest_file, source_for_file, DiscoveryFilter, FileMetadata,
+    ParserRegistry, Source,
+};
 
 /// Build FileMetadata from an absolute path and the qm_os root.
-/// Returns None if the path is outside root or has an unsupported extension.
-const ALLOWED_EXTENSIONS: &[&str] = &["xml", "yaml", "yml", "json", "md"];
-
-pub fn file_metadata_from_path(absolute_path: &Path, root: &Path) -> Result<Option<FileMetadata>> {
+/// Returns None if the path is outside root or discovery would skip it (unsupported
+/// extension, hidden, ignored, excluded, not included or too large; see `DiscoveryFilter`).
+pub fn file_metadata_from_path(
+    absolute_path: &Path,
+    root: &Path,
+    filter: &DiscoveryFilter,
+) -> Result<Option<FileMetadata>> {
     let root = root
         .canonicalize()
         .map_err(|e| RagmcpError::Config(format!("root canonicalize: {}", e)))?;
@@ -44,15 +53,15 @@ pub fn file_metadata_from_path(absolute_path: &Path, root: &Path) -> Result<Opti
         .unwrap_or("")
         .to_lowercase();
 
-    if !ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
-        return Ok(None);
-    }
-
     if !absolute_path.is_file() {
         return Ok(None);
     }
 
     let metadata = std::fs::metadata(&absolute_path).map_err(RagmcpError::Io)?;
+    if let Some(reason) = filter.check_file(&root, &absolute_path, metadata.len()) {
+        log::debug!("watch: {} skipped ({})", relative_path, reason);
+        return Ok(None);
+    }
     Ok(Some(FileMetadata {
         relative_path,
         absolute_path,
@@ -62,13 +71,17 @@ pub fn file_metadata_from_path(absolute_path: &Path, root: &Path) -> Result<Opti
     }))
 }
 
-/// Check if the document at doc_path has the given hash in the database.
-async fn get_stored_hash_for_path(db: &Db, doc_path: &str) -> Result<Option<String>> {
-    let doc_path = doc_path.to_string();
+/// Stored file hash of the document(s) ingested from the file at path.
+/// Mailbox messages (`path#<part>`) share the hash of their mailbox.
+async fn get_stored_hash_for_path(db: &Db, path: &str) -> Result<Option<String>> {
+    let path = path.to_string();
     let out = db
         .with_connection(move |conn| {
-            let mut stmt = conn.prepare("SELECT file_hash FROM documents WHERE doc_path = ?1")?;
-            let mut rows = stmt.query([&doc_path])?;
+            let mut stmt = conn.prepare(
+                "SELECT file_hash FROM documents \
+                 WHERE doc_path = ?1 OR substr(doc_path, 1, length(?1) + 1) = ?1 || '#' LIMIT 1",
+            )?;
+            let mut rows = stmt.query([&path])?;
             if let Some(row) = rows.next()? {
                 let h: String = row.get(0)?;
                 return Ok(Some(h));
@@ -81,47 +94,39 @@ async fn get_stored_hash_for_path(db: &Db, doc_path: &str) -> Result<Option<Stri
 
 /// Handle a single file change: hash check, ingest if changed, then embed chunks that have no embedding.
 /// When file hash is unchanged we skip re-ingestion but still backfill any chunks with NULL embeddings.
+/// The file is stored under the mount prefix of the source containing it.
 pub async fn handle_file_change(
     db: &Db,
     config: &Config,
-    root: &Path,
+    sources: &[Source],
     path: &Path,
     parser_registry: &ParserRegistry,
     embedder: &OpenAIEmbedder,
 ) -> Result<()> {
     let start = std::time::Instant::now();
 
-    let file = match file_metadata_from_path(path, root)? {
+    let Some(source) = source_for_file(sources, path) else {
+        return Ok(());
+    };
+    let filter = DiscoveryFilter::from_config(&source.ingest_config(&config.ingest))?;
+    let mut file = match file_metadata_from_path(path, &source.root, &filter)? {
         Some(f) => f,
         None => return Ok(()),
     };
+    file.relative_path = source.doc_path(&file.relative_path);
 
-    // doc_id must match insert_document: SHA256 of doc_path (relative_path)
-    let doc_id = format!("{:x}", Sha256::digest(file.relative_path.as_bytes()));
     let current_hash = compute_file_hash(&file.absolute_path)?;
     let stored_hash = get_stored_hash_for_path(db, &file.relative_path).await?;
 
     let hash_unchanged = stored_hash.as_deref() == Some(current_hash.as_str());
 
     if hash_unchanged {
-        // Skip re-ingestion; still backfill any chunks that have NULL embedding for this doc
-        let chunks = get_chunks_without_embedding_for_doc(db, &doc_id).await?;
-        if chunks.is_empty() {
+        // Skip re-ingestion; still backfill any chunks that have NULL embedding for this file
+        let stored = embed_source_documents(db, config, embedder, &file.relative_path).await?;
+        if stored == 0 {
             log::info!("watch: {} skip (unchanged, all embedded)", file.relative_path);
             return Ok(());
         }
-        let batch_size = config.embeddings.batch_size;
-        let mut stored = 0;
-        for batch in chunks.chunks(batch_size) {
-            let texts: Vec<String> = batch.iter().map(|(_, t)| t.clone()).collect();
-            let embeddings = embedder.embed_batch(texts).await?;
-            let pairs: Vec<(String, Vec<f32>)> = batch
-                .iter()
-                .map(|(id, _)| id.clone())
-                .zip(embeddings)
-                .collect();
-            stored += store_embeddings_batch(db, pairs).await?;
-        }
         log::info!(
             "watch: {} skip ingest, backfilled {} embeddings in {:?}",
             file.relative_path,
@@ -132,10 +137,17 @@ pub async fn handle_file_change(
     }
 
     // New or modified: re-ingest then embed only chunks without embeddings
-    ingest_file(db, &file, parser_registry, config).await?;
+    match ingest_file(db, &file, parser_registry, config).await {
+        Ok(_) => {}
+        Err(RagmcpError::Skipped(reason)) => {
+            log::warn!("watch: {} skipped ({})", file.relative_path, reason);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    }
 
-    let chunks = get_chunks_without_embedding_for_doc(db, &doc_id).await?;
-    if chunks.is_empty() {
+    let stored = embed_source_documents(db, config, embedder, &file.relative_path).await?;
+    if stored == 0 {
         log::info!(
             "watch: {} ingested, no new chunks to embed in {:?}",
             file.relative_path,
@@ -144,19 +156,6 @@ pub async fn handle_file_change(
         return Ok(());
     }
 
-    let batch_size = config.embeddings.batch_size;
-    let mut stored = 0;
-    for batch in chunks.chunks(batch_size) {
-        let texts: Vec<String> = batch.iter().map(|(_, t)| t.clone()).collect();
-        let embeddings = embedder.embed_batch(texts).await?;
-        let pairs: Vec<(String, Vec<f32>)> = batch
-            .iter()
-            .map(|(id, _)| id.clone())
-            .zip(embeddings)
-            .collect();
-        stored += store_embeddings_batch(db, pairs).await?;
-    }
-
     log::info!(
         "watch: {} ingested, {} chunks embedded in {:?}",
         file.relative_path,
@@ -166,26 +165,79 @@ pub async fn handle_file_change(
     Ok(())
 }
 
-/// Run the file watcher: spawn watcher thread, then async loop that receives paths
-/// and calls handle_file_change. Runs until the watcher thread exits (e.g. receiver dropped).
+/// Embed the documents ingested from the file at `relative_path` (one per mailbox
+/// message) and their chunks that have no embedding yet. Returns the number of chunk
+/// embeddings stored.
+async fn embed_source_documents(
+    db: &Db,
+    config: &Config,
+    embedder: &OpenAIEmbedder,
+    relative_path: &str,
+) -> Result<usize> {
+    let batch_size = config.embeddings.batch_size;
+    let mut stored = 0;
+    for doc_id in document_ids_for_source(db, relative_path).await? {
+        embed_document_if_missing(db, embedder, &doc_id).await?;
+        let chunks = get_chunks_without_embedding_for_doc(db, &doc_id).await?;
+        for batch in chunks.chunks(batch_size) {
+            let texts: Vec<String> = batch.iter().map(|(_, t)| t.clone()).collect();
+            let embeddings = embedder.embed_batch(texts).await?;
+            let pairs: Vec<(String, Vec<f32>)> = batch
+                .iter()
+                .map(|(id, _)| id.clone())
+                .zip(embeddings)
+                .collect();
+            stored += store_embeddings_batch(db, pairs).await?;
+        }
+    }
+    Ok(stored)
+}
+
+/// Build the document-level embedding (title + summary) if the document has none.
+async fn embed_document_if_missing(db: &Db, embedder: &OpenAIEmbedder, doc_id: &str) -> Result<usize> {
+    let documents = get_documents_without_embedding(db, Some(doc_id)).await?;
+    if documents.is_empty() {
+        return Ok(0);
+    }
+    let texts: Vec<String> = documents.iter().map(|(_, t)| t.clone()).collect();
+    let embeddings = embedder.embed_batch(texts).await?;
+    let pairs: Vec<(String, Vec<f32>)> = documents
+        .into_iter()
+        .map(|(id, _)| id)
+        .zip(embeddings)
+        .collect();
+    store_document_embeddings(db, pairs).await
+}
+
+/// Run the file watcher: spawn one watcher thread per available source root, then async
+/// loop that receives paths and calls handle_file_change. Runs until all watcher threads
+/// exit (e.g. receiver dropped).
 pub async fn run_watcher(
     db: Db,
     config: Config,
     embedder: OpenAIEmbedder,
     debounce_ms: u64,
 ) -> Result<()> {
-    let root = config.rag_folder().to_path_buf();
+    let sources = config.sources();
     let (tx, rx) = mpsc::channel();
     let rx = Arc::new(Mutex::new(rx));
 
-    std::thread::spawn(move || {
-        if let Err(e) = watcher::run_watcher_thread(&root, debounce_ms, tx) {
-            log::error!("watcher thread error: {}", e);
+    for source in &sources {
+        if !source.is_available() {
+            log::warn!("watch: source '{}' unavailable ({}), not watched", source.name(), source.root.display());
+            continue;
         }
-    });
+        let root = source.root.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = watcher::run_watcher_thread(&root, debounce_ms, tx) {
+                log::error!("watcher thread error ({}): {}", root.display(), e);
+            }
+        });
+    }
+    drop(tx);
 
-    let parser_registry = ParserRegistry::new();
-    let root_ref = config.rag_folder().to_path_buf();
+    let parser_registry = ParserRegistry::with_config(&config.ingest);
 
     loop {
         let rx_clone = rx.clone();
@@ -201,7 +253,7 @@ pub async fn run_watcher(
         if let Err(e) = handle_file_change(
             &db,
             &config,
-            &root_ref,
+            &sources,
             &path,
             &parser_registry,
             &embedder,
@@ -220,7 +272,11 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
-    /// Validates that doc_id derived in the watcher (SHA256 of relative_path) matches
+    fn filter() -> DiscoveryFilter {
+        DiscoveryFilter::from_config(&crate::config::IngestConfig::default()).unwrap()
+    }
+
+    /// Validates that the doc_id looked up by the watcher (by relative_path) matches
     /// insert_document, and that get_chunks_without_embedding_for_doc + store_embeddings_batch
     /// correctly backfill NULL embeddings for that doc (no OpenAI API).
     #[tokio::test]
@@ -260,22 +316,26 @@ mod tests {
                 tokens: 2,
                 section_header: Some("A".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
             Chunk {
                 text: "Chunk two".to_string(),
                 tokens: 2,
                 section_header: Some("B".to_string()),
                 chunk_type: Some("test".to_string()),
+                page: None,
             },
         ];
         insert_chunks(&db, &doc_id_from_insert, chunks).await.unwrap();
 
-        // Watcher uses the same doc_id derivation as insert_document (SHA256 of doc_path)
-        let doc_id_watcher = format!("{:x}", Sha256::digest(relative_path.as_bytes()));
+        // Watcher looks up the doc_ids ingested from the changed file by its relative path
+        let doc_ids = document_ids_for_source(&db, relative_path).await.unwrap();
         assert_eq!(
-            doc_id_from_insert, doc_id_watcher,
-            "doc_id from insert_document must match watcher derivation"
+            doc_ids,
+            vec![doc_id_from_insert.clone()],
+            "doc_id from insert_document must match watcher lookup"
         );
+        let doc_id_watcher = doc_ids[0].clone();
 
         let without_before = get_chunks_without_embedding_for_doc(&db, &doc_id_watcher).await.unwrap();
         assert_eq!(without_before.len(), 2, "both chunks should have NULL embedding before backfill");
@@ -301,7 +361,7 @@ mod tests {
         let file_path = sub.join("doc.xml");
         fs::write(&file_path, "<root/>").unwrap();
 
-        let meta = file_metadata_from_path(&file_path, root.path()).unwrap();
+        let meta = file_metadata_from_path(&file_path, root.path(), &filter()).unwrap();
         let meta = meta.expect("expected Some(FileMetadata)");
         assert_eq!(meta.relative_path, "sub/doc.xml");
         assert_eq!(meta.extension, "xml");
@@ -315,17 +375,17 @@ mod tests {
         let file_path = other.path().join("doc.xml");
         fs::write(&file_path, "<root/>").unwrap();
 
-        let meta = file_metadata_from_path(&file_path, root.path()).unwrap();
+        let meta = file_metadata_from_path(&file_path, root.path(), &filter()).unwrap();
         assert!(meta.is_none());
     }
 
     #[test]
     fn test_file_metadata_from_path_unsupported_extension_returns_none() {
         let root = TempDir::new().unwrap();
-        let file_path = root.path().join("readme.txt");
-        fs::write(&file_path, "text").unwrap();
+        let file_path = root.path().join("diagram.png");
+        fs::write(&file_path, "png").unwrap();
 
-        let meta = file_metadata_from_path(&file_path, root.path()).unwrap();
+        let meta = file_metadata_from_path(&file_path, root.path(), &filter()).unwrap();
         assert!(meta.is_none());
     }
 }